@@ -33,3 +33,13 @@ fn endofgame(bencher: divan::Bencher, file: &str) {
 
     bencher.bench(|| analysis::endofgame::parse(divan::black_box(&data)));
 }
+
+#[divan::bench(args = ["dust2.dem", "inferno.dem", "nuke.dem"])]
+fn metadata(bencher: divan::Bencher, file: &str) {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../testfiles/")
+        .join(file);
+    let data = std::fs::read(path).unwrap();
+
+    bencher.bench(|| analysis::metadata::parse(divan::black_box(&data)));
+}
@@ -0,0 +1,71 @@
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct AdvantageStats {
+    /// The team number the team started the match with
+    pub team: i32,
+    /// The number of players the team had alive
+    pub players: usize,
+    /// The number of players the enemy team had alive
+    pub enemies: usize,
+    /// The number of rounds in which the team got into the situation
+    pub rounds: usize,
+    /// The rounds that were converted into a win
+    pub won: usize,
+    /// The rounds that were lost despite the advantage
+    pub lost: usize,
+}
+
+/// Counts how often every team converted or threw a man advantage, like a 5v4 or 4v3. A round
+/// counts once for every situation the team was in during it. The result is ordered by team and
+/// then by the size of the situation, starting with 5v4
+pub fn advantage_stats(rounds: &[crate::perround::Round]) -> Vec<AdvantageStats> {
+    let starting_teams = crate::roundtypes::starting_teams(rounds);
+
+    let mut stats = std::collections::HashMap::<(i32, usize, usize), AdvantageStats>::new();
+    for round in rounds {
+        let winner = round.winreason.winning_team();
+
+        for side in [2, 3] {
+            let team = match crate::roundtypes::side_team(round, &starting_teams, side) {
+                Some(t) => t,
+                None => continue,
+            };
+
+            let situations: std::collections::BTreeSet<(usize, usize)> = round
+                .alive
+                .iter()
+                .map(|a| if side == 2 { (a.t, a.ct) } else { (a.ct, a.t) })
+                .filter(|(players, enemies)| players > enemies && *enemies > 0)
+                .collect();
+
+            for (players, enemies) in situations {
+                let entry =
+                    stats
+                        .entry((team, players, enemies))
+                        .or_insert_with(|| AdvantageStats {
+                            team,
+                            players,
+                            enemies,
+                            rounds: 0,
+                            won: 0,
+                            lost: 0,
+                        });
+                entry.rounds += 1;
+                match winner {
+                    Some(w) if w == side => entry.won += 1,
+                    Some(_) => entry.lost += 1,
+                    None => {}
+                };
+            }
+        }
+    }
+
+    let mut result: Vec<_> = stats.into_values().collect();
+    result.sort_unstable_by_key(|s| {
+        (
+            s.team,
+            core::cmp::Reverse(s.players),
+            core::cmp::Reverse(s.enemies),
+        )
+    });
+    result
+}
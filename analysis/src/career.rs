@@ -0,0 +1,235 @@
+//! Statistics of a single player summed up across several demos, based on the results of the per
+//! round analysis.
+
+/// The average kills per round, survived rounds per round and multi kill rating per round, used to
+/// normalise the rating so an average player ends up at 1.0
+const AVERAGE_KPR: f32 = 0.679;
+const AVERAGE_SPR: f32 = 0.317;
+const AVERAGE_RMK: f32 = 1.277;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PlayerStats {
+    pub matches: usize,
+    pub wins: usize,
+    pub losses: usize,
+    pub rounds: usize,
+    pub rounds_won: usize,
+    pub kills: usize,
+    pub deaths: usize,
+    pub assists: usize,
+    pub damage: usize,
+    pub headshots: usize,
+    /// The number of rounds with 1 to 5 kills
+    pub multi_kills: [usize; 5],
+    /// The rounds, kills and deaths of the demos with a per round analysis. The headshots, multi
+    /// kills and won rounds are only known for those, so the numbers based on them use these
+    /// instead of the totals that also include the scoreboard of the other demos
+    pub detailed_rounds: usize,
+    pub detailed_kills: usize,
+    pub detailed_deaths: usize,
+}
+
+impl PlayerStats {
+    pub fn add(&mut self, other: &Self) {
+        self.matches += other.matches;
+        self.wins += other.wins;
+        self.losses += other.losses;
+        self.rounds += other.rounds;
+        self.rounds_won += other.rounds_won;
+        self.kills += other.kills;
+        self.deaths += other.deaths;
+        self.assists += other.assists;
+        self.damage += other.damage;
+        self.headshots += other.headshots;
+        for (count, other) in self.multi_kills.iter_mut().zip(other.multi_kills.iter()) {
+            *count += other;
+        }
+        self.detailed_rounds += other.detailed_rounds;
+        self.detailed_kills += other.detailed_kills;
+        self.detailed_deaths += other.detailed_deaths;
+    }
+
+    pub fn kd(&self) -> f32 {
+        self.kills as f32 / self.deaths.max(1) as f32
+    }
+
+    /// The average damage per round
+    pub fn adr(&self) -> f32 {
+        ratio(self.damage, self.rounds)
+    }
+
+    pub fn headshot_percentage(&self) -> f32 {
+        ratio(self.headshots, self.detailed_kills) * 100.0
+    }
+
+    /// The percentage of matches won, draws count as played but neither won nor lost
+    pub fn win_rate(&self) -> f32 {
+        ratio(self.wins, self.matches) * 100.0
+    }
+
+    pub fn round_win_rate(&self) -> f32 {
+        ratio(self.rounds_won, self.detailed_rounds) * 100.0
+    }
+
+    /// The rating based on kills, survived rounds and multi kill rounds per round, like the HLTV
+    /// 1.0 rating
+    pub fn rating(&self) -> f32 {
+        if self.detailed_rounds == 0 {
+            return 0.0;
+        }
+
+        let rounds = self.detailed_rounds as f32;
+        let kpr = self.detailed_kills as f32 / rounds;
+        let spr = self.detailed_rounds.saturating_sub(self.detailed_deaths) as f32 / rounds;
+        let rmk = self
+            .multi_kills
+            .iter()
+            .enumerate()
+            .map(|(idx, count)| ((idx + 1) * (idx + 1) * count) as f32)
+            .sum::<f32>()
+            / rounds;
+
+        (kpr / AVERAGE_KPR + 0.7 * spr / AVERAGE_SPR + rmk / AVERAGE_RMK) / 2.7
+    }
+}
+
+/// The stats of the player in the rounds of a single demo, split by the side they played on (2 for
+/// T and 3 for CT). Only the round based numbers are filled in, the match results and assists have
+/// to come from the scoreboard of the demo
+pub fn side_stats(
+    rounds: &[crate::perround::Round],
+    steam_id: u64,
+) -> std::collections::BTreeMap<i32, PlayerStats> {
+    let mut sides = std::collections::BTreeMap::<i32, PlayerStats>::new();
+
+    for round in rounds {
+        let player = match round.players.iter().find(|p| p.steam_id == steam_id) {
+            Some(p) => p,
+            None => continue,
+        };
+
+        let stats = sides.entry(player.team).or_default();
+        stats.rounds += 1;
+        stats.detailed_rounds += 1;
+        stats.rounds_won += (round.winreason.winning_team() == Some(player.team)) as usize;
+        stats.kills += player.kills;
+        stats.detailed_kills += player.kills;
+        stats.deaths += (!player.survived) as usize;
+        stats.detailed_deaths += (!player.survived) as usize;
+        stats.damage += player.damage_dealt;
+        stats.headshots += round
+            .events
+            .iter()
+            .filter(|event| match event {
+                crate::perround::RoundEvent::Kill {
+                    attacker,
+                    died,
+                    headshot,
+                    ..
+                } => *headshot && *attacker == steam_id && *died != steam_id,
+                _ => false,
+            })
+            .count();
+        if (1..=5).contains(&player.kills) {
+            stats.multi_kills[player.kills - 1] += 1;
+        }
+    }
+
+    sides
+}
+
+fn ratio(value: usize, total: usize) -> f32 {
+    if total == 0 {
+        0.0
+    } else {
+        value as f32 / total as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn average_rating() {
+        let stats = PlayerStats {
+            rounds: 100,
+            kills: 68,
+            deaths: 68,
+            multi_kills: [30, 13, 4, 0, 0],
+            detailed_rounds: 100,
+            detailed_kills: 68,
+            detailed_deaths: 68,
+            ..Default::default()
+        };
+
+        assert!((stats.rating() - 1.0).abs() < 0.05, "{}", stats.rating());
+        assert_eq!(1.0, stats.kd());
+        assert_eq!(0.0, stats.adr());
+    }
+
+    #[test]
+    fn add() {
+        let mut total = PlayerStats::default();
+        let stats = PlayerStats {
+            matches: 1,
+            wins: 1,
+            rounds: 20,
+            kills: 15,
+            headshots: 6,
+            damage: 1600,
+            multi_kills: [5, 3, 0, 0, 1],
+            detailed_rounds: 20,
+            detailed_kills: 15,
+            ..Default::default()
+        };
+        total.add(&stats);
+        total.add(&stats);
+
+        assert_eq!(2, total.matches);
+        assert_eq!(30, total.kills);
+        assert_eq!([10, 6, 0, 0, 2], total.multi_kills);
+        assert_eq!(80.0, total.adr());
+        assert_eq!(40.0, total.headshot_percentage());
+        assert_eq!(100.0, total.win_rate());
+    }
+
+    #[test]
+    fn demo_without_rounds() {
+        let detailed = PlayerStats {
+            matches: 1,
+            rounds: 20,
+            rounds_won: 10,
+            kills: 20,
+            deaths: 10,
+            headshots: 10,
+            multi_kills: [6, 4, 2, 0, 0],
+            detailed_rounds: 20,
+            detailed_kills: 20,
+            detailed_deaths: 10,
+            ..Default::default()
+        };
+        let scoreboard = PlayerStats {
+            matches: 1,
+            rounds: 20,
+            kills: 10,
+            deaths: 20,
+            assists: 4,
+            damage: 1200,
+            ..Default::default()
+        };
+
+        let mut total = PlayerStats::default();
+        total.add(&detailed);
+        total.add(&scoreboard);
+
+        assert_eq!(30, total.kills);
+        assert_eq!(30, total.deaths);
+        assert_eq!(4, total.assists);
+        assert_eq!(1.0, total.kd());
+        assert_eq!(30.0, total.adr());
+        assert_eq!(50.0, total.headshot_percentage());
+        assert_eq!(50.0, total.round_win_rate());
+        assert_eq!(detailed.rating(), total.rating());
+    }
+}
@@ -1,17 +1,27 @@
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct EndOfGame {
     pub map: String,
     pub players: Vec<(PlayerInfo, PlayerStats)>,
     pub teams: std::collections::HashMap<i32, TeamInfo>,
+    /// The stats where the numbers tracked by the game differ from the ones computed from the
+    /// events, the players use the numbers of the game in that case
+    #[serde(default)]
+    pub stat_mismatches: Vec<StatMismatch>,
+    /// Whether the demo could be read until the end of the match, the stats only cover the part
+    /// of the match that was recorded otherwise
+    pub complete: bool,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct TeamInfo {
     pub end_score: usize,
     pub start_side: String,
+    /// The name of the clan or team, only set in league and tournament demos
+    #[serde(default)]
+    pub clan_name: Option<String>,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct PlayerInfo {
     pub name: String,
     pub steam_id: String,
@@ -20,7 +30,7 @@ pub struct PlayerInfo {
     pub ingame_id: i32,
 }
 
-#[derive(Debug, Default, PartialEq)]
+#[derive(Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct PlayerStats {
     pub kills: usize,
     pub deaths: usize,
@@ -31,6 +41,27 @@ pub struct PlayerStats {
     pub self_damage: usize,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct StatMismatch {
+    pub steam_id: u64,
+    /// `kills`, `deaths`, `assists` or `damage`
+    pub stat: String,
+    /// The value computed from the events
+    pub events: usize,
+    /// The value tracked by the player controller
+    pub controller: usize,
+}
+
+/// The state of a player controller, including the stats the game itself tracks for the
+/// scoreboard in its action tracking services
+#[derive(Debug, Default)]
+struct ControllerState {
+    kills: Option<usize>,
+    deaths: Option<usize>,
+    assists: Option<usize>,
+    damage: Option<usize>,
+}
+
 pub fn parse(buf: &[u8]) -> Result<EndOfGame, ()> {
     let tmp = csdemo::Container::parse(buf).map_err(|e| ())?;
     let output = csdemo::lazyparser::LazyParser::new(tmp);
@@ -44,9 +75,17 @@ pub fn parse(buf: &[u8]) -> Result<EndOfGame, ()> {
 
     let mut track = false;
     let mut player_life = std::collections::HashMap::<_, u8>::new();
-    for event in output.events().filter_map(|e| e.ok()) {
+    let mut completeness = crate::Completeness::default();
+    for event in output.events() {
+        let event = match completeness.ok(event) {
+            Some(e) => e,
+            None => continue,
+        };
+
         match event {
             csdemo::DemoEvent::GameEvent(gevent) => {
+                completeness.event(gevent.as_ref());
+
                 match gevent.as_ref() {
                     csdemo::game_event::GameEvent::RoundAnnounceMatchStart(_) => {
                         player_stats.clear();
@@ -56,7 +95,10 @@ pub fn parse(buf: &[u8]) -> Result<EndOfGame, ()> {
                         track = true;
                     }
                     csdemo::game_event::GameEvent::PlayerSpawn(pspawn) => {
-                        let userid = pspawn.userid.unwrap();
+                        let userid = match pspawn.userid {
+                            Some(u) => u,
+                            None => continue,
+                        };
 
                         player_life.insert(userid.clone(), 100);
 
@@ -96,34 +138,66 @@ pub fn parse(buf: &[u8]) -> Result<EndOfGame, ()> {
     let mut teams = std::collections::HashMap::<i32, TeamInfo>::new();
 
     let mut entity_to_team = std::collections::HashMap::new();
-    for (tick, state) in output.entities().filter_map(|e| e.ok()) {
+    let mut controller_players = std::collections::HashMap::<i32, u64>::new();
+    let mut controllers = std::collections::HashMap::<u64, ControllerState>::new();
+    let mut participants = crate::players::Participants::default();
+    let mut clan_names = std::collections::HashMap::new();
+    for entity in output.entities() {
+        let (tick, state) = match completeness.ok(entity) {
+            Some(e) => e,
+            None => continue,
+        };
+
+        if state.class.as_ref() == "CCSPlayerController" {
+            participants.update(&state);
+            update_controller(&state, &mut controller_players, &mut controllers);
+            continue;
+        }
+
         let team = match csdemo::structured::ccsteam::CCSTeam::try_from(&state) {
             Ok(t) => t,
             Err(_) => continue,
         };
 
+        // The clan names are swapped between the team entities at halftime, together with the
+        // players
+        if let Some(csdemo::parser::Variant::String(name)) =
+            state.get_prop("CCSTeam.m_szClanTeamname").map(|p| &p.value)
+        {
+            clan_names.insert(team.entity_id(), name.clone());
+        }
+        let clan_name = clan_names
+            .get(&team.entity_id())
+            .filter(|n| !n.is_empty())
+            .cloned();
+
         let pawns = team.player_pawns();
         let player_ids = pawns
             .into_iter()
             .filter_map(|pawn| pawn_to_player.get(&pawn))
             .collect::<Vec<_>>();
         if player_ids.is_empty() {
-            if let Some(team_number) = entity_to_team.get(&team.entity_id()) {
+            if let Some(team_entry) = entity_to_team
+                .get(&team.entity_id())
+                .and_then(|team_number| teams.get_mut(team_number))
+            {
                 if let Some(score) = team.score() {
-                    if let Some(team_entry) = teams.get_mut(team_number) {
-                        team_entry.end_score = score as usize;
-                    }
+                    team_entry.end_score = score as usize;
                 }
+                team_entry.clan_name = clan_name.or(team_entry.clan_name.take());
             }
 
             continue;
         }
 
-        let team_number = player_ids
+        let team_number = match player_ids
             .iter()
             .filter_map(|p| player_info.get(*p).map(|p| p.team))
             .next()
-            .unwrap();
+        {
+            Some(t) => t,
+            None => continue,
+        };
 
         entity_to_team.insert(team.entity_id(), team_number);
 
@@ -133,29 +207,70 @@ pub fn parse(buf: &[u8]) -> Result<EndOfGame, ()> {
                 .team_name()
                 .map(|t| t.to_owned())
                 .unwrap_or(String::new()),
+            clan_name: None,
         });
         if let Some(score) = team.score() {
             team_entry.end_score = score as usize;
         }
+        team_entry.clan_name = clan_name.or(team_entry.clan_name.take());
     }
 
-    let mut players: Vec<_> = player_stats
-        .into_iter()
-        .filter_map(|(id, stats)| {
-            let player = player_info.get(&id)?;
-
-            Some((
-                PlayerInfo {
-                    name: player.name.clone(),
-                    steam_id: player.xuid.to_string(),
-                    team: player.team,
-                    color: player.color,
-                    ingame_id: id.0,
-                },
-                stats,
-            ))
-        })
-        .collect();
+    let mut merged_stats = merge_stats(player_stats.into_iter().filter_map(|(id, stats)| {
+        let player = player_info.get(&id)?;
+        // Coaches and the casters of GOTV demos are connected like players, but never play
+        if !participants.played(player.xuid) {
+            return None;
+        }
+
+        let info = PlayerInfo {
+            name: player.name.clone(),
+            steam_id: player.xuid.to_string(),
+            team: player.team,
+            color: player.color,
+            ingame_id: id.0,
+        };
+        Some((player.xuid, info, stats))
+    }));
+
+    // The numbers of the game are the ones shown on the ingame scoreboard, so they take
+    // precedence over the ones computed from the events. They are the totals of the whole match
+    // though, so for a demo that was cut short they don't match the rounds that were recorded
+    let complete = completeness.complete();
+    let mut stat_mismatches = Vec::new();
+    for (steam_id, (_, stats)) in merged_stats.iter_mut().filter(|_| complete) {
+        let game_stats = match controllers.get(steam_id) {
+            Some(s) => s,
+            None => continue,
+        };
+
+        for (stat, value, game_value) in [
+            ("kills", &mut stats.kills, game_stats.kills),
+            ("deaths", &mut stats.deaths, game_stats.deaths),
+            ("assists", &mut stats.assists, game_stats.assists),
+            ("damage", &mut stats.damage, game_stats.damage),
+        ] {
+            let game_value = match game_value {
+                Some(v) => v,
+                None => continue,
+            };
+            if *value != game_value {
+                stat_mismatches.push(StatMismatch {
+                    steam_id: *steam_id,
+                    stat: stat.to_owned(),
+                    events: *value,
+                    controller: game_value,
+                });
+                *value = game_value;
+            }
+        }
+    }
+    stat_mismatches.sort_unstable_by(|m1, m2| {
+        m1.steam_id
+            .cmp(&m2.steam_id)
+            .then_with(|| m1.stat.cmp(&m2.stat))
+    });
+
+    let mut players: Vec<_> = merged_stats.into_values().collect();
     players.sort_unstable_by_key(|(p, _)| p.ingame_id);
 
     let map = header.map_name().to_owned();
@@ -164,20 +279,90 @@ pub fn parse(buf: &[u8]) -> Result<EndOfGame, ()> {
         map,
         players,
         teams,
+        stat_mismatches,
+        complete,
     })
 }
 
+/// A player gets a new ingame id every time they reconnect, so the stats need to be merged by their
+/// steam id. The merged player keeps their first ingame id
+fn merge_stats(
+    stats: impl IntoIterator<Item = (u64, PlayerInfo, PlayerStats)>,
+) -> std::collections::HashMap<u64, (PlayerInfo, PlayerStats)> {
+    let mut merged_stats = std::collections::HashMap::<u64, (PlayerInfo, PlayerStats)>::new();
+    for (steam_id, player, stats) in stats {
+        let ingame_id = player.ingame_id;
+        let (info, merged) = merged_stats
+            .entry(steam_id)
+            .or_insert_with(|| (player, PlayerStats::default()));
+        info.ingame_id = info.ingame_id.min(ingame_id);
+
+        merged.kills += stats.kills;
+        merged.deaths += stats.deaths;
+        merged.damage += stats.damage;
+        merged.assists += stats.assists;
+        merged.team_kills += stats.team_kills;
+        merged.team_damage += stats.team_damage;
+        merged.self_damage += stats.self_damage;
+    }
+    merged_stats
+}
+
+fn update_controller(
+    state: &csdemo::parser::entities::EntityState,
+    controller_players: &mut std::collections::HashMap<i32, u64>,
+    controllers: &mut std::collections::HashMap<u64, ControllerState>,
+) {
+    if let Some(csdemo::parser::Variant::U64(steam_id)) = state
+        .get_prop("CCSPlayerController.m_steamID")
+        .map(|p| &p.value)
+    {
+        if *steam_id != 0 {
+            controller_players.insert(state.id, *steam_id);
+        }
+    }
+    let controller = match controller_players.get(&state.id) {
+        Some(steam_id) => controllers.entry(*steam_id).or_default(),
+        None => return,
+    };
+
+    for prop in state.props.iter() {
+        let value = match prop.value.as_i32() {
+            Some(v) => v,
+            None => continue,
+        };
+
+        let field = match prop.prop_info.prop_name.rsplit_once(".m_matchStats.") {
+            Some((_, field)) => field,
+            None => continue,
+        };
+        let value = Some(value.max(0) as usize);
+
+        match field {
+            "m_iKills" => controller.kills = value,
+            "m_iDeaths" => controller.deaths = value,
+            "m_iAssists" => controller.assists = value,
+            "m_iDamage" => controller.damage = value,
+            _ => {}
+        };
+    }
+}
+
 fn player_death(
     death: &csdemo::game_event::PlayerDeath,
     player_info: &std::collections::HashMap<csdemo::UserId, csdemo::parser::Player>,
     player_stats: &mut std::collections::HashMap<csdemo::UserId, PlayerStats>,
 ) {
-    let player_died_id = death.userid.unwrap();
-
-    let player_died_player = player_info.get(&player_died_id).unwrap();
+    let (player_died_id, player_died_player) = match death
+        .userid
+        .and_then(|u| player_info.get(&u).map(|p| (u, p)))
+    {
+        Some(p) => p,
+        None => return,
+    };
     let player_died = player_stats.entry(player_died_id).or_default();
 
-    let attacker_id = match death.attacker.filter(|p| p.0 < 10) {
+    let attacker_id = match death.attacker.filter(|p| player_info.contains_key(p)) {
         Some(a) => a,
         None => {
             return;
@@ -186,9 +371,10 @@ fn player_death(
 
     player_died.deaths += 1;
 
-    let attacker_player = player_info
-        .get(&attacker_id)
-        .expect(&format!("Attacker-ID: {:?}", attacker_id));
+    let attacker_player = match player_info.get(&attacker_id) {
+        Some(p) => p,
+        None => return,
+    };
     if attacker_player.xuid == player_died_player.xuid {
         // TODO
         // Player committed Suicide
@@ -201,11 +387,10 @@ fn player_death(
         attacker.kills += 1;
     }
 
-    if let Some(assist_id) = death.assister.filter(|p| p.0 < 10) {
-        let assister_player = player_info
-            .get(&assist_id)
-            .expect(&format!("Assister-ID: {:?}", assist_id));
-
+    if let Some((assist_id, assister_player)) = death
+        .assister
+        .and_then(|a| player_info.get(&a).map(|p| (a, p)))
+    {
         if assister_player.team == player_died_player.team {
         } else {
             let assister = player_stats.entry(assist_id).or_default();
@@ -220,7 +405,10 @@ fn player_hurt(
     player_stats: &mut std::collections::HashMap<csdemo::UserId, PlayerStats>,
     player_life: &mut std::collections::HashMap<csdemo::UserId, u8>,
 ) {
-    let attacked_player = match player_info.get(hurt.userid.as_ref().unwrap()) {
+    let (attacked_id, attacked_player) = match hurt
+        .userid
+        .and_then(|u| player_info.get(&u).map(|p| (u, p)))
+    {
         Some(a) => a,
         None => {
             return;
@@ -240,13 +428,11 @@ fn player_hurt(
         Some(csdemo::RawValue::U64(v)) => v as u8,
         _ => 0,
     };
-    let previous_health = player_life
-        .get(hurt.userid.as_ref().unwrap())
-        .copied()
-        .unwrap();
-    let dmg_dealt = previous_health - n_health;
+    // Players that were already alive when the recording started never had a spawn event
+    let previous_health = player_life.get(&attacked_id).copied().unwrap_or(100);
+    let dmg_dealt = previous_health.saturating_sub(n_health);
 
-    player_life.insert(hurt.userid.unwrap(), n_health);
+    player_life.insert(attacked_id, n_health);
 
     if let Some(attacking_player) = player_info.get(&attacker_id) {
         let attacker = player_stats.entry(attacker_id).or_default();
@@ -260,3 +446,41 @@ fn player_hurt(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(ingame_id: i32) -> PlayerInfo {
+        PlayerInfo {
+            name: "player".to_owned(),
+            steam_id: "76561198000000001".to_owned(),
+            team: 2,
+            color: 0,
+            ingame_id,
+        }
+    }
+
+    #[test]
+    fn merge_reconnect() {
+        let stats = |kills, deaths, damage| PlayerStats {
+            kills,
+            deaths,
+            damage,
+            ..Default::default()
+        };
+
+        let merged = merge_stats([
+            (76561198000000001, info(11), stats(7, 4, 650)),
+            (76561198000000001, info(2), stats(5, 6, 480)),
+            (76561198000000002, info(3), stats(9, 8, 1100)),
+        ]);
+        assert_eq!(2, merged.len());
+
+        let (info, stats) = merged.get(&76561198000000001).unwrap();
+        assert_eq!(2, info.ingame_id);
+        assert_eq!(12, stats.kills);
+        assert_eq!(10, stats.deaths);
+        assert_eq!(1130, stats.damage);
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+use crate::heatmap::PawnID;
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct GrenadeThrow {
+    /// The round the grenade was thrown in, starting at 0
+    pub round: usize,
+    /// The name of the projectile, like `SmokeGrenade` or `Molotov`
+    pub kind: String,
+    pub thrower: Option<u64>,
+    pub team: u32,
+    pub throw_tick: u32,
+    /// The first position of the projectile, in the same coordinates as the heatmaps
+    pub throw_position: (f32, f32, f32),
+    pub land_tick: u32,
+    /// The last position of the projectile, where it landed or detonated
+    pub land_position: (f32, f32, f32),
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct Grenades {
+    /// All the grenades thrown during the rounds, ordered by the time they were thrown
+    pub grenades: Vec<GrenadeThrow>,
+    /// Whether the demo could be read until the end of the match, only the rounds that were
+    /// finished are returned otherwise
+    pub complete: bool,
+}
+
+/// The kind of grenade for the entity class, `None` if the class is not a grenade projectile
+pub(crate) fn projectile_kind(class: &str) -> Option<&str> {
+    class
+        .strip_suffix("Projectile")
+        .map(|c| c.trim_start_matches('C'))
+}
+
+/// The pawn of the player who threw the projectile, if the update contains it
+pub(crate) fn projectile_thrower(state: &csdemo::parser::entities::EntityState) -> Option<PawnID> {
+    state
+        .props
+        .iter()
+        .find(|p| p.prop_info.prop_name.ends_with(".m_hThrower"))
+        .and_then(|p| p.value.as_u32())
+        .map(PawnID::from)
+}
+
+/// Whether an update of a projectile entity still belongs to the grenade of its last update, as
+/// entity ids are reused for later grenades.
+///
+/// A projectile does not stop getting updates for longer than a second while it is flying, so an
+/// update after a longer gap belongs to a new grenade. Grenades that already landed only get
+/// updates when they detonate though, but stay in place.
+pub(crate) fn same_grenade(
+    last: (u32, (f32, f32, f32)),
+    tick: u32,
+    position: (f32, f32, f32),
+    tick_rate: u32,
+) -> bool {
+    let (last_tick, last_position) = last;
+    tick.saturating_sub(last_tick) <= tick_rate || last_position == position
+}
+
+pub fn parse(buf: &[u8]) -> Result<Grenades, ()> {
+    let tmp = csdemo::Container::parse(buf).map_err(|e| ())?;
+    let output = csdemo::lazyparser::LazyParser::new(tmp);
+
+    let tick_rate = crate::metadata::tick_rate(output.events());
+
+    let mut controllers = HashMap::new();
+    let mut pawn_players = HashMap::<PawnID, u64>::new();
+    let mut entity_cells = HashMap::new();
+    let mut entity_offsets = HashMap::new();
+    // The grenade that each projectile entity currently belongs to
+    let mut projectiles = HashMap::<PawnID, usize>::new();
+
+    let mut grenades: Vec<GrenadeThrow> = Vec::new();
+    let mut rounds = 0;
+    let mut in_round = false;
+    let mut completeness = crate::Completeness::default();
+    for entity in output.entities() {
+        let (tick, state) = match completeness.ok(entity) {
+            Some(e) => e,
+            None => continue,
+        };
+        completeness.entity(&state);
+
+        let class: &str = state.class.as_ref();
+        match class {
+            "CCSPlayerController" => {
+                crate::heatmap::update_pawn_players(&state, &mut controllers, &mut pawn_players);
+            }
+            "CCSGameRulesProxy" => {
+                let count = |name: &str| state.get_prop(name).and_then(|p| p.value.as_u32());
+
+                if let Some(count) = count("CCSGameRulesProxy.CCSGameRules.m_nRoundStartCount") {
+                    if rounds < count.saturating_sub(1) as usize {
+                        rounds += 1;
+                        in_round = true;
+                        projectiles.clear();
+                    }
+                }
+                if let Some(count) = count("CCSGameRulesProxy.CCSGameRules.m_nRoundEndCount") {
+                    if rounds == count.saturating_sub(1) as usize {
+                        in_round = false;
+                    }
+                }
+            }
+            _ => {
+                let kind = match projectile_kind(class) {
+                    Some(k) if in_round => k,
+                    _ => continue,
+                };
+
+                let entity_id = PawnID::from(state.id);
+                let position =
+                    crate::heatmap::update_position(&state, &mut entity_cells, &mut entity_offsets);
+
+                let previous = projectiles.get(&entity_id).copied().filter(|idx| {
+                    let grenade = &grenades[*idx];
+                    let last = (grenade.land_tick, grenade.land_position);
+                    same_grenade(last, tick, position, tick_rate)
+                });
+                let idx = match previous {
+                    Some(idx) => idx,
+                    None => {
+                        grenades.push(GrenadeThrow {
+                            round: rounds - 1,
+                            kind: kind.to_owned(),
+                            thrower: None,
+                            team: 0,
+                            throw_tick: tick,
+                            throw_position: position,
+                            land_tick: tick,
+                            land_position: position,
+                        });
+                        projectiles.insert(entity_id, grenades.len() - 1);
+                        grenades.len() - 1
+                    }
+                };
+                let grenade = &mut grenades[idx];
+
+                let thrower = projectile_thrower(&state).and_then(|p| pawn_players.get(&p));
+                if let Some(thrower) = thrower {
+                    grenade.thrower = Some(*thrower);
+                }
+                let team = state
+                    .props
+                    .iter()
+                    .find(|p| p.prop_info.prop_name.ends_with(".m_iTeamNum"))
+                    .and_then(|p| p.value.as_u32());
+                if let Some(team) = team {
+                    grenade.team = team;
+                }
+
+                grenade.land_tick = tick;
+                grenade.land_position = position;
+            }
+        };
+    }
+
+    let complete = completeness.complete();
+    if !complete && in_round {
+        // The round that was being played when the demo was cut off never ended
+        grenades.retain(|g| g.round + 1 < rounds);
+    }
+
+    Ok(Grenades { grenades, complete })
+}
@@ -1,9 +1,19 @@
 use std::collections::HashMap;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Player {
+    pub name: String,
+    pub team: i32,
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct Output {
-    pub players: HashMap<csdemo::UserId, csdemo::parser::Player>,
-    pub head_to_head: HashMap<csdemo::UserId, HashMap<csdemo::UserId, usize>>,
+    /// All the players by their steam id
+    pub players: HashMap<u64, Player>,
+    /// The kills of every player against each of their enemies, by steam id
+    pub head_to_head: HashMap<u64, HashMap<u64, usize>>,
+    /// Whether the demo could be read until the end of the match
+    pub complete: bool,
 }
 
 pub fn parse(buf: &[u8]) -> Result<Output, ()> {
@@ -11,24 +21,32 @@ pub fn parse(buf: &[u8]) -> Result<Output, ()> {
 
     let output = csdemo::lazyparser::LazyParser::new(tmp);
 
-    let players = output.player_info();
+    let player_info = output.player_info();
+    let mut players: HashMap<u64, Player> = player_info.values().filter(|p| p.xuid != 0).map(|p| (p.xuid, Player {
+        name: p.name.clone(),
+        team: p.team,
+    })).collect();
 
     let mut head_to_head = HashMap::new();
 
-    for event in output.events().filter_map(|e| e.ok()) {
-        let event = match event {
-            csdemo::DemoEvent::GameEvent(ge) => *ge,
+    let mut completeness = crate::Completeness::default();
+    for event in output.events() {
+        let event = match completeness.ok(event) {
+            Some(csdemo::DemoEvent::GameEvent(ge)) => *ge,
             _ => continue,
         };
+        completeness.event(&event);
 
         match event {
             csdemo::game_event::GameEvent::PlayerDeath(death) => {
-                let (attacker_player, attacker) = match death.attacker.and_then(|u| players.get(&u).zip(Some(u))) {
+                // Players get a new user id when they reconnect, so everything is tracked by their
+                // steam id instead
+                let attacker_player = match death.attacker.and_then(|u| player_info.get(&u)).filter(|p| p.xuid != 0) {
                     Some(a) => a,
                     None => continue,
                 };
 
-                let (died_player, died) = match death.userid.and_then(|u| players.get(&u).zip(Some(u))) {
+                let died_player = match death.userid.and_then(|u| player_info.get(&u)).filter(|p| p.xuid != 0) {
                     Some(d) => d,
                     None => continue,
                 };
@@ -37,16 +55,27 @@ pub fn parse(buf: &[u8]) -> Result<Output, ()> {
                     continue;
                 }
 
-                let attacker_entry: &mut HashMap<_, _> = head_to_head.entry(attacker).or_default();
-                let died_killed: &mut usize = attacker_entry.entry(died).or_default();
+                let attacker_entry: &mut HashMap<_, _> = head_to_head.entry(attacker_player.xuid).or_default();
+                let died_killed: &mut usize = attacker_entry.entry(died_player.xuid).or_default();
                 *died_killed += 1;
             }
             _ => {}
         };
     }
 
+    // Only the player controllers tell the coaches and casters apart from the players
+    let mut participants = crate::players::Participants::default();
+    for entity in output.entities() {
+        if let Some((_, state)) = completeness.ok(entity) {
+            participants.update(&state);
+        }
+    }
+    players.retain(|steam_id, _| participants.played(*steam_id));
+    head_to_head.retain(|steam_id, _| participants.played(*steam_id));
+
     Ok(Output {
         players,
         head_to_head,
+        complete: completeness.complete(),
     })
 }
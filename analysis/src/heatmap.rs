@@ -49,10 +49,19 @@ impl HeatMap {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct HeatMapOutput {
-    pub player_heatmaps: std::collections::HashMap<(csdemo::UserId, String), HeatMap>,
-    pub player_info: std::collections::HashMap<csdemo::UserId, csdemo::parser::Player>,
+    /// The heatmaps for every player and team, ordered by steam id
+    pub player_heatmaps: Vec<PlayerHeatMap>,
+    /// Whether the demo could be read until the end of the match
+    pub complete: bool,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct PlayerHeatMap {
+    pub steam_id: u64,
+    pub team: String,
+    pub heatmap: HeatMap,
 }
 
 #[derive(Debug)]
@@ -82,48 +91,31 @@ pub fn parse(config: &Config, buf: &[u8]) -> Result<HeatMapOutput, ()> {
 
     let output = csdemo::lazyparser::LazyParser::new(tmp);
 
-    let pawn_ids = {
-        let mut tmp = std::collections::HashMap::<PawnID, _>::new();
-
-        for event in output.events().filter_map(|e| e.ok()) {
-            let entry = match event {
-                csdemo::DemoEvent::GameEvent(ge) => match ge.as_ref() {
-                    csdemo::game_event::GameEvent::PlayerSpawn(pspawn) => {
-                        match pspawn.userid_pawn.as_ref() {
-                            Some(csdemo::RawValue::I32(v)) => {
-                                Some((PawnID::from(*v), pspawn.userid.unwrap()))
-                            }
-                            _ => None,
-                        }
-                    }
-                    _ => None,
-                },
-                _ => None,
-            };
-
-            if let Some((pawn, userid)) = entry {
-                if let Some(previous) = tmp.insert(pawn, userid) {
-                    assert_eq!(previous, userid);
-                }
-            }
-        }
-
-        tmp
-    };
-
+    let mut controllers = std::collections::HashMap::new();
+    let mut pawn_players = std::collections::HashMap::new();
     let mut teams = std::collections::HashMap::new();
-    let mut player_lifestate = std::collections::HashMap::<csdemo::UserId, u32>::new();
-    let mut player_position = std::collections::HashMap::<csdemo::UserId, (f32, f32, f32)>::new();
+    let mut player_lifestate = std::collections::HashMap::<PawnID, u32>::new();
+    let mut player_position = std::collections::HashMap::<PawnID, (f32, f32, f32)>::new();
     let mut player_cells = std::collections::HashMap::new();
 
     let mut heatmaps = std::collections::HashMap::new();
-    for (tick, state) in output.entities().filter_map(|s| s.ok()) {
+    let mut participants = crate::players::Participants::default();
+    let mut completeness = crate::Completeness::default();
+    for entity in output.entities() {
+        let (tick, state) = match completeness.ok(entity) {
+            Some(e) => e,
+            None => continue,
+        };
+        completeness.entity(&state);
+        participants.update(&state);
+
         let _tracing_guard = tracing::debug_span!("Tick", ?tick).entered();
 
         process_tick(
             config,
             &state,
-            &pawn_ids,
+            &mut controllers,
+            &mut pawn_players,
             &mut teams,
             &mut player_lifestate,
             &mut player_position,
@@ -132,27 +124,156 @@ pub fn parse(config: &Config, buf: &[u8]) -> Result<HeatMapOutput, ()> {
         );
     }
 
-    tracing::debug!("Pawn-IDs: {:?}", pawn_ids);
+    tracing::debug!("Pawn-Players: {:?}", pawn_players);
+
+    let mut player_heatmaps: Vec<_> = heatmaps
+        .into_iter()
+        .filter(|((steam_id, _), _)| participants.played(*steam_id))
+        .map(|((steam_id, team), heatmap)| PlayerHeatMap {
+            steam_id,
+            team,
+            heatmap,
+        })
+        .collect();
+    player_heatmaps
+        .sort_unstable_by(|h1, h2| (h1.steam_id, &h1.team).cmp(&(h2.steam_id, &h2.team)));
 
     Ok(HeatMapOutput {
-        player_heatmaps: heatmaps,
-        player_info: output.player_info(),
+        player_heatmaps,
+        complete: completeness.complete(),
     })
 }
 
 pub const MAX_COORD: f32 = (1 << 14) as f32;
 
+/// The size of the cells used to encode the positions of entities
+const CELL_WIDTH: f32 = (1 << 9) as f32;
+
+/// The state of a player controller needed to figure out which player a pawn belongs to
+#[derive(Debug, Default)]
+pub(crate) struct ControllerState {
+    pub(crate) steam_id: Option<u64>,
+    pub(crate) pawn: Option<PawnID>,
+}
+
+/// Updates the mapping of pawns to the steam id of their player, using the controller entities.
+///
+/// This is done while going through the entities, because the pawn of a player changes when they
+/// reconnect and the pawn ids can be reused by other players afterwards.
+pub(crate) fn update_pawn_players(
+    entity_state: &csdemo::parser::entities::EntityState,
+    controllers: &mut std::collections::HashMap<PawnID, ControllerState>,
+    pawn_players: &mut std::collections::HashMap<PawnID, u64>,
+) {
+    if entity_state.class.as_ref() != "CCSPlayerController" {
+        return;
+    }
+
+    let mut steam_id = None;
+    let mut pawn = None;
+    for prop in entity_state.props.iter() {
+        match prop.prop_info.prop_name.as_ref() {
+            "CCSPlayerController.m_steamID" => {
+                if let csdemo::parser::Variant::U64(v) = prop.value {
+                    steam_id = Some(v);
+                }
+            }
+            "CCSPlayerController.m_hPlayerPawn" => {
+                if let Some(v) = prop.value.as_u32() {
+                    pawn = Some(PawnID::from(v));
+                }
+            }
+            _ => {}
+        };
+    }
+
+    let controller = controllers
+        .entry(PawnID::from(entity_state.id))
+        .or_default();
+    update_controller(controller, steam_id, pawn, pawn_players);
+}
+
+/// Applies the values of a controller update, `None` for the values that did not change
+fn update_controller(
+    controller: &mut ControllerState,
+    steam_id: Option<u64>,
+    pawn: Option<PawnID>,
+    pawn_players: &mut std::collections::HashMap<PawnID, u64>,
+) {
+    if let Some(steam_id) = steam_id {
+        controller.steam_id = Some(steam_id).filter(|v| *v != 0);
+    }
+    if let Some(pawn) = pawn {
+        controller.pawn = Some(pawn);
+    }
+
+    if let (Some(steam_id), Some(pawn)) = (controller.steam_id, controller.pawn) {
+        pawn_players.insert(pawn, steam_id);
+    }
+}
+
+/// Decodes the position of an entity from its cell and the offset inside of that cell.
+///
+/// An update only contains the values that changed, so the missing ones are taken from the
+/// previous updates of the entity.
+pub(crate) fn update_position(
+    entity_state: &csdemo::parser::entities::EntityState,
+    entity_cells: &mut std::collections::HashMap<PawnID, (u32, u32, u32)>,
+    entity_offsets: &mut std::collections::HashMap<PawnID, (f32, f32, f32)>,
+) -> (f32, f32, f32) {
+    let id = PawnID::from(entity_state.id);
+    let (mut x_cell, mut y_cell, mut z_cell) = entity_cells.get(&id).copied().unwrap_or_default();
+    let (mut x_offset, mut y_offset, mut z_offset) =
+        entity_offsets.get(&id).copied().unwrap_or_default();
+
+    for prop in entity_state.props.iter() {
+        let prop_name: &str = prop.prop_info.prop_name.as_ref();
+        let name = match prop_name.split_once(".CBodyComponentBaseAnimGraph.") {
+            Some((_, name)) => name,
+            None => continue,
+        };
+
+        match name {
+            "m_cellX" => x_cell = prop.value.as_u32().unwrap_or(x_cell),
+            "m_cellY" => y_cell = prop.value.as_u32().unwrap_or(y_cell),
+            "m_cellZ" => z_cell = prop.value.as_u32().unwrap_or(z_cell),
+            "m_vecX" => x_offset = prop.value.as_f32().unwrap_or(x_offset),
+            "m_vecY" => y_offset = prop.value.as_f32().unwrap_or(y_offset),
+            "m_vecZ" => z_offset = prop.value.as_f32().unwrap_or(z_offset),
+            _ => {}
+        };
+    }
+
+    entity_cells.insert(id, (x_cell, y_cell, z_cell));
+    entity_offsets.insert(id, (x_offset, y_offset, z_offset));
+
+    (
+        x_cell as f32 * CELL_WIDTH + x_offset,
+        y_cell as f32 * CELL_WIDTH + y_offset,
+        z_cell as f32 * CELL_WIDTH + z_offset,
+    )
+}
+
 fn process_tick(
     config: &Config,
     entity_state: &csdemo::parser::entities::EntityState,
-    pawn_ids: &std::collections::HashMap<PawnID, csdemo::UserId>,
+    controllers: &mut std::collections::HashMap<PawnID, ControllerState>,
+    pawn_players: &mut std::collections::HashMap<PawnID, u64>,
     teams: &mut std::collections::HashMap<PawnID, String>,
-    player_lifestate: &mut std::collections::HashMap<csdemo::UserId, u32>,
-    player_position: &mut std::collections::HashMap<csdemo::UserId, (f32, f32, f32)>,
-    player_cells: &mut std::collections::HashMap<csdemo::UserId, (u32, u32, u32)>,
-    heatmaps: &mut std::collections::HashMap<(csdemo::UserId, String), HeatMap>,
+    player_lifestate: &mut std::collections::HashMap<PawnID, u32>,
+    player_position: &mut std::collections::HashMap<PawnID, (f32, f32, f32)>,
+    player_cells: &mut std::collections::HashMap<PawnID, (u32, u32, u32)>,
+    heatmaps: &mut std::collections::HashMap<(u64, String), HeatMap>,
 ) {
-    if !matches!(entity_state.class.as_ref(), "CCSPlayerPawn" | "CCSTeam") {
+    if !matches!(
+        entity_state.class.as_ref(),
+        "CCSPlayerPawn" | "CCSTeam" | "CCSPlayerController"
+    ) {
+        return;
+    }
+
+    if entity_state.class.as_ref() == "CCSPlayerController" {
+        update_pawn_players(entity_state, controllers, pawn_players);
         return;
     }
 
@@ -182,7 +303,7 @@ fn process_tick(
     }
 
     let pawn_id = PawnID::from(entity_state.id);
-    let user_id = match pawn_ids.get(&pawn_id).cloned() {
+    let steam_id = match pawn_players.get(&pawn_id).copied() {
         Some(id) => id,
         None => return,
     };
@@ -193,84 +314,17 @@ fn process_tick(
 
     let _inner_guard = tracing::trace_span!("Entity", entity_id=?entity_state.id).entered();
 
-    let x_cell = match entity_state
-        .get_prop("CCSPlayerPawn.CBodyComponentBaseAnimGraph.m_cellX")
-        .map(|prop| prop.value.as_u32())
-        .flatten()
-    {
-        Some(c) => c,
-        None => player_cells.get(&user_id).map(|(x, _, _)| *x).unwrap_or(0),
-    };
-    let y_cell = match entity_state
-        .get_prop("CCSPlayerPawn.CBodyComponentBaseAnimGraph.m_cellY")
-        .map(|prop| prop.value.as_u32())
-        .flatten()
-    {
-        Some(c) => c,
-        None => player_cells.get(&user_id).map(|(_, y, _)| *y).unwrap_or(0),
-    };
-    let z_cell = match entity_state
-        .get_prop("CCSPlayerPawn.CBodyComponentBaseAnimGraph.m_cellZ")
-        .map(|prop| prop.value.as_u32())
-        .flatten()
-    {
-        Some(c) => c,
-        None => player_cells.get(&user_id).map(|(_, _, z)| *z).unwrap_or(0),
-    };
-
-    player_cells.insert(user_id, (x_cell, y_cell, z_cell));
+    let (x_coord, y_coord, z_coord) = update_position(entity_state, player_cells, player_position);
 
-    let x_coord = match entity_state
-        .get_prop("CCSPlayerPawn.CBodyComponentBaseAnimGraph.m_vecX")
-        .map(|prop| prop.value.as_f32())
-        .flatten()
-    {
-        Some(c) => c,
-        None => player_position
-            .get(&user_id)
-            .map(|(x, _, _)| *x)
-            .unwrap_or(0.0),
-    };
-    let y_coord = match entity_state
-        .get_prop("CCSPlayerPawn.CBodyComponentBaseAnimGraph.m_vecY")
-        .map(|prop| prop.value.as_f32())
-        .flatten()
+    // Corrupted entity data can result in positions outside of the map, which would otherwise
+    // blow up the size of the heatmap
+    if [x_coord, y_coord, z_coord]
+        .iter()
+        .any(|c| !(0.0..=2.0 * MAX_COORD).contains(c))
     {
-        Some(c) => c,
-        None => player_position
-            .get(&user_id)
-            .map(|(_, y, _)| *y)
-            .unwrap_or(0.0),
-    };
-    let z_coord = match entity_state
-        .get_prop("CCSPlayerPawn.CBodyComponentBaseAnimGraph.m_vecZ")
-        .map(|prop| prop.value.as_f32())
-        .flatten()
-    {
-        Some(c) => c,
-        None => player_position
-            .get(&user_id)
-            .map(|(_, _, z)| *z)
-            .unwrap_or(0.0),
-    };
-
-    player_position.insert(user_id, (x_coord, y_coord, z_coord));
-
-    assert!(x_coord >= 0.0);
-    assert!(y_coord >= 0.0);
-    assert!(z_coord >= 0.0);
-
-    let x_cell_coord = (x_cell as f32 * (1 << 9) as f32) as f32;
-    let y_cell_coord = (y_cell as f32 * (1 << 9) as f32) as f32;
-    let z_cell_coord = (z_cell as f32 * (1 << 9) as f32) as f32;
-
-    let x_coord = x_cell_coord + x_coord;
-    let y_coord = y_cell_coord + y_coord;
-    let z_coord = z_cell_coord + z_coord;
-
-    assert!(x_coord >= 0.0);
-    assert!(y_coord >= 0.0);
-    assert!(z_coord >= 0.0);
+        tracing::warn!("Invalid Coord (X, Y, Z): {:?}", (x_coord, y_coord, z_coord));
+        return;
+    }
 
     let x_cell = (x_coord / config.cell_size) as usize;
     let y_cell = (y_coord / config.cell_size) as usize;
@@ -288,10 +342,10 @@ fn process_tick(
 
     let lifestate = match n_lifestate {
         Some(state) => {
-            player_lifestate.insert(user_id, state);
+            player_lifestate.insert(pawn_id, state);
             state
         }
-        None => player_lifestate.get(&user_id).copied().unwrap_or(1),
+        None => player_lifestate.get(&pawn_id).copied().unwrap_or(1),
     };
 
     // 0 means alive
@@ -302,7 +356,7 @@ fn process_tick(
     // tracing::trace!("Coord (X, Y, Z): {:?} -> {:?}", (x_coord, y_coord, z_coord), (x_cell, y_cell));
 
     let heatmap = heatmaps
-        .entry((user_id.clone(), team))
+        .entry((steam_id, team))
         .or_insert(HeatMap::new(config.cell_size));
     heatmap.increment(x_cell, y_cell);
 }
@@ -336,6 +390,24 @@ impl HeatMap {
         )
     }
 
+    /// All the cells that were visited at least once, with the world coordinates of the cell
+    pub fn cells(&self) -> impl Iterator<Item = ((f32, f32), usize)> + '_ {
+        self.rows.iter().enumerate().flat_map(move |(y, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|(_, cell)| **cell > 0)
+                .map(move |(x, cell)| {
+                    (
+                        (
+                            (self.min_x + x) as f32 * self.block_size - MAX_COORD,
+                            (self.min_y + y) as f32 * self.block_size - MAX_COORD,
+                        ),
+                        *cell,
+                    )
+                })
+        })
+    }
+
     pub fn as_image(&self) -> image::RgbImage {
         use colors_transform::Color;
 
@@ -393,6 +465,52 @@ impl HeatMap {
         self.max_y = self.min_y + self.rows.len();
         self.max_x = self.min_x + self.rows.iter().map(|r| r.len()).max().unwrap_or(0);
     }
+
+    /// Adds the counts of another heatmap to this one, like the heatmaps of the same player in
+    /// several demos of a map. Both heatmaps need to use the same cell size
+    pub fn merge(&mut self, other: &HeatMap) {
+        debug_assert_eq!(self.block_size, other.block_size);
+
+        if self.rows.is_empty() {
+            self.min_x = other.min_x;
+            self.min_y = other.min_y;
+            self.max_x = other.max_x;
+            self.max_y = other.max_y;
+        }
+
+        let min_x = self.min_x.min(other.min_x);
+        let min_y = self.min_y.min(other.min_y);
+
+        // Move the existing cells, so the rows start at the new minimum
+        let _ = self
+            .rows
+            .splice(0..0, (min_y..self.min_y).map(|_| Vec::new()));
+        for row in self.rows.iter_mut().filter(|r| !r.is_empty()) {
+            let _ = row.splice(0..0, (min_x..self.min_x).map(|_| 0));
+        }
+        self.min_x = min_x;
+        self.min_y = min_y;
+
+        for (y, row) in other.rows.iter().enumerate() {
+            let y = other.min_y + y - min_y;
+            if self.rows.len() <= y {
+                self.rows.resize(y + 1, Vec::new());
+            }
+            let target = self.rows.get_mut(y).unwrap();
+
+            for (x, cell) in row.iter().copied().enumerate().filter(|(_, c)| *c > 0) {
+                let x = other.min_x + x - min_x;
+                if target.len() <= x {
+                    target.resize(x + 1, 0);
+                }
+                target[x] += cell;
+                self.max_value = self.max_value.max(target[x]);
+            }
+        }
+
+        self.max_x = self.max_x.max(other.max_x);
+        self.max_y = self.max_y.max(other.max_y);
+    }
 }
 
 #[cfg(test)]
@@ -450,4 +568,70 @@ mod tests {
 
         assert_eq!(&vec![vec![1, 0], vec![0, 0]], &input.rows);
     }
+
+    #[test]
+    fn merge() {
+        let mut input = HeatMap::new(2.0);
+        input.increment(1, 1);
+
+        let mut other = HeatMap::new(2.0);
+        other.increment(1, 1);
+        other.increment(2, 3);
+
+        input.merge(&other);
+
+        assert_eq!(
+            &vec![vec![], vec![0, 2], vec![], vec![0, 0, 1]],
+            &input.rows
+        );
+        assert_eq!(input.max_x, 2);
+        assert_eq!(input.max_y, 3);
+        assert_eq!(input.max_value, 2);
+
+        // Heatmaps that were fitted to a different area are lined up by their coordinates
+        let mut fitted = HeatMap::new(2.0);
+        fitted.increment(2, 2);
+        fitted.fit(2.0..6.0, 2.0..6.0);
+        assert_eq!(&vec![vec![0, 0], vec![0, 1]], &fitted.rows);
+
+        let mut empty = HeatMap::new(2.0);
+        empty.merge(&fitted);
+        empty.merge(&other);
+
+        assert_eq!(empty.min_x, 0);
+        assert_eq!(empty.min_y, 0);
+        assert_eq!(
+            &vec![vec![], vec![0, 1], vec![0, 0, 1], vec![0, 0, 1]],
+            &empty.rows
+        );
+    }
+
+    #[test]
+    fn pawn_players_reconnect() {
+        let mut controllers = std::collections::HashMap::<PawnID, ControllerState>::new();
+        let mut pawn_players = std::collections::HashMap::new();
+        let mut update = |controller: i32, steam_id, pawn: Option<u32>| {
+            update_controller(
+                controllers.entry(PawnID::from(controller)).or_default(),
+                steam_id,
+                pawn.map(PawnID::from),
+                &mut pawn_players,
+            )
+        };
+
+        update(1, Some(76561198000000001), Some(10));
+        // The player reconnects and gets a new controller and pawn
+        update(2, Some(76561198000000001), Some(11));
+        // The old controller and its pawn are reused for another player, only the steam id changes
+        update(1, Some(76561198000000002), None);
+
+        assert_eq!(
+            Some(&76561198000000002),
+            pawn_players.get(&PawnID::from(10))
+        );
+        assert_eq!(
+            Some(&76561198000000001),
+            pawn_players.get(&PawnID::from(11))
+        );
+    }
 }
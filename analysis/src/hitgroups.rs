@@ -0,0 +1,196 @@
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+pub enum HitGroup {
+    Generic,
+    Head,
+    Chest,
+    Stomach,
+    LeftArm,
+    RightArm,
+    LeftLeg,
+    RightLeg,
+    Neck,
+    Gear,
+}
+
+impl HitGroup {
+    /// The `hitgroup` field of the `player_hurt` event
+    pub fn from_id(id: u32) -> Option<Self> {
+        match id {
+            0 => Some(Self::Generic),
+            1 => Some(Self::Head),
+            2 => Some(Self::Chest),
+            3 => Some(Self::Stomach),
+            4 => Some(Self::LeftArm),
+            5 => Some(Self::RightArm),
+            6 => Some(Self::LeftLeg),
+            7 => Some(Self::RightLeg),
+            8 => Some(Self::Neck),
+            10 => Some(Self::Gear),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct HitStats {
+    pub hitgroup: HitGroup,
+    pub weapon: String,
+    pub hits: usize,
+    /// The health that was taken, hits on players with less health left than the damage of the
+    /// shot only count the remaining health
+    pub damage: usize,
+    pub armor_damage: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PlayerHits {
+    pub steam_id: u64,
+    /// The hits on enemies, per hitgroup and weapon
+    pub dealt: Vec<HitStats>,
+    /// The hits taken from enemies, per hitgroup and weapon
+    pub received: Vec<HitStats>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct Hitgroups {
+    /// The players ordered by their steam id
+    pub players: Vec<PlayerHits>,
+    /// Whether the demo could be read until the end of the match, the stats only cover the part
+    /// of the match that was recorded otherwise
+    pub complete: bool,
+}
+
+type HitKey = (u64, HitGroup, String);
+
+pub fn parse(buf: &[u8]) -> Result<Hitgroups, ()> {
+    let tmp = csdemo::Container::parse(buf).map_err(|e| ())?;
+    let output = csdemo::lazyparser::LazyParser::new(tmp);
+
+    let player_info = output.player_info();
+
+    let mut dealt = std::collections::BTreeMap::<HitKey, HitStats>::new();
+    let mut received = std::collections::BTreeMap::<HitKey, HitStats>::new();
+
+    let mut track = false;
+    let mut player_life = std::collections::HashMap::<csdemo::UserId, u32>::new();
+    let mut completeness = crate::Completeness::default();
+    for event in output.events() {
+        let ge = match completeness.ok(event) {
+            Some(csdemo::DemoEvent::GameEvent(ge)) => ge,
+            _ => continue,
+        };
+        completeness.event(ge.as_ref());
+
+        match ge.as_ref() {
+            csdemo::game_event::GameEvent::RoundAnnounceMatchStart(_) => {
+                dealt.clear();
+                received.clear();
+                track = true;
+            }
+            csdemo::game_event::GameEvent::RoundPreStart(_) => {
+                track = true;
+            }
+            csdemo::game_event::GameEvent::WinPanelMatch(_)
+            | csdemo::game_event::GameEvent::RoundOfficiallyEnded(_) => {
+                track = false;
+            }
+            csdemo::game_event::GameEvent::PlayerSpawn(spawn) => {
+                if let Some(userid) = spawn.userid {
+                    player_life.insert(userid, 100);
+                }
+            }
+            csdemo::game_event::GameEvent::PlayerHurt(hurt) if track => {
+                let (attacked_id, attacked) = match hurt
+                    .userid
+                    .and_then(|u| player_info.get(&u).map(|p| (u, p)))
+                {
+                    Some(a) => a,
+                    None => continue,
+                };
+
+                let health = crate::raw_number(&hurt.health)
+                    .map(|v| v as u32)
+                    .unwrap_or(0);
+                // Players that were already alive when the recording started never had a spawn
+                // event
+                let previous_health = player_life.get(&attacked_id).copied().unwrap_or(100);
+                player_life.insert(attacked_id, health);
+
+                let attacker = match hurt.attacker.and_then(|a| player_info.get(&a)) {
+                    Some(a) if a.team != attacked.team => a,
+                    _ => continue,
+                };
+                let hitgroup = match crate::raw_number(&hurt.hitgroup)
+                    .and_then(|id| HitGroup::from_id(id as u32))
+                {
+                    Some(h) => h,
+                    None => continue,
+                };
+                let weapon = hurt.weapon.clone().unwrap_or_default();
+
+                let damage = previous_health.saturating_sub(health) as usize;
+                let armor_damage = crate::raw_number(&hurt.dmg_armor)
+                    .map(|v| v as usize)
+                    .unwrap_or(0);
+
+                for (entries, steam_id) in
+                    [(&mut dealt, attacker.xuid), (&mut received, attacked.xuid)]
+                {
+                    let stats = entries
+                        .entry((steam_id, hitgroup, weapon.clone()))
+                        .or_insert_with(|| HitStats {
+                            hitgroup,
+                            weapon: weapon.clone(),
+                            hits: 0,
+                            damage: 0,
+                            armor_damage: 0,
+                        });
+                    stats.hits += 1;
+                    stats.damage += damage;
+                    stats.armor_damage += armor_damage;
+                }
+            }
+            _ => {}
+        };
+    }
+
+    // Only the player controllers tell the coaches and casters apart from the players
+    let mut participants = crate::players::Participants::default();
+    for entity in output.entities() {
+        if let Some((_, state)) = completeness.ok(entity) {
+            participants.update(&state);
+        }
+    }
+
+    let mut players = std::collections::BTreeMap::<u64, PlayerHits>::new();
+    for ((steam_id, _, _), stats) in dealt {
+        players
+            .entry(steam_id)
+            .or_insert_with(|| PlayerHits {
+                steam_id,
+                ..Default::default()
+            })
+            .dealt
+            .push(stats);
+    }
+    for ((steam_id, _, _), stats) in received {
+        players
+            .entry(steam_id)
+            .or_insert_with(|| PlayerHits {
+                steam_id,
+                ..Default::default()
+            })
+            .received
+            .push(stats);
+    }
+
+    Ok(Hitgroups {
+        players: players
+            .into_values()
+            .filter(|p| participants.played(p.steam_id))
+            .collect(),
+        complete: completeness.complete(),
+    })
+}
@@ -0,0 +1,139 @@
+/// The upper bounds of the engagement ranges in game units, kills from further away than the last
+/// bound make up an extra range
+pub const RANGE_BOUNDS: [f32; 4] = [500.0, 1000.0, 1500.0, 2500.0];
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct WeaponStats {
+    pub weapon: String,
+    pub kills: usize,
+    pub headshots: usize,
+    pub noscopes: usize,
+    pub wallbangs: usize,
+    pub smoke_kills: usize,
+    pub blind_kills: usize,
+    /// The number of kills in each engagement range, see [`RANGE_BOUNDS`]. Kills without a known
+    /// distance are not counted
+    pub ranges: [usize; RANGE_BOUNDS.len() + 1],
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PlayerKillStats {
+    pub steam_id: u64,
+    pub wallbangs: usize,
+    pub smoke_kills: usize,
+    pub blind_kills: usize,
+}
+
+/// The engagement range a kill distance falls into, as an index into [`WeaponStats::ranges`]
+pub fn range_index(distance: f32) -> usize {
+    RANGE_BOUNDS.partition_point(|bound| *bound <= distance)
+}
+
+/// A readable name for the engagement range, like `500-1000` or `2500+`
+pub fn range_name(idx: usize) -> String {
+    let lower = idx
+        .checked_sub(1)
+        .and_then(|i| RANGE_BOUNDS.get(i))
+        .unwrap_or(&0.0);
+    match RANGE_BOUNDS.get(idx) {
+        Some(upper) => format!("{}-{}", lower, upper),
+        None => format!("{}+", lower),
+    }
+}
+
+/// Aggregates the kills per weapon, ordered by the number of kills
+pub fn weapon_stats<'e, I>(events: I) -> Vec<WeaponStats>
+where
+    I: IntoIterator<Item = &'e crate::perround::RoundEvent>,
+{
+    let mut weapons = std::collections::HashMap::<&str, WeaponStats>::new();
+    for event in events {
+        if let crate::perround::RoundEvent::Kill {
+            attacker,
+            died,
+            weapon,
+            headshot,
+            noscope,
+            penetrated,
+            thrusmoke,
+            attackerblind,
+            distance,
+            ..
+        } = event
+        {
+            if attacker == died {
+                continue;
+            }
+
+            let name = weapon.as_deref().unwrap_or("unknown");
+            let stats = weapons.entry(name).or_insert_with(|| WeaponStats {
+                weapon: name.to_owned(),
+                ..Default::default()
+            });
+            stats.kills += 1;
+            stats.headshots += *headshot as usize;
+            stats.noscopes += *noscope as usize;
+            stats.wallbangs += *penetrated as usize;
+            stats.smoke_kills += *thrusmoke as usize;
+            stats.blind_kills += *attackerblind as usize;
+            if let Some(distance) = distance {
+                stats.ranges[range_index(*distance)] += 1;
+            }
+        }
+    }
+
+    let mut result: Vec<_> = weapons.into_values().collect();
+    result.sort_unstable_by(|w1, w2| w2.kills.cmp(&w1.kills).then(w1.weapon.cmp(&w2.weapon)));
+    result
+}
+
+/// Counts the special kills of every player, ordered by steam id
+pub fn player_stats<'e, I>(events: I) -> Vec<PlayerKillStats>
+where
+    I: IntoIterator<Item = &'e crate::perround::RoundEvent>,
+{
+    let mut players = std::collections::BTreeMap::<u64, PlayerKillStats>::new();
+    for event in events {
+        if let crate::perround::RoundEvent::Kill {
+            attacker,
+            died,
+            penetrated,
+            thrusmoke,
+            attackerblind,
+            ..
+        } = event
+        {
+            if attacker == died {
+                continue;
+            }
+
+            let stats = players.entry(*attacker).or_insert_with(|| PlayerKillStats {
+                steam_id: *attacker,
+                ..Default::default()
+            });
+            stats.wallbangs += *penetrated as usize;
+            stats.smoke_kills += *thrusmoke as usize;
+            stats.blind_kills += *attackerblind as usize;
+        }
+    }
+
+    players.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranges() {
+        assert_eq!(0, range_index(0.0));
+        assert_eq!(0, range_index(499.0));
+        assert_eq!(1, range_index(500.0));
+        assert_eq!(3, range_index(2000.0));
+        assert_eq!(4, range_index(5000.0));
+
+        assert_eq!("0-500", range_name(0));
+        assert_eq!("500-1000", range_name(1));
+        assert_eq!("2500+", range_name(4));
+    }
+}
@@ -2,3 +2,138 @@ pub mod endofgame;
 pub mod heatmap;
 pub mod perround;
 pub mod head_to_head;
+pub mod metadata;
+pub mod timeline;
+pub mod players;
+pub mod minimap;
+pub mod quality;
+pub mod replay;
+pub mod grenades;
+pub mod places;
+pub mod kills;
+pub mod hitgroups;
+pub mod roundtypes;
+pub mod advantage;
+pub mod winprob;
+pub mod setups;
+pub mod movement;
+pub mod postplant;
+pub mod career;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+/// Keeps track of whether a demo could be read until the end of the match.
+///
+/// Demos that were cut short (server crash, interrupted upload) either contain frames that fail
+/// to parse or simply stop before the match ended. The analyses still return everything they
+/// could get out of such a demo, but mark the result as incomplete.
+#[derive(Debug, Default)]
+pub(crate) struct Completeness {
+    failed: bool,
+    match_ended: bool,
+}
+
+impl Completeness {
+    /// Unwraps a parsed frame, remembering if it could not be parsed
+    pub fn ok<T, E>(&mut self, result: Result<T, E>) -> Option<T> {
+        if result.is_err() {
+            self.failed = true;
+        }
+        result.ok()
+    }
+
+    pub fn event(&mut self, event: &csdemo::game_event::GameEvent) {
+        if let csdemo::game_event::GameEvent::WinPanelMatch(_) = event {
+            self.match_ended = true;
+        }
+    }
+
+    pub fn entity(&mut self, state: &csdemo::parser::entities::EntityState) {
+        // 5 is GAMEPHASE_MATCH_ENDED
+        if state
+            .get_prop("CCSGameRulesProxy.CCSGameRules.m_gamePhase")
+            .map(|p| p.value.as_i32())
+            .flatten()
+            == Some(5)
+        {
+            self.match_ended = true;
+        }
+    }
+
+    pub fn complete(&self) -> bool {
+        !self.failed && self.match_ended
+    }
+}
+
+/// The numeric fields of game events can be sent as any of the number types
+pub(crate) fn raw_number(value: &Option<csdemo::RawValue>) -> Option<f32> {
+    match value {
+        Some(csdemo::RawValue::F32(v)) => Some(*v),
+        Some(csdemo::RawValue::I32(v)) => Some(*v as f32),
+        Some(csdemo::RawValue::U64(v)) => Some(*v as f32),
+        _ => None,
+    }
+}
+
+/// The values a player had over the course of a demo, like their position or callout, to look up
+/// the value at the time of a game event
+#[derive(Debug)]
+pub(crate) struct PlayerHistory<T> {
+    players: std::collections::HashMap<u64, Vec<(u32, T)>>,
+}
+
+impl<T> Default for PlayerHistory<T> {
+    fn default() -> Self {
+        Self {
+            players: std::collections::HashMap::new(),
+        }
+    }
+}
+
+impl<T: PartialEq> PlayerHistory<T> {
+    /// Records the value of the player from the tick onwards, the ticks need to be increasing
+    pub fn update(&mut self, steam_id: u64, tick: u32, value: T) {
+        let history = self.players.entry(steam_id).or_default();
+        if history.last().map(|(_, v)| v) != Some(&value) {
+            history.push((tick, value));
+        }
+    }
+
+    /// The tick of the latest value recorded for the player
+    pub fn last_tick(&self, steam_id: u64) -> Option<u32> {
+        self.players.get(&steam_id)?.last().map(|(tick, _)| *tick)
+    }
+
+    /// The steam ids of all players with a recorded value
+    pub fn players(&self) -> impl Iterator<Item = u64> + '_ {
+        self.players.keys().copied()
+    }
+
+    /// The value the player had during the tick
+    pub fn get(&self, steam_id: u64, tick: u32) -> Option<&T> {
+        let history = self.players.get(&steam_id)?;
+        let idx = history.partition_point(|(t, _)| *t <= tick);
+        history.get(idx.checked_sub(1)?).map(|(_, value)| value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn player_history() {
+        let mut history = PlayerHistory::default();
+        history.update(1, 10, "TSpawn");
+        history.update(1, 20, "TSpawn");
+        history.update(1, 40, "BombsiteA");
+
+        assert_eq!(Some(40), history.last_tick(1));
+        assert_eq!(None, history.get(1, 5));
+        assert_eq!(Some(&"TSpawn"), history.get(1, 10));
+        assert_eq!(Some(&"TSpawn"), history.get(1, 25));
+        assert_eq!(Some(&"BombsiteA"), history.get(1, 100));
+        assert_eq!(None, history.get(2, 100));
+    }
+}
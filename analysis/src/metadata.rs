@@ -0,0 +1,150 @@
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum DemoType {
+    Gotv,
+    Pov,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum GameMode {
+    Matchmaking,
+    ValveServer,
+    Community,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Metadata {
+    pub map: String,
+    pub server_name: String,
+    pub demo_type: DemoType,
+    pub tick_rate: u32,
+    pub playback_ticks: u32,
+    pub game_mode: GameMode,
+    /// The tick at which the last match start was announced, everything before is warmup
+    pub match_start_tick: Option<u32>,
+    pub network_protocol: i32,
+    pub build_number: i32,
+}
+
+impl Metadata {
+    /// The playback duration of the demo in seconds
+    pub fn duration(&self) -> u32 {
+        self.playback_ticks / self.tick_rate.max(1)
+    }
+}
+
+/// The tick rate of CS2 servers, used if a demo contains no server info
+pub const DEFAULT_TICK_RATE: u32 = 64;
+
+/// The tick rate of the server that recorded the demo, taken from the tick interval of its server
+/// info. Community servers can run at a different tick rate than the 64 ticks of the official ones
+pub(crate) fn tick_rate<E>(events: impl IntoIterator<Item = Result<csdemo::DemoEvent, E>>) -> u32 {
+    events
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .find_map(|event| match event {
+            csdemo::DemoEvent::ServerInfo(info) => interval_tick_rate(info.tick_interval()),
+            _ => None,
+        })
+        .unwrap_or(DEFAULT_TICK_RATE)
+}
+
+fn interval_tick_rate(tick_interval: f32) -> Option<u32> {
+    Some((1.0 / tick_interval).round() as u32).filter(|_| tick_interval > 0.0)
+}
+
+pub fn parse(buf: &[u8]) -> Result<Metadata, ()> {
+    let tmp = csdemo::Container::parse(buf).map_err(|e| ())?;
+    let output = csdemo::lazyparser::LazyParser::new(tmp);
+
+    let header = output.file_header().ok_or(())?;
+
+    let mut playback_ticks = 0;
+    let mut match_start_tick = None;
+    for event in output.events().filter_map(|e| e.ok()) {
+        match event {
+            csdemo::DemoEvent::Tick(tick) => {
+                playback_ticks = playback_ticks.max(tick.tick());
+            }
+            csdemo::DemoEvent::GameEvent(ge) => {
+                if let csdemo::game_event::GameEvent::RoundAnnounceMatchStart(_) = ge.as_ref() {
+                    match_start_tick = Some(playback_ticks);
+                }
+            }
+            _ => {}
+        };
+    }
+
+    let mut queued_matchmaking = false;
+    let mut valve_ds = false;
+    for (_, state) in output.entities().filter_map(|e| e.ok()) {
+        if state.class.as_ref() != "CCSGameRulesProxy" {
+            continue;
+        }
+
+        for prop in state.props.iter() {
+            let value = match prop.value {
+                csdemo::parser::Variant::Bool(v) => v,
+                _ => continue,
+            };
+
+            match prop.prop_info.prop_name.as_ref() {
+                "CCSGameRulesProxy.CCSGameRules.m_bIsQueuedMatchmaking" => {
+                    queued_matchmaking = value;
+                }
+                "CCSGameRulesProxy.CCSGameRules.m_bIsValveDS" => {
+                    valve_ds = value;
+                }
+                _ => {}
+            };
+        }
+    }
+
+    let game_mode = if queued_matchmaking {
+        GameMode::Matchmaking
+    } else if valve_ds {
+        GameMode::ValveServer
+    } else {
+        GameMode::Community
+    };
+
+    Ok(Metadata {
+        map: header.map_name().to_owned(),
+        server_name: header.server_name().to_owned(),
+        demo_type: demo_type(header.client_name()),
+        tick_rate: tick_rate(output.events()),
+        playback_ticks,
+        game_mode,
+        match_start_tick,
+        network_protocol: header.network_protocol(),
+        build_number: header.build_num(),
+    })
+}
+
+/// GOTV demos are recorded by the (Source)TV client, while POV demos are recorded by the player
+/// and therefore contain their name as the client
+fn demo_type(client_name: &str) -> DemoType {
+    if client_name.contains("TV Demo") {
+        DemoType::Gotv
+    } else {
+        DemoType::Pov
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn demo_type_from_client() {
+        assert_eq!(DemoType::Gotv, demo_type("SourceTV Demo"));
+        assert_eq!(DemoType::Gotv, demo_type("GOTV Demo"));
+        assert_eq!(DemoType::Pov, demo_type("Excel"));
+    }
+
+    #[test]
+    fn tick_rates() {
+        assert_eq!(Some(64), interval_tick_rate(1.0 / 64.0));
+        assert_eq!(Some(128), interval_tick_rate(0.0078125));
+        assert_eq!(None, interval_tick_rate(0.0));
+    }
+}
@@ -0,0 +1,87 @@
+// The corresponding values for each map can be found using the Source2 Viewer and opening the
+// files in 'game/csgo/pak01_dir.vpk' and then 'resource/overviews/{map}.txt'
+pub static MINIMAP_COORDINATES: phf::Map<&str, MiniMapDefinition> = phf::phf_map! {
+    "cs_italy" => MiniMapDefinition {
+        pos_x: -2647.0,
+        pos_y: 2592.0,
+        scale: 4.6
+    },
+    "cs_office" => MiniMapDefinition {
+        pos_x: -1838.0,
+        pos_y: 1858.0,
+        scale: 4.1,
+    },
+    "de_ancient" => MiniMapDefinition {
+        pos_x: -2953.0,
+        pos_y: 2164.0,
+        scale: 5.0,
+    },
+    "de_anubis" => MiniMapDefinition {
+        pos_x: -2796.0,
+        pos_y: 3328.0,
+        scale: 5.22,
+    },
+    "de_dust2" => MiniMapDefinition {
+        pos_x: -2476.0,
+        pos_y: 3239.0,
+        scale: 4.4
+    },
+    "de_inferno" => MiniMapDefinition {
+        pos_x: -2087.0,
+        pos_y: 3870.0,
+        scale: 4.9,
+    },
+    "de_mirage" => MiniMapDefinition {
+        pos_x: -3230.0,
+        pos_y: 1713.0,
+        scale: 5.0,
+    },
+    "de_nuke" => MiniMapDefinition {
+        pos_x: -3453.0,
+        pos_y: 2887.0,
+        scale: 7.0,
+    },
+    "de_overpass" => MiniMapDefinition {
+        pos_x: -4831.0,
+        pos_y: 1781.0,
+        scale: 5.2,
+    },
+    "de_vertigo" => MiniMapDefinition {
+        pos_x: -3168.0,
+        pos_y: 1762.0,
+        scale: 4.0,
+    },
+};
+
+#[derive(Debug, PartialEq)]
+pub struct MiniMapDefinition {
+    pub pos_x: f32,
+    pub pos_y: f32,
+    pub scale: f32,
+}
+
+impl MiniMapDefinition {
+    pub fn x_coord(&self, map_coord: f32) -> f32 {
+        (map_coord * self.scale) + self.pos_x + crate::heatmap::MAX_COORD
+    }
+    pub fn y_coord(&self, map_coord: f32) -> f32 {
+        -(map_coord * self.scale) + self.pos_y + crate::heatmap::MAX_COORD
+    }
+
+    /// Converts a position, in the same coordinates as the heatmaps, to the pixel on the minimap
+    pub fn radar_position(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            (x - self.pos_x - crate::heatmap::MAX_COORD) / self.scale,
+            (self.pos_y + crate::heatmap::MAX_COORD - y) / self.scale,
+        )
+    }
+
+    /// Fits the heatmap to the area covered by the minimap (1024x1024 pixels), so it can be
+    /// drawn on top of the minimap
+    pub fn fit(&self, heatmap: &mut crate::heatmap::HeatMap) {
+        heatmap.fit(
+            self.x_coord(0.0)..self.x_coord(1024.0),
+            self.y_coord(1024.0)..self.y_coord(0.0),
+        );
+    }
+}
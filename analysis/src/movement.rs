@@ -0,0 +1,222 @@
+//! Movement statistics based on the sampled positions of the replays, like the distance a player
+//! traveled and how long they spent at every callout.
+
+/// The speed in units per second below which a player is standing still
+pub const STILL_SPEED: f32 = 20.0;
+/// The speed in units per second above which the movement between two samples is a teleport, like
+/// the respawn at the start of a round, and not counted
+pub const MAX_SPEED: f32 = 1000.0;
+
+#[derive(Debug, Default, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PlayerMovement {
+    pub steam_id: u64,
+    /// The horizontal distance traveled in units
+    pub distance: f32,
+    /// The seconds the player was alive for
+    pub time: f32,
+    /// The seconds spent standing still
+    pub still: f32,
+    /// The seconds spent moving while crouched
+    pub crouched: f32,
+    /// The seconds spent moving while holding the walk key
+    pub walking: f32,
+    /// The seconds spent moving at full speed
+    pub running: f32,
+    /// The seconds spent at every callout, ordered by time descending
+    pub places: Vec<(String, f32)>,
+}
+
+impl PlayerMovement {
+    /// The average speed in units per second while alive
+    pub fn average_speed(&self) -> f32 {
+        if self.time <= 0.0 {
+            0.0
+        } else {
+            self.distance / self.time
+        }
+    }
+}
+
+/// The movement of every player in the round, ordered by steam id
+pub fn round_movement(round: &crate::replay::RoundReplay, tick_rate: u32) -> Vec<PlayerMovement> {
+    let mut players = std::collections::BTreeMap::<u64, PlayerMovement>::new();
+    let mut places = std::collections::HashMap::<(u64, String), f32>::new();
+
+    for frames in round.frames.windows(2) {
+        let (previous, current) = (&frames[0], &frames[1]);
+        // The players can only move around in their spawn during the freeze time
+        if round.freeze_end.is_some_and(|end| previous.tick < end) {
+            continue;
+        }
+        let seconds = current.tick.saturating_sub(previous.tick) as f32 / tick_rate.max(1) as f32;
+        if seconds <= 0.0 {
+            continue;
+        }
+
+        for (idx, steam_id) in round.players.iter().enumerate() {
+            let samples = previous
+                .players
+                .get(idx)
+                .cloned()
+                .flatten()
+                .zip(current.players.get(idx).cloned().flatten());
+            let (start, end) = match samples.filter(|(s, e)| s.alive && e.alive) {
+                Some(s) => s,
+                None => continue,
+            };
+
+            let (dx, dy) = (
+                end.position.0 - start.position.0,
+                end.position.1 - start.position.1,
+            );
+            let distance = (dx * dx + dy * dy).sqrt();
+            let speed = distance / seconds;
+            if speed > MAX_SPEED {
+                continue;
+            }
+
+            let player = players.entry(*steam_id).or_insert_with(|| PlayerMovement {
+                steam_id: *steam_id,
+                ..Default::default()
+            });
+            player.distance += distance;
+            player.time += seconds;
+            if speed < STILL_SPEED {
+                player.still += seconds;
+            } else if end.crouched {
+                player.crouched += seconds;
+            } else if end.walking {
+                player.walking += seconds;
+            } else {
+                player.running += seconds;
+            }
+
+            if let Some(place) = start.place {
+                *places.entry((*steam_id, place)).or_default() += seconds;
+            }
+        }
+    }
+
+    for ((steam_id, place), seconds) in places {
+        if let Some(player) = players.get_mut(&steam_id) {
+            player.places.push((place, seconds));
+        }
+    }
+
+    players
+        .into_values()
+        .map(|mut p| {
+            sort_places(&mut p.places);
+            p
+        })
+        .collect()
+}
+
+/// Sums up the movement of every player across the rounds, ordered by steam id
+pub fn total_movement<'r, I>(rounds: I) -> Vec<PlayerMovement>
+where
+    I: IntoIterator<Item = &'r [PlayerMovement]>,
+{
+    let mut players = std::collections::BTreeMap::<u64, PlayerMovement>::new();
+    let mut places = std::collections::HashMap::<(u64, &str), f32>::new();
+    for movement in rounds.into_iter().flatten() {
+        let player = players
+            .entry(movement.steam_id)
+            .or_insert_with(|| PlayerMovement {
+                steam_id: movement.steam_id,
+                ..Default::default()
+            });
+        player.distance += movement.distance;
+        player.time += movement.time;
+        player.still += movement.still;
+        player.crouched += movement.crouched;
+        player.walking += movement.walking;
+        player.running += movement.running;
+
+        for (place, seconds) in movement.places.iter() {
+            *places
+                .entry((movement.steam_id, place.as_str()))
+                .or_default() += seconds;
+        }
+    }
+
+    for ((steam_id, place), seconds) in places {
+        if let Some(player) = players.get_mut(&steam_id) {
+            player.places.push((place.to_owned(), seconds));
+        }
+    }
+
+    players
+        .into_values()
+        .map(|mut p| {
+            sort_places(&mut p.places);
+            p
+        })
+        .collect()
+}
+
+fn sort_places(places: &mut [(String, f32)]) {
+    places.sort_unstable_by(|(p1, s1), (p2, s2)| s2.total_cmp(s1).then(p1.cmp(p2)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replay::{Frame, PlayerSample, RoundReplay};
+
+    fn sample(x: f32, place: &str, crouched: bool) -> Option<PlayerSample> {
+        Some(PlayerSample {
+            team: 2,
+            position: (x, 0.0, 0.0),
+            yaw: 0.0,
+            health: 100,
+            armor: 100,
+            weapon: None,
+            alive: true,
+            place: Some(place.to_owned()),
+            crouched,
+            walking: false,
+        })
+    }
+
+    #[test]
+    fn movement() {
+        let frame = |tick: u32, sample: Option<PlayerSample>| Frame {
+            tick,
+            players: vec![sample],
+        };
+        let round = RoundReplay {
+            freeze_end: Some(64),
+            players: vec![1],
+            frames: vec![
+                // Still in the freeze time
+                frame(0, sample(-100.0, "TSpawn", false)),
+                frame(32, sample(0.0, "TSpawn", false)),
+                frame(64, sample(0.0, "TSpawn", false)),
+                frame(128, sample(0.0, "TSpawn", false)),
+                frame(192, sample(250.0, "Outside", false)),
+                frame(256, sample(350.0, "Outside", true)),
+                // Respawning somewhere else is not counted as movement
+                frame(320, sample(5000.0, "Outside", false)),
+            ],
+            ..Default::default()
+        };
+
+        let result = round_movement(&round, 64);
+        assert_eq!(1, result.len());
+        let player = &result[0];
+        assert_eq!(350.0, player.distance);
+        assert_eq!(3.0, player.time);
+        assert_eq!(1.0, player.still);
+        assert_eq!(1.0, player.running);
+        assert_eq!(1.0, player.crouched);
+        assert_eq!(
+            vec![("TSpawn".to_owned(), 2.0), ("Outside".to_owned(), 1.0)],
+            player.places
+        );
+
+        let total = total_movement([result.as_slice(), result.as_slice()]);
+        assert_eq!(700.0, total[0].distance);
+        assert_eq!(4.0, total[0].places[0].1);
+    }
+}
@@ -48,17 +48,59 @@ pub static ROUND_WIN_REASON: phf::Map<i32, WinReason> = phf::phf_map! {
     20_i32 => WinReason::CTReachedHostage,
 };
 
-#[derive(Debug)]
+impl WinReason {
+    /// The team number of the side that won the round, 2 for T and 3 for CT
+    pub fn winning_team(&self) -> Option<i32> {
+        match self {
+            Self::BombExploded | Self::CTKilled | Self::CTSurrender => Some(2),
+            Self::BombDefused | Self::TKilled | Self::TimeRanOut | Self::TSurrender => Some(3),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct Round {
     pub winreason: WinReason,
     pub start: u32,
     pub end: u32,
     pub events: Vec<RoundEvent>,
+    pub players: Vec<RoundPlayer>,
+    /// The number of players alive on each side, at the start of the round and after every death
+    #[serde(default)]
+    pub alive: Vec<AliveCount>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct AliveCount {
+    pub tick: u32,
+    pub t: usize,
+    pub ct: usize,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RoundPlayer {
+    pub steam_id: u64,
+    pub team: i32,
+    pub kills: usize,
+    pub damage_dealt: usize,
+    pub damage_received: usize,
+    pub survived: bool,
+    pub end_health: u32,
+    pub equipment_saved: u32,
+    /// The value of the equipment at the end of the freeze time
+    #[serde(default)]
+    pub start_equipment: u32,
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub enum RoundEvent {
+    /// Only found in results stored before the plant site was recorded
     BombPlanted,
+    BombPlantedAt {
+        /// The callout the bomb was planted at
+        place: Option<String>,
+    },
     BombDefused,
     Kill {
         attacker: u64,
@@ -69,12 +111,45 @@ pub enum RoundEvent {
         headshot: bool,
         #[serde(default)]
         noscope: bool,
+        /// The callout the attacker was at, `None` if unknown
+        #[serde(default)]
+        attacker_place: Option<String>,
+        /// The callout the player who died was at, `None` if unknown
+        #[serde(default)]
+        died_place: Option<String>,
+        /// The bullet went through a wall or another object (wallbang)
+        #[serde(default)]
+        penetrated: bool,
+        #[serde(default)]
+        thrusmoke: bool,
+        #[serde(default)]
+        attackerblind: bool,
+        /// The distance between the attacker and the player who died in game units, `None` for
+        /// suicides or if a position is unknown
+        #[serde(default)]
+        distance: Option<f32>,
     },
 }
 
-#[derive(Debug)]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct PerRound {
     pub rounds: Vec<Round>,
+    /// Whether the demo could be read until the end of the match, only the rounds that were
+    /// finished are returned otherwise
+    pub complete: bool,
+}
+
+/// The minimum number of ticks between the recorded positions of a player, to keep the memory
+/// usage down for long demos
+pub(crate) const POSITION_INTERVAL: u32 = 4;
+
+#[derive(Debug, Default)]
+struct ControllerState {
+    steam_id: u64,
+    team: i32,
+    pawn: Option<crate::heatmap::PawnID>,
+    health: u32,
+    alive: bool,
 }
 
 pub fn parse(buf: &[u8]) -> Result<PerRound, ()> {
@@ -85,18 +160,71 @@ pub fn parse(buf: &[u8]) -> Result<PerRound, ()> {
     let player_info = output.player_info();
 
     let mut rounds: Vec<Round> = Vec::new();
-    for (tick, state) in output.entities().filter_map(|e| e.ok()) {
+    let mut controllers =
+        std::collections::HashMap::<crate::heatmap::PawnID, ControllerState>::new();
+    let mut pawn_equipment = std::collections::HashMap::<crate::heatmap::PawnID, u32>::new();
+    let mut start_equipment = std::collections::HashMap::<u64, u32>::new();
+    let mut freeze_period = false;
+    let mut places = crate::PlayerHistory::<String>::default();
+    let mut positions = crate::PlayerHistory::<(f32, f32, f32)>::default();
+    let mut entity_cells = std::collections::HashMap::new();
+    let mut entity_offsets = std::collections::HashMap::new();
+    let mut participants = crate::players::Participants::default();
+    let mut completeness = crate::Completeness::default();
+    for entity in output.entities() {
+        let (tick, state) = match completeness.ok(entity) {
+            Some(e) => e,
+            None => continue,
+        };
+        completeness.entity(&state);
+        participants.update(&state);
+
+        match state.class.as_ref() {
+            "CCSPlayerController" => update_controller(&state, &mut controllers),
+            "CCSPlayerPawn" => {
+                if let Some(value) = state
+                    .get_prop("CCSPlayerPawn.m_unCurrentEquipmentValue")
+                    .map(|p| p.value.as_u32())
+                    .flatten()
+                {
+                    pawn_equipment.insert(crate::heatmap::PawnID::from(state.id), value);
+                }
+
+                let pawn = crate::heatmap::PawnID::from(state.id);
+                let steam_id = controllers
+                    .values()
+                    .find(|c| c.pawn == Some(pawn) && c.steam_id != 0)
+                    .map(|c| c.steam_id);
+                if let Some((steam_id, place)) = steam_id.zip(crate::places::pawn_place(&state)) {
+                    places.update(steam_id, tick, place.to_owned());
+                }
+
+                let position =
+                    crate::heatmap::update_position(&state, &mut entity_cells, &mut entity_offsets);
+                if let Some(steam_id) = steam_id.filter(|s| {
+                    positions
+                        .last_tick(*s)
+                        .map_or(true, |last| tick >= last + POSITION_INTERVAL)
+                }) {
+                    positions.update(steam_id, tick, position);
+                }
+            }
+            _ => {}
+        };
+
         let round_start_count = state
             .get_prop("CCSGameRulesProxy.CCSGameRules.m_nRoundStartCount")
             .map(|v| v.value.as_u32())
             .flatten();
         if let Some(round_start_count) = round_start_count {
-            if rounds.len() < (round_start_count - 1) as usize {
+            if rounds.len() < round_start_count.saturating_sub(1) as usize {
                 rounds.push(Round {
                     winreason: WinReason::StillInProgress,
                     start: tick,
                     end: u32::MAX,
                     events: Vec::new(),
+                    players: Vec::new(),
+                    alive: Vec::new(),
                 });
             }
         }
@@ -106,12 +234,58 @@ pub fn parse(buf: &[u8]) -> Result<PerRound, ()> {
             .map(|v| v.value.as_u32())
             .flatten();
         if let Some(round_end_count) = round_end_count {
-            if rounds.len() == (round_end_count - 1) as usize {
-                rounds.last_mut().unwrap().end = tick;
+            if rounds.len() == round_end_count.saturating_sub(1) as usize {
+                let round = match rounds.last_mut() {
+                    Some(r) => r,
+                    None => continue,
+                };
+                round.end = tick;
+
+                // Snapshot the state of every player at the end of the round, the stats that are
+                // based on events are filled in later on
+                round.players = controllers
+                    .values()
+                    .filter(|c| c.steam_id != 0 && matches!(c.team, 2 | 3))
+                    .filter(|c| participants.playing(c.steam_id))
+                    .map(|c| RoundPlayer {
+                        steam_id: c.steam_id,
+                        team: c.team,
+                        survived: c.alive,
+                        end_health: if c.alive { c.health } else { 0 },
+                        equipment_saved: if c.alive {
+                            c.pawn
+                                .and_then(|p| pawn_equipment.get(&p))
+                                .copied()
+                                .unwrap_or(0)
+                        } else {
+                            0
+                        },
+                        start_equipment: start_equipment.get(&c.steam_id).copied().unwrap_or(0),
+                        ..Default::default()
+                    })
+                    .collect();
             }
         }
 
         if state.class.as_ref() == "CCSGameRulesProxy" {
+            // The buys are snapshotted once the freeze time ends and the players start moving
+            if let Some(csdemo::parser::Variant::Bool(v)) = state
+                .get_prop("CCSGameRulesProxy.CCSGameRules.m_bFreezePeriod")
+                .map(|p| &p.value)
+            {
+                if freeze_period && !v {
+                    start_equipment = controllers
+                        .values()
+                        .filter(|c| c.steam_id != 0)
+                        .filter_map(|c| {
+                            let value = pawn_equipment.get(&c.pawn?)?;
+                            Some((c.steam_id, *value))
+                        })
+                        .collect();
+                }
+                freeze_period = *v;
+            }
+
             let round_win_reason = state
                 .get_prop("CCSGameRulesProxy.CCSGameRules.m_eRoundWinReason")
                 .map(|p| p.value.as_i32())
@@ -119,17 +293,48 @@ pub fn parse(buf: &[u8]) -> Result<PerRound, ()> {
                 .map(|v| ROUND_WIN_REASON.get(&v))
                 .flatten()
                 .filter(|r| !matches!(r, WinReason::StillInProgress));
-            if let Some(round_win_reason) = round_win_reason {
-                rounds.last_mut().unwrap().winreason = round_win_reason.clone();
+            if let Some((round_win_reason, round)) = round_win_reason.zip(rounds.last_mut()) {
+                round.winreason = round_win_reason.clone();
             }
         }
     }
 
+    // Everyone who took part in a round is alive at its start
+    for round in rounds.iter_mut() {
+        let count = |side: i32| round.players.iter().filter(|p| p.team == side).count();
+        round.alive.push(AliveCount {
+            tick: round.start,
+            t: count(2),
+            ct: count(3),
+        });
+    }
+
     let mut rounds_iter = rounds.iter_mut();
 
     let mut current_tick = 0;
-    let mut current_round = rounds_iter.next().unwrap();
-    'events: for event in output.events().filter_map(|e| e.ok()) {
+    let mut current_round = match rounds_iter.next() {
+        Some(r) => r,
+        None => {
+            return Ok(PerRound {
+                rounds: Vec::new(),
+                complete: false,
+            })
+        }
+    };
+    let mut player_life = std::collections::HashMap::<csdemo::UserId, u32>::new();
+    let place = |userid: Option<csdemo::UserId>, tick: u32| {
+        userid
+            .and_then(|u| player_info.get(&u))
+            .and_then(|p| places.get(p.xuid, tick))
+            .filter(|p| !p.is_empty())
+            .cloned()
+    };
+    'events: for event in output.events() {
+        let event = match completeness.ok(event) {
+            Some(e) => e,
+            None => continue,
+        };
+
         match event {
             csdemo::DemoEvent::Tick(tick) => {
                 current_tick = tick.tick();
@@ -148,20 +353,58 @@ pub fn parse(buf: &[u8]) -> Result<PerRound, ()> {
                 }
 
                 let event = match *ge {
-                    csdemo::game_event::GameEvent::BombPlanted(planted) => RoundEvent::BombPlanted,
+                    csdemo::game_event::GameEvent::BombPlanted(planted) => {
+                        RoundEvent::BombPlantedAt {
+                            place: place(planted.userid, current_tick),
+                        }
+                    }
                     csdemo::game_event::GameEvent::BombDefused(defused) => RoundEvent::BombDefused,
+                    csdemo::game_event::GameEvent::PlayerSpawn(spawn) => {
+                        if let Some(userid) = spawn.userid {
+                            player_life.insert(userid, 100);
+                        }
+                        continue;
+                    }
+                    csdemo::game_event::GameEvent::PlayerHurt(hurt) => {
+                        player_hurt(&hurt, &player_info, &mut player_life, current_round);
+                        continue;
+                    }
                     csdemo::game_event::GameEvent::PlayerDeath(death) => {
                         let died = match death.userid {
                             Some(d) => d,
                             None => continue,
                         };
-                        let attacker = match death.attacker.filter(|p| p.0 <= 10) {
+                        let attacker = match death.attacker.filter(|p| player_info.contains_key(p))
+                        {
                             Some(a) => a,
                             None => died.clone(),
                         };
 
-                        let died_player = player_info.get(&died).unwrap();
-                        let attacker_player = player_info.get(&attacker).unwrap();
+                        let (died_player, attacker_player) =
+                            match player_info.get(&died).zip(player_info.get(&attacker)) {
+                                Some(p) => p,
+                                None => continue,
+                            };
+
+                        let died_entry = round_player(current_round, died_player);
+                        died_entry.survived = false;
+                        died_entry.end_health = 0;
+                        died_entry.equipment_saved = 0;
+                        let died_side = died_entry.team;
+                        record_death(current_round, died_side, current_tick);
+
+                        if attacker_player.team != died_player.team {
+                            round_player(current_round, attacker_player).kills += 1;
+                        }
+
+                        let distance = positions
+                            .get(attacker_player.xuid, current_tick)
+                            .zip(positions.get(died_player.xuid, current_tick))
+                            .filter(|_| attacker_player.xuid != died_player.xuid)
+                            .map(|(a, d)| {
+                                ((a.0 - d.0).powi(2) + (a.1 - d.1).powi(2) + (a.2 - d.2).powi(2))
+                                    .sqrt()
+                            });
 
                         RoundEvent::Kill {
                             attacker: attacker_player.xuid,
@@ -169,6 +412,13 @@ pub fn parse(buf: &[u8]) -> Result<PerRound, ()> {
                             weapon: death.weapon,
                             noscope: death.noscope.unwrap_or(false),
                             headshot: death.headshot.unwrap_or(false),
+                            attacker_place: place(Some(attacker), current_tick),
+                            died_place: place(Some(died), current_tick),
+                            penetrated: crate::raw_number(&death.penetrated)
+                                .is_some_and(|v| v > 0.0),
+                            thrusmoke: death.thrusmoke.unwrap_or(false),
+                            attackerblind: death.attackerblind.unwrap_or(false),
+                            distance,
                         }
                     }
                     _ => continue,
@@ -180,5 +430,121 @@ pub fn parse(buf: &[u8]) -> Result<PerRound, ()> {
         };
     }
 
-    Ok(PerRound { rounds })
+    for round in rounds.iter_mut() {
+        // The events also add the players involved, which includes coaches and casters
+        round.players.retain(|p| participants.played(p.steam_id));
+        round.players.sort_unstable_by_key(|p| p.steam_id);
+    }
+
+    let complete = completeness.complete();
+    if !complete {
+        // The round that was being played when the demo was cut off never ended
+        rounds.retain(|r| r.end != u32::MAX);
+    }
+
+    Ok(PerRound { rounds, complete })
+}
+
+fn update_controller(
+    state: &csdemo::parser::entities::EntityState,
+    controllers: &mut std::collections::HashMap<crate::heatmap::PawnID, ControllerState>,
+) {
+    let controller = controllers
+        .entry(crate::heatmap::PawnID::from(state.id))
+        .or_default();
+
+    for prop in state.props.iter() {
+        match prop.prop_info.prop_name.as_ref() {
+            "CCSPlayerController.m_steamID" => {
+                if let csdemo::parser::Variant::U64(v) = prop.value {
+                    controller.steam_id = v;
+                }
+            }
+            "CCSPlayerController.m_iTeamNum" => {
+                if let Some(v) = prop.value.as_u32() {
+                    controller.team = v as i32;
+                }
+            }
+            "CCSPlayerController.m_hPlayerPawn" => {
+                if let Some(v) = prop.value.as_u32() {
+                    controller.pawn = Some(crate::heatmap::PawnID::from(v));
+                }
+            }
+            "CCSPlayerController.m_iPawnHealth" => {
+                if let Some(v) = prop.value.as_u32() {
+                    controller.health = v;
+                }
+            }
+            "CCSPlayerController.m_bPawnIsAlive" => {
+                if let csdemo::parser::Variant::Bool(v) = prop.value {
+                    controller.alive = v;
+                }
+            }
+            _ => {}
+        };
+    }
+}
+
+fn record_death(round: &mut Round, side: i32, tick: u32) {
+    let mut alive = match round.alive.last() {
+        Some(a) => *a,
+        None => return,
+    };
+    match side {
+        2 => alive.t = alive.t.saturating_sub(1),
+        3 => alive.ct = alive.ct.saturating_sub(1),
+        _ => return,
+    };
+    alive.tick = tick;
+    round.alive.push(alive);
+}
+
+fn round_player<'r>(round: &'r mut Round, player: &csdemo::parser::Player) -> &'r mut RoundPlayer {
+    let idx = match round.players.iter().position(|p| p.steam_id == player.xuid) {
+        Some(idx) => idx,
+        None => {
+            round.players.push(RoundPlayer {
+                steam_id: player.xuid,
+                team: player.team,
+                survived: true,
+                ..Default::default()
+            });
+            round.players.len() - 1
+        }
+    };
+
+    &mut round.players[idx]
+}
+
+fn player_hurt(
+    hurt: &csdemo::game_event::PlayerHurt,
+    player_info: &std::collections::HashMap<csdemo::UserId, csdemo::parser::Player>,
+    player_life: &mut std::collections::HashMap<csdemo::UserId, u32>,
+    round: &mut Round,
+) {
+    let attacked_id = match hurt.userid {
+        Some(id) => id,
+        None => return,
+    };
+    let attacked_player = match player_info.get(&attacked_id) {
+        Some(p) => p,
+        None => return,
+    };
+
+    let n_health = crate::raw_number(&hurt.health)
+        .map(|v| v as u32)
+        .unwrap_or(0);
+    let previous_health = player_life.get(&attacked_id).copied().unwrap_or(100);
+    let dmg = previous_health.saturating_sub(n_health) as usize;
+    player_life.insert(attacked_id, n_health);
+
+    round_player(round, attacked_player).damage_received += dmg;
+
+    let attacking_player = match hurt.attacker.and_then(|a| player_info.get(&a)) {
+        Some(p) => p,
+        None => return,
+    };
+    if attacking_player.team != attacked_player.team {
+        round_player(round, attacking_player).damage_dealt += dmg;
+    }
 }
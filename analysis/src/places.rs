@@ -0,0 +1,53 @@
+/// The kills and deaths of a player at one callout
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CalloutStats {
+    pub steam_id: u64,
+    pub place: String,
+    pub kills: usize,
+    pub deaths: usize,
+}
+
+/// The place name in an update of a player pawn, if it changed
+pub(crate) fn pawn_place(state: &csdemo::parser::entities::EntityState) -> Option<&str> {
+    state
+        .get_prop("CCSPlayerPawn.m_szLastPlaceName")
+        .and_then(|p| match &p.value {
+            csdemo::parser::Variant::String(v) => Some(v.as_str()),
+            _ => None,
+        })
+}
+
+/// Counts the kills and deaths of every player per callout, ordered by player and callout
+pub fn callout_stats<'e, I>(events: I) -> Vec<CalloutStats>
+where
+    I: IntoIterator<Item = &'e crate::perround::RoundEvent>,
+{
+    let mut stats = std::collections::BTreeMap::<(u64, String), (usize, usize)>::new();
+    for event in events {
+        if let crate::perround::RoundEvent::Kill {
+            attacker,
+            died,
+            attacker_place,
+            died_place,
+            ..
+        } = event
+        {
+            if let Some(place) = attacker_place.as_ref().filter(|_| attacker != died) {
+                stats.entry((*attacker, place.clone())).or_default().0 += 1;
+            }
+            if let Some(place) = died_place.as_ref() {
+                stats.entry((*died, place.clone())).or_default().1 += 1;
+            }
+        }
+    }
+
+    stats
+        .into_iter()
+        .map(|((steam_id, place), (kills, deaths))| CalloutStats {
+            steam_id,
+            place,
+            kills,
+            deaths,
+        })
+        .collect()
+}
@@ -0,0 +1,258 @@
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PlayerConnection {
+    pub steam_id: u64,
+    pub name: String,
+    /// All the ingame ids the player had, a new one is assigned on every reconnect
+    pub user_ids: Vec<i32>,
+    /// The tick at which the player joined, 0 if they were already connected when the recording
+    /// started
+    pub join_tick: u32,
+    /// The tick at which the player left, `None` if they were still connected at the end
+    pub leave_tick: Option<u32>,
+    pub rounds_played: usize,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct Players {
+    pub players: Vec<PlayerConnection>,
+    /// Whether the demo could be read until the end of the match, players that were connected
+    /// when the demo was cut off have no leave tick
+    pub complete: bool,
+}
+
+impl Players {
+    pub fn get(&self, steam_id: u64) -> Option<&PlayerConnection> {
+        self.players.iter().find(|p| p.steam_id == steam_id)
+    }
+}
+
+impl PlayerConnection {
+    fn connect(&mut self, tick: u32) {
+        // Only the first join is relevant, reconnects are tracked by clearing the leave tick again
+        if self.leave_tick.take().is_none() && self.join_tick == 0 {
+            self.join_tick = tick;
+        }
+    }
+
+    fn disconnect(&mut self, tick: u32) {
+        self.leave_tick = Some(tick);
+    }
+}
+
+/// Merges the ingame ids of the players by their steam id, as a player gets a new one every time
+/// they reconnect
+fn connections<'p>(
+    player_info: impl IntoIterator<Item = (i32, u64, &'p str)>,
+) -> HashMap<u64, PlayerConnection> {
+    let mut players = HashMap::<u64, PlayerConnection>::new();
+    for (userid, steam_id, name) in player_info.into_iter().filter(|(_, s, _)| *s != 0) {
+        let entry = players.entry(steam_id).or_insert_with(|| PlayerConnection {
+            steam_id,
+            name: name.to_owned(),
+            user_ids: Vec::new(),
+            join_tick: 0,
+            leave_tick: None,
+            rounds_played: 0,
+        });
+        entry.user_ids.push(userid);
+    }
+    players
+}
+
+pub fn parse(buf: &[u8]) -> Result<Players, ()> {
+    let tmp = csdemo::Container::parse(buf).map_err(|e| ())?;
+    let output = csdemo::lazyparser::LazyParser::new(tmp);
+
+    let player_info = output.player_info();
+
+    let mut players = connections(
+        player_info
+            .iter()
+            .map(|(userid, p)| (userid.0, p.xuid, p.name.as_str())),
+    );
+
+    let steam_id = |userid: Option<csdemo::UserId>| {
+        userid
+            .and_then(|u| player_info.get(&u))
+            .map(|p| p.xuid)
+            .filter(|xuid| *xuid != 0)
+    };
+
+    let mut current_tick = 0;
+    let mut track = false;
+    let mut spawned = HashSet::<u64>::new();
+    let mut rounds_played = HashMap::<u64, usize>::new();
+    let mut completeness = crate::Completeness::default();
+    for event in output.events() {
+        let ge = match completeness.ok(event) {
+            Some(csdemo::DemoEvent::Tick(tick)) => {
+                current_tick = tick.tick();
+                continue;
+            }
+            Some(csdemo::DemoEvent::GameEvent(ge)) => ge,
+            _ => continue,
+        };
+        completeness.event(ge.as_ref());
+
+        match ge.as_ref() {
+            csdemo::game_event::GameEvent::PlayerConnectFull(connect) => {
+                if let Some(entry) = steam_id(connect.userid).and_then(|s| players.get_mut(&s)) {
+                    entry.connect(current_tick);
+                }
+            }
+            csdemo::game_event::GameEvent::PlayerDisconnect(disconnect) => {
+                if let Some(entry) = steam_id(disconnect.userid).and_then(|s| players.get_mut(&s)) {
+                    entry.disconnect(current_tick);
+                }
+            }
+            csdemo::game_event::GameEvent::RoundAnnounceMatchStart(_) => {
+                rounds_played.clear();
+                track = true;
+            }
+            csdemo::game_event::GameEvent::RoundPreStart(_) => {
+                spawned.clear();
+            }
+            csdemo::game_event::GameEvent::PlayerSpawn(spawn) => {
+                if let Some(xuid) = steam_id(spawn.userid) {
+                    spawned.insert(xuid);
+                }
+            }
+            csdemo::game_event::GameEvent::RoundOfficiallyEnded(_)
+            | csdemo::game_event::GameEvent::WinPanelMatch(_)
+                if track =>
+            {
+                for xuid in spawned.drain() {
+                    *rounds_played.entry(xuid).or_default() += 1;
+                }
+
+                if matches!(ge.as_ref(), csdemo::game_event::GameEvent::WinPanelMatch(_)) {
+                    track = false;
+                }
+            }
+            _ => {}
+        };
+    }
+
+    let mut players: Vec<_> = players
+        .into_values()
+        .map(|mut player| {
+            player.user_ids.sort_unstable();
+            player.rounds_played = rounds_played.get(&player.steam_id).copied().unwrap_or(0);
+            player
+        })
+        .collect();
+    players.sort_unstable_by_key(|p| p.user_ids.first().copied());
+
+    Ok(Players {
+        players,
+        complete: completeness.complete(),
+    })
+}
+
+/// Keeps track of which of the connected players actually played, using the player controllers.
+/// Coaches and the casters of GOTV demos are connected like players, but never play, so they are
+/// left out of the stats of every analysis
+#[derive(Debug, Default)]
+pub(crate) struct Participants {
+    /// The steam id of every controller entity
+    controllers: HashMap<i32, u64>,
+    states: HashMap<u64, ParticipantState>,
+}
+
+#[derive(Debug, Default)]
+struct ParticipantState {
+    team: i32,
+    coaching_team: i32,
+    /// Whether the controller was ever on one of the playing teams, without being a coach
+    played: bool,
+}
+
+impl Participants {
+    /// Updates the state of the player from the update of their controller, all other entities are
+    /// ignored
+    pub fn update(&mut self, state: &csdemo::parser::entities::EntityState) {
+        if state.class.as_ref() != "CCSPlayerController" {
+            return;
+        }
+
+        if let Some(csdemo::parser::Variant::U64(steam_id)) = state
+            .get_prop("CCSPlayerController.m_steamID")
+            .map(|p| &p.value)
+        {
+            if *steam_id != 0 {
+                self.controllers.insert(state.id, *steam_id);
+            }
+        }
+        let participant = match self.controllers.get(&state.id) {
+            Some(steam_id) => self.states.entry(*steam_id).or_default(),
+            None => return,
+        };
+
+        for prop in state.props.iter() {
+            match prop.prop_info.prop_name.as_ref() {
+                "CCSPlayerController.m_iTeamNum" => {
+                    participant.team = prop.value.as_i32().unwrap_or(participant.team);
+                }
+                "CCSPlayerController.m_iCoachingTeam" => {
+                    participant.coaching_team =
+                        prop.value.as_i32().unwrap_or(participant.coaching_team);
+                }
+                _ => {}
+            };
+        }
+
+        if participant.playing() {
+            participant.played = true;
+        }
+    }
+
+    /// Whether the player was on one of the playing teams at some point of the demo. Players
+    /// without a controller count as playing, so nothing is lost if the controllers are unknown
+    pub fn played(&self, steam_id: u64) -> bool {
+        self.states.get(&steam_id).map_or(true, |s| s.played)
+    }
+
+    /// Whether the player is currently on one of the playing teams
+    pub fn playing(&self, steam_id: u64) -> bool {
+        self.states.get(&steam_id).map_or(true, |s| s.playing())
+    }
+}
+
+impl ParticipantState {
+    fn playing(&self) -> bool {
+        matches!(self.team, 2 | 3) && self.coaching_team == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reconnect() {
+        let mut players = connections([
+            (2, 76561198000000001, "first"),
+            (3, 76561198000000002, "second"),
+            // The first player reconnected and got a new ingame id
+            (11, 76561198000000001, "first"),
+            // The casters of GOTV demos have no steam id
+            (12, 0, "GOTV"),
+        ]);
+        assert_eq!(2, players.len());
+
+        let player = players.get_mut(&76561198000000001).unwrap();
+        player.user_ids.sort_unstable();
+        assert_eq!(vec![2, 11], player.user_ids);
+
+        player.connect(100);
+        player.disconnect(2000);
+        player.connect(2500);
+        assert_eq!(100, player.join_tick);
+        assert_eq!(None, player.leave_tick);
+
+        player.disconnect(3000);
+        assert_eq!(Some(3000), player.leave_tick);
+    }
+}
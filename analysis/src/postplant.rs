@@ -0,0 +1,407 @@
+use std::collections::HashMap;
+
+use crate::heatmap::PawnID;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Outcome {
+    Defused,
+    Exploded,
+    /// All CTs were killed before the bomb exploded
+    Eliminated,
+    /// The round ended in any other way, like a surrender
+    Other,
+}
+
+impl Outcome {
+    fn from_reason(reason: &crate::perround::WinReason) -> Self {
+        match reason {
+            crate::perround::WinReason::BombDefused => Self::Defused,
+            crate::perround::WinReason::BombExploded => Self::Exploded,
+            crate::perround::WinReason::CTKilled => Self::Eliminated,
+            _ => Self::Other,
+        }
+    }
+
+    /// Whether the T side won the round
+    pub fn t_won(&self) -> bool {
+        matches!(self, Self::Exploded | Self::Eliminated)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PlantPosition {
+    pub steam_id: u64,
+    /// The position, in the same coordinates as the heatmaps
+    pub position: (f32, f32, f32),
+    pub place: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PostPlant {
+    /// Starting at 0
+    pub round: usize,
+    pub tick: u32,
+    /// The callout the bomb was planted at, like `BombsiteA`
+    pub site: Option<String>,
+    /// The team number the planting team started the match with
+    pub t_team: i32,
+    /// The team number the retaking team started the match with
+    pub ct_team: i32,
+    pub t_alive: usize,
+    pub ct_alive: usize,
+    /// The seconds that were left on the round timer when the bomb was planted
+    pub time_remaining: f32,
+    /// The alive Ts at the time of the plant
+    pub t_positions: Vec<PlantPosition>,
+    pub outcome: Outcome,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct PostPlants {
+    pub plants: Vec<PostPlant>,
+    /// Whether the demo could be read until the end of the match, only the rounds that were
+    /// finished are returned otherwise
+    pub complete: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PostPlantStats {
+    pub site: Option<String>,
+    /// The alive Ts and CTs at the time of the plant, `None` for all situations on the site
+    pub players: Option<(usize, usize)>,
+    pub rounds: usize,
+    pub defused: usize,
+    pub exploded: usize,
+    pub eliminated: usize,
+}
+
+impl PostPlantStats {
+    /// The rounds the T side won
+    pub fn t_wins(&self) -> usize {
+        self.exploded + self.eliminated
+    }
+}
+
+#[derive(Debug, Default)]
+struct RoundState {
+    start: u32,
+    end: u32,
+    freeze_end: Option<u32>,
+    /// The length of the round after the freeze time in seconds
+    round_time: u32,
+    winreason: Option<crate::perround::WinReason>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct PawnState {
+    team: u32,
+    alive: bool,
+}
+
+pub fn parse(buf: &[u8]) -> Result<PostPlants, ()> {
+    let tmp = csdemo::Container::parse(buf).map_err(|e| ())?;
+    let output = csdemo::lazyparser::LazyParser::new(tmp);
+
+    let player_info = output.player_info();
+    let tick_rate = crate::metadata::tick_rate(output.events());
+
+    let mut controllers = HashMap::<PawnID, crate::heatmap::ControllerState>::new();
+    let mut pawn_players = HashMap::<PawnID, u64>::new();
+    let mut entity_cells = HashMap::new();
+    let mut entity_offsets = HashMap::new();
+    let mut pawns = HashMap::<PawnID, PawnState>::new();
+    let mut states = crate::PlayerHistory::<PawnState>::default();
+    let mut places = crate::PlayerHistory::<String>::default();
+    let mut positions = crate::PlayerHistory::<(f32, f32, f32)>::default();
+    // The side every player started on, to tell the teams apart after switching sides
+    let mut starting_teams = HashMap::<u64, i32>::new();
+
+    let mut rounds: Vec<RoundState> = Vec::new();
+    let mut freeze_period = false;
+    let mut round_time = 0;
+    let mut completeness = crate::Completeness::default();
+    for entity in output.entities() {
+        let (tick, state) = match completeness.ok(entity) {
+            Some(e) => e,
+            None => continue,
+        };
+        completeness.entity(&state);
+
+        match state.class.as_ref() {
+            "CCSPlayerController" => {
+                crate::heatmap::update_pawn_players(&state, &mut controllers, &mut pawn_players);
+            }
+            "CCSGameRulesProxy" => {
+                let count = |name: &str| state.get_prop(name).and_then(|p| p.value.as_u32());
+
+                if let Some(count) = count("CCSGameRulesProxy.CCSGameRules.m_nRoundStartCount") {
+                    if rounds.len() < count.saturating_sub(1) as usize {
+                        rounds.push(RoundState {
+                            start: tick,
+                            end: u32::MAX,
+                            round_time,
+                            ..Default::default()
+                        });
+                    }
+                }
+                if let Some(count) = count("CCSGameRulesProxy.CCSGameRules.m_nRoundEndCount") {
+                    if rounds.len() == count.saturating_sub(1) as usize {
+                        if let Some(round) = rounds.last_mut().filter(|r| r.end == u32::MAX) {
+                            round.end = tick;
+                        }
+                    }
+                }
+
+                if let Some(value) = count("CCSGameRulesProxy.CCSGameRules.m_iRoundTime") {
+                    round_time = value;
+                    if let Some(round) = rounds.last_mut().filter(|r| r.freeze_end.is_none()) {
+                        round.round_time = value;
+                    }
+                }
+
+                if let Some(csdemo::parser::Variant::Bool(v)) = state
+                    .get_prop("CCSGameRulesProxy.CCSGameRules.m_bFreezePeriod")
+                    .map(|p| &p.value)
+                {
+                    if freeze_period && !v {
+                        if let Some(round) = rounds.last_mut().filter(|r| r.end == u32::MAX) {
+                            round.freeze_end = Some(tick);
+                        }
+                    }
+                    freeze_period = *v;
+                }
+
+                let round_win_reason = state
+                    .get_prop("CCSGameRulesProxy.CCSGameRules.m_eRoundWinReason")
+                    .and_then(|p| p.value.as_i32())
+                    .and_then(|v| crate::perround::ROUND_WIN_REASON.get(&v))
+                    .filter(|r| !matches!(r, crate::perround::WinReason::StillInProgress));
+                if let Some((reason, round)) = round_win_reason.zip(rounds.last_mut()) {
+                    round.winreason = Some(reason.clone());
+                }
+            }
+            "CCSPlayerPawn" => {
+                let id = PawnID::from(state.id);
+                let pawn = pawns.entry(id).or_default();
+                for prop in state.props.iter() {
+                    match prop.prop_info.prop_name.as_ref() {
+                        "CCSPlayerPawn.m_iTeamNum" => {
+                            pawn.team = prop.value.as_u32().unwrap_or(pawn.team);
+                        }
+                        "CCSPlayerPawn.m_lifeState" => {
+                            // 0 means alive
+                            if let Some(v) = prop.value.as_u32() {
+                                pawn.alive = v == 0;
+                            }
+                        }
+                        _ => {}
+                    };
+                }
+
+                let position =
+                    crate::heatmap::update_position(&state, &mut entity_cells, &mut entity_offsets);
+                let steam_id = match pawn_players.get(&id) {
+                    Some(s) => *s,
+                    None => continue,
+                };
+
+                states.update(steam_id, tick, *pawn);
+                if matches!(pawn.team, 2 | 3) {
+                    starting_teams.entry(steam_id).or_insert(pawn.team as i32);
+                }
+                if let Some(place) = crate::places::pawn_place(&state) {
+                    places.update(steam_id, tick, place.to_owned());
+                }
+                if positions.last_tick(steam_id).map_or(true, |last| {
+                    tick >= last + crate::perround::POSITION_INTERVAL
+                }) {
+                    positions.update(steam_id, tick, position);
+                }
+            }
+            _ => {}
+        };
+    }
+
+    let mut plants = Vec::new();
+    let mut current_tick = 0;
+    for event in output.events() {
+        let ge = match completeness.ok(event) {
+            Some(csdemo::DemoEvent::Tick(tick)) => {
+                current_tick = tick.tick();
+                continue;
+            }
+            Some(csdemo::DemoEvent::GameEvent(ge)) => ge,
+            _ => continue,
+        };
+        completeness.event(ge.as_ref());
+
+        let planted = match ge.as_ref() {
+            csdemo::game_event::GameEvent::BombPlanted(planted) => planted,
+            _ => continue,
+        };
+        let (round_idx, round) = match rounds
+            .iter()
+            .enumerate()
+            .find(|(_, r)| r.start <= current_tick && current_tick <= r.end)
+        {
+            Some(r) => r,
+            None => continue,
+        };
+
+        let alive: Vec<(u64, u32)> = states
+            .players()
+            .filter_map(|steam_id| {
+                let state = states.get(steam_id, current_tick)?;
+                (state.alive && matches!(state.team, 2 | 3)).then_some((steam_id, state.team))
+            })
+            .collect();
+        let side_team = |side: u32| {
+            crate::roundtypes::majority(
+                alive
+                    .iter()
+                    .filter(|(_, team)| *team == side)
+                    .filter_map(|(steam_id, _)| starting_teams.get(steam_id).copied()),
+            )
+            .unwrap_or(side as i32)
+        };
+
+        let mut t_positions: Vec<PlantPosition> = alive
+            .iter()
+            .filter(|(_, team)| *team == 2)
+            .filter_map(|(steam_id, _)| {
+                Some(PlantPosition {
+                    steam_id: *steam_id,
+                    position: *positions.get(*steam_id, current_tick)?,
+                    place: places.get(*steam_id, current_tick).cloned(),
+                })
+            })
+            .collect();
+        t_positions.sort_unstable_by_key(|p| p.steam_id);
+
+        let elapsed = round
+            .freeze_end
+            .map(|start| current_tick.saturating_sub(start) as f32)
+            .unwrap_or(0.0)
+            / tick_rate as f32;
+
+        plants.push(PostPlant {
+            round: round_idx,
+            tick: current_tick,
+            site: planted
+                .userid
+                .and_then(|u| player_info.get(&u))
+                .and_then(|p| places.get(p.xuid, current_tick))
+                .filter(|p| !p.is_empty())
+                .cloned(),
+            t_team: side_team(2),
+            ct_team: side_team(3),
+            t_alive: alive.iter().filter(|(_, team)| *team == 2).count(),
+            ct_alive: alive.iter().filter(|(_, team)| *team == 3).count(),
+            time_remaining: (round.round_time as f32 - elapsed).max(0.0),
+            t_positions,
+            outcome: round
+                .winreason
+                .as_ref()
+                .map(Outcome::from_reason)
+                .unwrap_or(Outcome::Other),
+        });
+    }
+
+    let complete = completeness.complete();
+    if !complete {
+        // The round that was being played when the demo was cut off never ended
+        plants.retain(|p| rounds.get(p.round).is_some_and(|r| r.end != u32::MAX));
+    }
+
+    Ok(PostPlants { plants, complete })
+}
+
+/// The outcomes of the plants per site, once for all situations on the site and once for every
+/// number of alive players. Ordered by site, with the totals of a site first and then starting
+/// with the situations that had the most players alive
+pub fn post_plant_stats<'p, I>(plants: I) -> Vec<PostPlantStats>
+where
+    I: IntoIterator<Item = &'p PostPlant>,
+{
+    let mut stats = HashMap::<(Option<&str>, Option<(usize, usize)>), PostPlantStats>::new();
+    for plant in plants {
+        let site = plant.site.as_deref();
+        for players in [None, Some((plant.t_alive, plant.ct_alive))] {
+            let entry = stats
+                .entry((site, players))
+                .or_insert_with(|| PostPlantStats {
+                    site: plant.site.clone(),
+                    players,
+                    rounds: 0,
+                    defused: 0,
+                    exploded: 0,
+                    eliminated: 0,
+                });
+            entry.rounds += 1;
+            match plant.outcome {
+                Outcome::Defused => entry.defused += 1,
+                Outcome::Exploded => entry.exploded += 1,
+                Outcome::Eliminated => entry.eliminated += 1,
+                Outcome::Other => {}
+            };
+        }
+    }
+
+    let mut result: Vec<_> = stats.into_values().collect();
+    result.sort_unstable_by(|s1, s2| {
+        let players = |s: &PostPlantStats| {
+            s.players
+                .map(|(t, ct)| (core::cmp::Reverse(t + ct), core::cmp::Reverse(t)))
+        };
+        s1.site.cmp(&s2.site).then(players(s1).cmp(&players(s2)))
+    });
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plant(site: &str, t_alive: usize, ct_alive: usize, outcome: Outcome) -> PostPlant {
+        PostPlant {
+            round: 0,
+            tick: 0,
+            site: Some(site.to_owned()),
+            t_team: 2,
+            ct_team: 3,
+            t_alive,
+            ct_alive,
+            time_remaining: 30.0,
+            t_positions: Vec::new(),
+            outcome,
+        }
+    }
+
+    #[test]
+    fn stats() {
+        let plants = [
+            plant("BombsiteA", 4, 3, Outcome::Exploded),
+            plant("BombsiteA", 4, 3, Outcome::Defused),
+            plant("BombsiteA", 2, 2, Outcome::Eliminated),
+            plant("BombsiteB", 5, 5, Outcome::Other),
+        ];
+
+        let result = post_plant_stats(plants.iter());
+        let situations: Vec<_> = result
+            .iter()
+            .map(|s| (s.site.as_deref().unwrap(), s.players, s.rounds, s.t_wins()))
+            .collect();
+        assert_eq!(
+            vec![
+                ("BombsiteA", None, 3, 2),
+                ("BombsiteA", Some((4, 3)), 2, 1),
+                ("BombsiteA", Some((2, 2)), 1, 1),
+                ("BombsiteB", None, 1, 0),
+                ("BombsiteB", Some((5, 5)), 1, 0),
+            ],
+            situations
+        );
+        assert_eq!(1, result[0].defused);
+        assert_eq!(2, plants.iter().filter(|p| p.outcome.t_won()).count());
+    }
+}
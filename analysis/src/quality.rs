@@ -0,0 +1,187 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+pub enum MissingPlayerInfo {
+    /// An event referenced an ingame id, that is not part of the player info
+    UserId(i32),
+    /// A player controller has a steam id, that is not part of the player info
+    SteamId(u64),
+}
+
+/// Everything the analyses silently skip over, to know when their numbers can't be trusted
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct QualityReport {
+    /// The number of events that failed to parse, by the kind of error
+    pub failed_events: BTreeMap<String, usize>,
+    /// The number of entity updates that failed to parse, by the kind of error
+    pub failed_entities: BTreeMap<String, usize>,
+    pub players_missing_info: Vec<MissingPlayerInfo>,
+    /// The numbers of all the finished rounds without a win reason, starting at 0
+    pub rounds_missing_winreason: Vec<usize>,
+    /// The map, if there is no minimap for it
+    pub unknown_map: Option<String>,
+    pub complete: bool,
+}
+
+impl QualityReport {
+    pub fn is_clean(&self) -> bool {
+        self.failed_events.is_empty()
+            && self.failed_entities.is_empty()
+            && self.players_missing_info.is_empty()
+            && self.rounds_missing_winreason.is_empty()
+            && self.unknown_map.is_none()
+    }
+}
+
+pub fn parse(buf: &[u8]) -> Result<QualityReport, ()> {
+    let tmp = csdemo::Container::parse(buf).map_err(|e| ())?;
+    let output = csdemo::lazyparser::LazyParser::new(tmp);
+
+    let header = output.file_header().ok_or(())?;
+    let player_info = output.player_info();
+
+    let mut report = QualityReport::default();
+    let mut completeness = crate::Completeness::default();
+
+    let mut missing = BTreeSet::new();
+    let mut check_user = |userid: Option<csdemo::UserId>| {
+        if let Some(userid) = userid.filter(|u| !player_info.contains_key(u)) {
+            missing.insert(MissingPlayerInfo::UserId(userid.0));
+        }
+    };
+    for event in output.events() {
+        let event = match event {
+            Ok(e) => e,
+            Err(e) => {
+                *report.failed_events.entry(error_kind(&e)).or_default() += 1;
+                completeness.ok(Err::<(), _>(e));
+                continue;
+            }
+        };
+
+        let ge = match event {
+            csdemo::DemoEvent::GameEvent(ge) => ge,
+            _ => continue,
+        };
+        completeness.event(ge.as_ref());
+
+        match ge.as_ref() {
+            csdemo::game_event::GameEvent::PlayerDeath(death) => {
+                check_user(death.userid);
+                check_user(death.attacker);
+            }
+            csdemo::game_event::GameEvent::PlayerHurt(hurt) => {
+                check_user(hurt.userid);
+                check_user(hurt.attacker);
+            }
+            csdemo::game_event::GameEvent::PlayerSpawn(spawn) => {
+                check_user(spawn.userid);
+            }
+            _ => {}
+        };
+    }
+
+    let known_steam_ids: BTreeSet<u64> = player_info.values().map(|p| p.xuid).collect();
+    // Whether each round got a win reason and how many rounds have ended
+    let mut rounds: Vec<bool> = Vec::new();
+    let mut ended_rounds = 0;
+    for entity in output.entities() {
+        let (_, state) = match entity {
+            Ok(e) => e,
+            Err(e) => {
+                *report.failed_entities.entry(error_kind(&e)).or_default() += 1;
+                completeness.ok(Err::<(), _>(e));
+                continue;
+            }
+        };
+        completeness.entity(&state);
+
+        match state.class.as_ref() {
+            "CCSPlayerController" => {
+                let steam_id = state
+                    .get_prop("CCSPlayerController.m_steamID")
+                    .and_then(|p| match p.value {
+                        csdemo::parser::Variant::U64(v) => Some(v),
+                        _ => None,
+                    })
+                    .unwrap_or(0);
+                if steam_id != 0 && !known_steam_ids.contains(&steam_id) {
+                    missing.insert(MissingPlayerInfo::SteamId(steam_id));
+                }
+            }
+            "CCSGameRulesProxy" => {
+                let count = |name: &str| state.get_prop(name).and_then(|p| p.value.as_u32());
+
+                if let Some(count) = count("CCSGameRulesProxy.CCSGameRules.m_nRoundStartCount") {
+                    while rounds.len() < count.saturating_sub(1) as usize {
+                        rounds.push(false);
+                    }
+                }
+                if let Some(count) = count("CCSGameRulesProxy.CCSGameRules.m_nRoundEndCount") {
+                    ended_rounds = ended_rounds.max(count.saturating_sub(1) as usize);
+                }
+
+                let has_reason = state
+                    .get_prop("CCSGameRulesProxy.CCSGameRules.m_eRoundWinReason")
+                    .and_then(|p| p.value.as_i32())
+                    .and_then(|v| crate::perround::ROUND_WIN_REASON.get(&v))
+                    .is_some_and(|r| !matches!(r, crate::perround::WinReason::StillInProgress));
+                if let Some(round) = rounds.last_mut().filter(|_| has_reason) {
+                    *round = true;
+                }
+            }
+            _ => {}
+        };
+    }
+
+    report.players_missing_info = missing.into_iter().collect();
+    report.rounds_missing_winreason = rounds
+        .iter()
+        .take(ended_rounds)
+        .enumerate()
+        .filter(|(_, has_reason)| !**has_reason)
+        .map(|(number, _)| number)
+        .collect();
+
+    let map = header.map_name().to_owned();
+    if !crate::minimap::MINIMAP_COORDINATES.contains_key(&map) {
+        report.unknown_map = Some(map);
+    }
+
+    report.complete = completeness.complete();
+
+    Ok(report)
+}
+
+/// The name of the error variant, without any of the data attached to it
+fn error_kind<E>(error: &E) -> String
+where
+    E: core::fmt::Debug,
+{
+    let debug = format!("{:?}", error);
+    debug
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .next()
+        .unwrap_or_default()
+        .to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    enum TestError {
+        Simple,
+        Tuple(usize),
+        Struct { inner: usize },
+    }
+
+    #[test]
+    fn error_kinds() {
+        assert_eq!("Simple", error_kind(&TestError::Simple));
+        assert_eq!("Tuple", error_kind(&TestError::Tuple(3)));
+        assert_eq!("Struct", error_kind(&TestError::Struct { inner: 3 }));
+    }
+}
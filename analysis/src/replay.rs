@@ -0,0 +1,629 @@
+use std::collections::HashMap;
+
+use crate::heatmap::PawnID;
+
+pub struct Config {
+    /// The number of samples per second
+    pub sample_rate: u32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self { sample_rate: 8 }
+    }
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct Replay {
+    /// The tick rate of the server, to convert the ticks of the frames into seconds
+    pub tick_rate: u32,
+    pub sample_rate: u32,
+    pub rounds: Vec<RoundReplay>,
+    /// Whether the demo could be read until the end of the match, only the rounds that were
+    /// finished are returned otherwise
+    pub complete: bool,
+}
+
+/// A round contains a sample of every player several times a second, so it is serialized in a
+/// more compact form than the one used here
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(from = "StoredRoundReplay", into = "StoredRoundReplay")]
+pub struct RoundReplay {
+    pub start: u32,
+    pub end: u32,
+    /// The tick at which the freeze time ended and the players could start moving
+    pub freeze_end: Option<u32>,
+    /// The steam ids of the players in the round, the samples of each frame are in the same order
+    pub players: Vec<u64>,
+    pub frames: Vec<Frame>,
+    pub kills: Vec<Kill>,
+    pub grenades: Vec<Grenade>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Frame {
+    pub tick: u32,
+    /// The samples for the players of the round, `None` if the player had no pawn at this point.
+    /// Players that first showed up later on in the round are missing from the earlier frames
+    pub players: Vec<Option<PlayerSample>>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PlayerSample {
+    pub team: u32,
+    /// The position, in the same coordinates as the heatmaps
+    pub position: (f32, f32, f32),
+    /// The horizontal view angle in degrees
+    pub yaw: f32,
+    pub health: u32,
+    pub armor: u32,
+    pub weapon: Option<String>,
+    pub alive: bool,
+    /// The callout the player was at, `None` outside of any named area
+    #[serde(default)]
+    pub place: Option<String>,
+    #[serde(default)]
+    pub crouched: bool,
+    /// Moving slowly and silently, by holding the walk key
+    #[serde(default)]
+    pub walking: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Kill {
+    pub tick: u32,
+    /// `None` for deaths without another player as the attacker (fall damage, the bomb, etc.)
+    pub attacker: Option<u64>,
+    pub died: u64,
+    pub weapon: Option<String>,
+    /// The last sampled position of the player who died
+    pub position: Option<(f32, f32, f32)>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Grenade {
+    /// The name of the projectile, like `SmokeGrenade` or `Molotov`
+    pub kind: String,
+    pub thrower: Option<u64>,
+    /// The sampled positions of the projectile, as `(tick, x, y, z)`
+    pub points: Vec<(u32, f32, f32, f32)>,
+}
+
+/// The samples are stored as tuples instead of objects, with the weapon and place names stored
+/// once per round and referred to by their index
+#[derive(serde::Serialize, serde::Deserialize)]
+struct StoredRoundReplay {
+    start: u32,
+    end: u32,
+    #[serde(default)]
+    freeze_end: Option<u32>,
+    players: Vec<u64>,
+    /// The weapon and place names used in the samples
+    strings: Vec<String>,
+    /// The tick and samples of every frame
+    frames: Vec<(u32, Vec<Option<StoredSample>>)>,
+    kills: Vec<Kill>,
+    grenades: Vec<Grenade>,
+}
+
+/// A [`PlayerSample`] stored as an array instead of an object
+#[derive(Clone, Copy, serde::Serialize, serde::Deserialize)]
+struct StoredSample(
+    /// Team
+    u32,
+    /// Position
+    (f32, f32, f32),
+    /// Yaw
+    f32,
+    /// Health
+    u32,
+    /// Armor
+    u32,
+    /// Weapon, as an index into the strings of the round
+    Option<u32>,
+    /// Place, as an index into the strings of the round
+    Option<u32>,
+    /// Alive, crouched and walking flags
+    u8,
+);
+
+const SAMPLE_ALIVE: u8 = 1;
+const SAMPLE_CROUCHED: u8 = 2;
+const SAMPLE_WALKING: u8 = 4;
+
+impl From<RoundReplay> for StoredRoundReplay {
+    fn from(round: RoundReplay) -> Self {
+        let mut strings = Vec::new();
+        let mut indices = HashMap::<String, u32>::new();
+        let mut intern = |value: Option<String>| {
+            value.map(|value| {
+                *indices.entry(value).or_insert_with_key(|value| {
+                    strings.push(value.clone());
+                    strings.len() as u32 - 1
+                })
+            })
+        };
+
+        let mut frames = Vec::with_capacity(round.frames.len());
+        for frame in round.frames {
+            let samples = frame
+                .players
+                .into_iter()
+                .map(|sample| {
+                    let sample = sample?;
+                    let flags = [
+                        (sample.alive, SAMPLE_ALIVE),
+                        (sample.crouched, SAMPLE_CROUCHED),
+                        (sample.walking, SAMPLE_WALKING),
+                    ]
+                    .into_iter()
+                    .filter(|(set, _)| *set)
+                    .fold(0, |flags, (_, flag)| flags | flag);
+
+                    Some(StoredSample(
+                        sample.team,
+                        sample.position,
+                        sample.yaw,
+                        sample.health,
+                        sample.armor,
+                        intern(sample.weapon),
+                        intern(sample.place),
+                        flags,
+                    ))
+                })
+                .collect();
+            frames.push((frame.tick, samples));
+        }
+
+        Self {
+            start: round.start,
+            end: round.end,
+            freeze_end: round.freeze_end,
+            players: round.players,
+            strings,
+            frames,
+            kills: round.kills,
+            grenades: round.grenades,
+        }
+    }
+}
+
+impl From<StoredRoundReplay> for RoundReplay {
+    fn from(round: StoredRoundReplay) -> Self {
+        let string = |idx: Option<u32>| idx.and_then(|i| round.strings.get(i as usize)).cloned();
+
+        let frames = round
+            .frames
+            .iter()
+            .map(|(tick, samples)| Frame {
+                tick: *tick,
+                players: samples
+                    .iter()
+                    .map(|sample| {
+                        let StoredSample(team, position, yaw, health, armor, weapon, place, flags) =
+                            (*sample)?;
+                        Some(PlayerSample {
+                            team,
+                            position,
+                            yaw,
+                            health,
+                            armor,
+                            weapon: string(weapon),
+                            alive: flags & SAMPLE_ALIVE != 0,
+                            place: string(place),
+                            crouched: flags & SAMPLE_CROUCHED != 0,
+                            walking: flags & SAMPLE_WALKING != 0,
+                        })
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        Self {
+            start: round.start,
+            end: round.end,
+            freeze_end: round.freeze_end,
+            players: round.players,
+            frames,
+            kills: round.kills,
+            grenades: round.grenades,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct PawnState {
+    team: u32,
+    position: (f32, f32, f32),
+    yaw: f32,
+    health: u32,
+    armor: u32,
+    weapon: Option<PawnID>,
+    alive: bool,
+    place: String,
+    crouched: bool,
+    walking: bool,
+}
+
+pub fn parse(config: &Config, buf: &[u8]) -> Result<Replay, ()> {
+    let tmp = csdemo::Container::parse(buf).map_err(|e| ())?;
+    let output = csdemo::lazyparser::LazyParser::new(tmp);
+
+    let player_info = output.player_info();
+
+    let tick_rate = crate::metadata::tick_rate(output.events());
+    let interval = (tick_rate / config.sample_rate.max(1)).max(1);
+
+    let mut controllers = HashMap::<PawnID, crate::heatmap::ControllerState>::new();
+    let mut pawn_players = HashMap::<PawnID, u64>::new();
+    let mut entity_cells = HashMap::new();
+    let mut entity_offsets = HashMap::new();
+    let mut entity_classes = HashMap::<PawnID, String>::new();
+    let mut pawns = HashMap::<PawnID, PawnState>::new();
+    // The grenade of the current round, that each projectile entity belongs to, with the tick and
+    // position of its last update
+    let mut projectiles = HashMap::<PawnID, (usize, u32, (f32, f32, f32))>::new();
+
+    let mut rounds: Vec<RoundReplay> = Vec::new();
+    let mut participants = crate::players::Participants::default();
+    let mut current_tick = 0;
+    let mut next_sample = 0;
+    let mut freeze_period = false;
+    let mut completeness = crate::Completeness::default();
+    for entity in output.entities() {
+        let (tick, state) = match completeness.ok(entity) {
+            Some(e) => e,
+            None => continue,
+        };
+        completeness.entity(&state);
+        participants.update(&state);
+
+        // All the updates for a tick are done, so the state at the end of it can be sampled
+        if tick != current_tick {
+            let round = rounds.last_mut().filter(|r| r.end == u32::MAX);
+            if let Some(round) = round.filter(|_| current_tick >= next_sample) {
+                sample(
+                    round,
+                    current_tick,
+                    &controllers,
+                    &pawns,
+                    &entity_classes,
+                    &participants,
+                );
+                next_sample = current_tick + interval;
+            }
+            current_tick = tick;
+        }
+
+        let entity_id = PawnID::from(state.id);
+        let class: &str = state.class.as_ref();
+        match class {
+            "CCSPlayerController" => {
+                crate::heatmap::update_pawn_players(&state, &mut controllers, &mut pawn_players);
+            }
+            "CCSGameRulesProxy" => {
+                let count = |name: &str| state.get_prop(name).and_then(|p| p.value.as_u32());
+
+                if let Some(count) = count("CCSGameRulesProxy.CCSGameRules.m_nRoundStartCount") {
+                    if rounds.len() < count.saturating_sub(1) as usize {
+                        rounds.push(RoundReplay {
+                            start: tick,
+                            end: u32::MAX,
+                            ..Default::default()
+                        });
+                        projectiles.clear();
+                        next_sample = tick;
+                    }
+                }
+                if let Some(count) = count("CCSGameRulesProxy.CCSGameRules.m_nRoundEndCount") {
+                    if rounds.len() == count.saturating_sub(1) as usize {
+                        if let Some(round) = rounds.last_mut().filter(|r| r.end == u32::MAX) {
+                            round.end = tick;
+                        }
+                    }
+                }
+
+                if let Some(csdemo::parser::Variant::Bool(v)) = state
+                    .get_prop("CCSGameRulesProxy.CCSGameRules.m_bFreezePeriod")
+                    .map(|p| &p.value)
+                {
+                    if freeze_period && !v {
+                        if let Some(round) = rounds.last_mut().filter(|r| r.end == u32::MAX) {
+                            round.freeze_end = Some(tick);
+                        }
+                    }
+                    freeze_period = *v;
+                }
+            }
+            "CCSPlayerPawn" => {
+                let position =
+                    crate::heatmap::update_position(&state, &mut entity_cells, &mut entity_offsets);
+                update_pawn(&state, position, pawns.entry(entity_id).or_default());
+            }
+            _ if crate::grenades::projectile_kind(class).is_some() => {
+                let round = match rounds.last_mut().filter(|r| r.end == u32::MAX) {
+                    Some(r) => r,
+                    None => continue,
+                };
+                let position =
+                    crate::heatmap::update_position(&state, &mut entity_cells, &mut entity_offsets);
+
+                let grenade = projectiles
+                    .get(&entity_id)
+                    .filter(|(_, last_tick, last_position)| {
+                        let last = (*last_tick, *last_position);
+                        crate::grenades::same_grenade(last, tick, position, tick_rate)
+                    })
+                    .map(|(idx, _, _)| *idx)
+                    .unwrap_or_else(|| {
+                        round.grenades.push(Grenade {
+                            kind: crate::grenades::projectile_kind(class)
+                                .unwrap_or(class)
+                                .to_owned(),
+                            thrower: None,
+                            points: Vec::new(),
+                        });
+                        round.grenades.len() - 1
+                    });
+                projectiles.insert(entity_id, (grenade, tick, position));
+                let grenade = &mut round.grenades[grenade];
+
+                let thrower =
+                    crate::grenades::projectile_thrower(&state).and_then(|p| pawn_players.get(&p));
+                if let Some(thrower) = thrower {
+                    grenade.thrower = Some(*thrower);
+                }
+
+                if grenade
+                    .points
+                    .last()
+                    .map_or(true, |p| tick >= p.0 + interval)
+                {
+                    let (x, y, z) = position;
+                    grenade.points.push((tick, x, y, z));
+                }
+            }
+            _ => {
+                // Only the classes are needed, to get the names of the active weapons
+                if entity_classes.get(&entity_id).map(|c| c.as_str()) != Some(class) {
+                    entity_classes.insert(entity_id, class.to_owned());
+                }
+            }
+        };
+    }
+
+    let mut current_tick = 0;
+    for event in output.events() {
+        let ge = match completeness.ok(event) {
+            Some(csdemo::DemoEvent::Tick(tick)) => {
+                current_tick = tick.tick();
+                continue;
+            }
+            Some(csdemo::DemoEvent::GameEvent(ge)) => ge,
+            _ => continue,
+        };
+        completeness.event(ge.as_ref());
+
+        let death = match ge.as_ref() {
+            csdemo::game_event::GameEvent::PlayerDeath(death) => death,
+            _ => continue,
+        };
+
+        let steam_id = |userid: Option<csdemo::UserId>| {
+            userid
+                .and_then(|u| player_info.get(&u))
+                .map(|p| p.xuid)
+                .filter(|xuid| *xuid != 0)
+        };
+        let died = match steam_id(death.userid) {
+            Some(d) => d,
+            None => continue,
+        };
+        let round = match rounds
+            .iter_mut()
+            .find(|r| r.start <= current_tick && current_tick <= r.end)
+        {
+            Some(r) => r,
+            None => continue,
+        };
+
+        let position = round
+            .players
+            .iter()
+            .position(|p| *p == died)
+            .and_then(|idx| {
+                round
+                    .frames
+                    .iter()
+                    .rev()
+                    .filter(|f| f.tick <= current_tick)
+                    .find_map(|f| f.players.get(idx).cloned().flatten())
+                    .map(|s| s.position)
+            });
+
+        round.kills.push(Kill {
+            tick: current_tick,
+            attacker: steam_id(death.attacker).filter(|a| *a != died),
+            died,
+            weapon: death.weapon.clone(),
+            position,
+        });
+    }
+
+    let complete = completeness.complete();
+    if !complete {
+        // The round that was being played when the demo was cut off never ended
+        rounds.retain(|r| r.end != u32::MAX);
+    }
+
+    Ok(Replay {
+        tick_rate,
+        sample_rate: tick_rate / interval,
+        rounds,
+        complete,
+    })
+}
+
+fn update_pawn(
+    state: &csdemo::parser::entities::EntityState,
+    position: (f32, f32, f32),
+    pawn: &mut PawnState,
+) {
+    pawn.position = position;
+
+    for prop in state.props.iter() {
+        match prop.prop_info.prop_name.as_ref() {
+            "CCSPlayerPawn.m_iTeamNum" => {
+                pawn.team = prop.value.as_u32().unwrap_or(pawn.team);
+            }
+            "CCSPlayerPawn.m_iHealth" => {
+                pawn.health = prop.value.as_u32().unwrap_or(pawn.health);
+            }
+            "CCSPlayerPawn.m_ArmorValue" => {
+                pawn.armor = prop.value.as_u32().unwrap_or(pawn.armor);
+            }
+            "CCSPlayerPawn.m_lifeState" => {
+                // 0 means alive
+                if let Some(v) = prop.value.as_u32() {
+                    pawn.alive = v == 0;
+                }
+            }
+            "CCSPlayerPawn.m_angEyeAngles" => {
+                // Pitch, yaw and roll
+                if let csdemo::parser::Variant::VecXYZ([_, yaw, _]) = prop.value {
+                    pawn.yaw = yaw;
+                }
+            }
+            "CCSPlayerPawn.m_pWeaponServices.m_hActiveWeapon" => {
+                pawn.weapon = prop.value.as_u32().map(PawnID::from);
+            }
+            "CCSPlayerPawn.CCSPlayer_MovementServices.m_bDucked" => {
+                if let csdemo::parser::Variant::Bool(v) = prop.value {
+                    pawn.crouched = v;
+                }
+            }
+            "CCSPlayerPawn.m_bIsWalking" => {
+                if let csdemo::parser::Variant::Bool(v) = prop.value {
+                    pawn.walking = v;
+                }
+            }
+            _ => {}
+        };
+    }
+
+    if let Some(place) = crate::places::pawn_place(state) {
+        pawn.place = place.to_owned();
+    }
+}
+
+fn sample(
+    round: &mut RoundReplay,
+    tick: u32,
+    controllers: &HashMap<PawnID, crate::heatmap::ControllerState>,
+    pawns: &HashMap<PawnID, PawnState>,
+    entity_classes: &HashMap<PawnID, String>,
+    participants: &crate::players::Participants,
+) {
+    let mut samples = vec![None; round.players.len()];
+    for controller in controllers.values() {
+        let (steam_id, pawn) = match (controller.steam_id, controller.pawn) {
+            (Some(s), Some(p)) if participants.playing(s) => (s, p),
+            _ => continue,
+        };
+        let pawn = match pawns.get(&pawn).filter(|p| matches!(p.team, 2 | 3)) {
+            Some(p) => p,
+            None => continue,
+        };
+
+        let idx = match round.players.iter().position(|p| *p == steam_id) {
+            Some(idx) => idx,
+            None => {
+                round.players.push(steam_id);
+                samples.push(None);
+                round.players.len() - 1
+            }
+        };
+
+        samples[idx] = Some(PlayerSample {
+            team: pawn.team,
+            position: pawn.position,
+            yaw: pawn.yaw,
+            health: pawn.health,
+            armor: pawn.armor,
+            weapon: pawn
+                .weapon
+                .and_then(|w| entity_classes.get(&w))
+                .map(|class| weapon_name(class).to_owned()),
+            alive: pawn.alive,
+            place: Some(pawn.place.clone()).filter(|p| !p.is_empty()),
+            crouched: pawn.crouched,
+            walking: pawn.walking,
+        });
+    }
+
+    round.frames.push(Frame {
+        tick,
+        players: samples,
+    });
+}
+
+/// The weapon entities are named `CWeaponAWP`, `CAK47`, `CKnife`, etc.
+fn weapon_name(class: &str) -> &str {
+    class
+        .strip_prefix("CWeapon")
+        .or_else(|| class.strip_prefix('C'))
+        .unwrap_or(class)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stored_roundtrip() {
+        let sample = |weapon: &str, place: Option<&str>, alive| PlayerSample {
+            team: 2,
+            position: (-512.5, 1024.0, 64.25),
+            yaw: 90.0,
+            health: if alive { 100 } else { 0 },
+            armor: 100,
+            weapon: Some(weapon.to_owned()),
+            alive,
+            place: place.map(|p| p.to_owned()),
+            crouched: true,
+            walking: false,
+        };
+        let round = RoundReplay {
+            start: 100,
+            end: 3000,
+            players: vec![76561198000000001, 76561198000000002],
+            frames: vec![
+                Frame {
+                    tick: 100,
+                    players: vec![Some(sample("ak47", Some("TSpawn"), true)), None],
+                },
+                Frame {
+                    tick: 108,
+                    players: vec![
+                        Some(sample("ak47", None, false)),
+                        Some(sample("knife", Some("TSpawn"), true)),
+                    ],
+                },
+            ],
+            ..Default::default()
+        };
+
+        let stored = serde_json::to_value(&round).unwrap();
+        assert_eq!(
+            serde_json::json!(["ak47", "TSpawn", "knife"]),
+            stored["strings"]
+        );
+
+        let result: RoundReplay = serde_json::from_value(stored).unwrap();
+        assert_eq!(round.players, result.players);
+        for (expected, frame) in round.frames.iter().zip(result.frames.iter()) {
+            assert_eq!(expected.tick, frame.tick);
+            assert_eq!(expected.players, frame.players);
+        }
+    }
+}
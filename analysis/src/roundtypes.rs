@@ -0,0 +1,246 @@
+/// The average equipment value per player below which a team is saving money
+pub const ECO_BOUND: u32 = 1500;
+/// The average equipment value per player from which a team has bought full rifles and utility
+pub const FULL_BUY_BOUND: u32 = 3500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum BuyType {
+    Pistol,
+    /// A buy against a team that is saving
+    AntiEco,
+    Eco,
+    Force,
+    GunRound,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TeamRound {
+    /// The team number the team started the match with, it stays the same after switching sides
+    pub team: i32,
+    /// The side the team played in the round, 2 for T and 3 for CT
+    pub side: i32,
+    pub buy: BuyType,
+    /// The average equipment value per player at the end of the freeze time
+    pub equipment_value: u32,
+    pub won: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RoundType {
+    pub teams: Vec<TeamRound>,
+    /// The bomb was planted during the round
+    pub post_plant: bool,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TeamSummary {
+    /// The team number the team started the match with
+    pub team: i32,
+    pub pistol_rounds: usize,
+    pub pistol_wins: usize,
+    /// Pistol round wins that were followed up with a win in the next round
+    pub pistol_conversions: usize,
+    pub force_rounds: usize,
+    pub force_wins: usize,
+    /// Rounds in which the opening kill put the team up 5v4
+    pub advantage_rounds: usize,
+    pub advantage_wins: usize,
+}
+
+/// Classifies the buy of both teams in every round
+pub fn classify(rounds: &[crate::perround::Round]) -> Vec<RoundType> {
+    let starting_teams = starting_teams(rounds);
+
+    let mut first_t_team = None;
+    let mut switched = false;
+    rounds
+        .iter()
+        .enumerate()
+        .map(|(idx, round)| {
+            let sides: Vec<(i32, i32, u32)> = [2, 3]
+                .into_iter()
+                .filter_map(|side| {
+                    let team = side_team(round, &starting_teams, side)?;
+                    let players: Vec<_> = round.players.iter().filter(|p| p.team == side).collect();
+                    let value = players.iter().map(|p| p.start_equipment).sum::<u32>()
+                        / players.len() as u32;
+                    Some((side, team, value))
+                })
+                .collect();
+
+            // The second pistol round is the first one after the teams switched sides, the
+            // overtime halves start with full money
+            let t_team = sides.iter().find(|(side, _, _)| *side == 2).map(|s| s.1);
+            let pistol = if idx == 0 {
+                first_t_team = t_team;
+                true
+            } else if !switched
+                && t_team
+                    .zip(first_t_team)
+                    .is_some_and(|(t, first)| t != first)
+            {
+                switched = true;
+                true
+            } else {
+                false
+            };
+
+            let teams = sides
+                .iter()
+                .map(|(side, team, value)| {
+                    let enemy_value = sides.iter().find(|(s, _, _)| s != side).map(|(_, _, v)| *v);
+                    TeamRound {
+                        team: *team,
+                        side: *side,
+                        buy: buy_type(*value, enemy_value, pistol),
+                        equipment_value: *value,
+                        won: round.winreason.winning_team() == Some(*side),
+                    }
+                })
+                .collect();
+
+            RoundType {
+                teams,
+                post_plant: round.events.iter().any(|e| {
+                    matches!(
+                        e,
+                        crate::perround::RoundEvent::BombPlanted
+                            | crate::perround::RoundEvent::BombPlantedAt { .. }
+                    )
+                }),
+            }
+        })
+        .collect()
+}
+
+/// The conversion stats of every team, ordered by team number
+pub fn team_summary(rounds: &[crate::perround::Round], types: &[RoundType]) -> Vec<TeamSummary> {
+    let mut teams = std::collections::BTreeMap::<i32, TeamSummary>::new();
+    for (idx, (round, round_type)) in rounds.iter().zip(types.iter()).enumerate() {
+        let advantage = opening_advantage(round);
+
+        for team_round in round_type.teams.iter() {
+            let summary = teams.entry(team_round.team).or_insert_with(|| TeamSummary {
+                team: team_round.team,
+                ..Default::default()
+            });
+
+            match team_round.buy {
+                BuyType::Pistol => {
+                    summary.pistol_rounds += 1;
+                    if team_round.won {
+                        summary.pistol_wins += 1;
+
+                        let next_won = types
+                            .get(idx + 1)
+                            .and_then(|t| t.teams.iter().find(|t| t.team == team_round.team))
+                            .is_some_and(|t| t.won);
+                        summary.pistol_conversions += next_won as usize;
+                    }
+                }
+                BuyType::Force => {
+                    summary.force_rounds += 1;
+                    summary.force_wins += team_round.won as usize;
+                }
+                _ => {}
+            };
+
+            if advantage == Some(team_round.side) {
+                summary.advantage_rounds += 1;
+                summary.advantage_wins += team_round.won as usize;
+            }
+        }
+    }
+
+    teams.into_values().collect()
+}
+
+fn buy_type(value: u32, enemy_value: Option<u32>, pistol: bool) -> BuyType {
+    if pistol {
+        BuyType::Pistol
+    } else if value < ECO_BOUND {
+        BuyType::Eco
+    } else if enemy_value.is_some_and(|v| v < ECO_BOUND) {
+        BuyType::AntiEco
+    } else if value < FULL_BUY_BOUND {
+        BuyType::Force
+    } else {
+        BuyType::GunRound
+    }
+}
+
+/// The side a player played in the first round they were part of
+pub(crate) fn starting_teams(
+    rounds: &[crate::perround::Round],
+) -> std::collections::HashMap<u64, i32> {
+    let mut teams = std::collections::HashMap::new();
+    for player in rounds.iter().flat_map(|r| r.players.iter()) {
+        teams.entry(player.steam_id).or_insert(player.team);
+    }
+    teams
+}
+
+/// The team that played on the side in the round, identified by the side it started the match on
+pub(crate) fn side_team(
+    round: &crate::perround::Round,
+    starting_teams: &std::collections::HashMap<u64, i32>,
+    side: i32,
+) -> Option<i32> {
+    majority(
+        round
+            .players
+            .iter()
+            .filter(|p| p.team == side)
+            .filter_map(|p| starting_teams.get(&p.steam_id).copied()),
+    )
+}
+
+pub(crate) fn majority<I>(values: I) -> Option<i32>
+where
+    I: IntoIterator<Item = i32>,
+{
+    let mut counts = std::collections::HashMap::<i32, usize>::new();
+    for value in values {
+        *counts.entry(value).or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .max_by_key(|(value, count)| (*count, *value))
+        .map(|(value, _)| value)
+}
+
+/// The side that was up 5v4 after the first death of the round
+fn opening_advantage(round: &crate::perround::Round) -> Option<i32> {
+    let first_death = round.events.iter().find_map(|e| match e {
+        crate::perround::RoundEvent::Kill { died, .. } => Some(*died),
+        _ => None,
+    })?;
+    let died_side = round
+        .players
+        .iter()
+        .find(|p| p.steam_id == first_death)?
+        .team;
+
+    let alive = |side: i32| round.players.iter().filter(|p| p.team == side).count();
+    let (side, enemy) = match died_side {
+        2 => (3, 2),
+        3 => (2, 3),
+        _ => return None,
+    };
+
+    (alive(side) == 5 && alive(enemy) == 5).then_some(side)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buys() {
+        assert_eq!(BuyType::Pistol, buy_type(800, Some(800), true));
+        assert_eq!(BuyType::Eco, buy_type(400, Some(5000), false));
+        assert_eq!(BuyType::AntiEco, buy_type(3000, Some(600), false));
+        assert_eq!(BuyType::Force, buy_type(2500, Some(4500), false));
+        assert_eq!(BuyType::GunRound, buy_type(4800, Some(4500), false));
+    }
+}
@@ -0,0 +1,408 @@
+use std::collections::HashMap;
+
+use crate::heatmap::PawnID;
+
+pub struct Config {
+    /// The seconds after the end of the freeze time at which the positions are taken
+    pub delay: u32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self { delay: 15 }
+    }
+}
+
+/// The size of the zones that are used for players outside of any named callout, in the same
+/// coordinates as the heatmaps
+const ZONE_SIZE: f32 = 512.0;
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SideSetup {
+    /// Starting at 0
+    pub round: usize,
+    /// The team number the team started the match with
+    pub team: i32,
+    /// The side the team played in the round, 2 for T and 3 for CT
+    pub side: i32,
+    /// The callouts of the alive players in sorted order, so the same setup always compares
+    /// equal, across rounds and demos of the same map
+    pub places: Vec<String>,
+    pub players: Vec<u64>,
+    pub won: bool,
+}
+
+impl SideSetup {
+    /// Whether the side was played by the team of the given players, which is the case if most of
+    /// the players of the side are part of it. This finds the team across demos, in which it can
+    /// have a different team number and substitutes
+    pub fn played_by(&self, roster: &[u64]) -> bool {
+        let known = self.players.iter().filter(|p| roster.contains(p)).count();
+        known * 2 > self.players.len()
+    }
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct Setups {
+    /// The setups of both sides in every round, ordered by round
+    pub sides: Vec<SideSetup>,
+    /// Whether the demo could be read until the end of the match, only the rounds that were
+    /// finished are returned otherwise
+    pub complete: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SetupStats {
+    pub side: i32,
+    pub places: Vec<String>,
+    /// All the rounds the setup was played in, including the ones with a similar setup
+    pub rounds: usize,
+    pub wins: usize,
+    /// The rounds that were played with a setup that differed in one position
+    pub similar: usize,
+}
+
+#[derive(Debug, Default)]
+struct PawnState {
+    team: u32,
+    position: (f32, f32, f32),
+    alive: bool,
+    place: String,
+}
+
+pub fn parse(config: &Config, buf: &[u8]) -> Result<Setups, ()> {
+    let tmp = csdemo::Container::parse(buf).map_err(|e| ())?;
+    let output = csdemo::lazyparser::LazyParser::new(tmp);
+
+    let delay = config.delay * crate::metadata::tick_rate(output.events());
+
+    let mut controllers = HashMap::<PawnID, crate::heatmap::ControllerState>::new();
+    let mut pawn_players = HashMap::<PawnID, u64>::new();
+    let mut entity_cells = HashMap::new();
+    let mut entity_offsets = HashMap::new();
+    let mut pawns = HashMap::<PawnID, PawnState>::new();
+    let mut starting_teams = HashMap::<u64, i32>::new();
+    let mut participants = crate::players::Participants::default();
+
+    let mut sides: Vec<SideSetup> = Vec::new();
+    let mut rounds = 0;
+    let mut finished_rounds = 0;
+    let mut in_round = false;
+    let mut freeze_period = false;
+    let mut snapshot_tick = None;
+    let mut current_tick = 0;
+    let mut completeness = crate::Completeness::default();
+    for entity in output.entities() {
+        let (tick, state) = match completeness.ok(entity) {
+            Some(e) => e,
+            None => continue,
+        };
+        completeness.entity(&state);
+        participants.update(&state);
+
+        // All the updates for a tick are done, so the positions at the end of it can be taken
+        if tick != current_tick {
+            if snapshot_tick.is_some_and(|s| current_tick >= s) && in_round {
+                snapshot_tick = None;
+                snapshot(
+                    rounds - 1,
+                    &controllers,
+                    &pawns,
+                    &participants,
+                    &mut starting_teams,
+                    &mut sides,
+                );
+            }
+            current_tick = tick;
+        }
+
+        match state.class.as_ref() {
+            "CCSPlayerController" => {
+                crate::heatmap::update_pawn_players(&state, &mut controllers, &mut pawn_players);
+            }
+            "CCSGameRulesProxy" => {
+                let count = |name: &str| state.get_prop(name).and_then(|p| p.value.as_u32());
+
+                if let Some(count) = count("CCSGameRulesProxy.CCSGameRules.m_nRoundStartCount") {
+                    if rounds < count.saturating_sub(1) as usize {
+                        rounds += 1;
+                        in_round = true;
+                        snapshot_tick = None;
+                    }
+                }
+                if let Some(count) = count("CCSGameRulesProxy.CCSGameRules.m_nRoundEndCount") {
+                    if rounds == count.saturating_sub(1) as usize && in_round {
+                        in_round = false;
+                        finished_rounds = rounds;
+                    }
+                }
+
+                if let Some(csdemo::parser::Variant::Bool(v)) = state
+                    .get_prop("CCSGameRulesProxy.CCSGameRules.m_bFreezePeriod")
+                    .map(|p| &p.value)
+                {
+                    if freeze_period && !v {
+                        snapshot_tick = Some(tick + delay);
+                    }
+                    freeze_period = *v;
+                }
+
+                let winner = state
+                    .get_prop("CCSGameRulesProxy.CCSGameRules.m_eRoundWinReason")
+                    .and_then(|p| p.value.as_i32())
+                    .and_then(|v| crate::perround::ROUND_WIN_REASON.get(&v))
+                    .and_then(|r| r.winning_team());
+                if let Some(winner) = winner {
+                    for side in sides.iter_mut().filter(|s| s.round + 1 == rounds) {
+                        side.won = side.side == winner;
+                    }
+                }
+            }
+            "CCSPlayerPawn" => {
+                let position =
+                    crate::heatmap::update_position(&state, &mut entity_cells, &mut entity_offsets);
+                update_pawn(
+                    &state,
+                    position,
+                    pawns.entry(PawnID::from(state.id)).or_default(),
+                );
+            }
+            _ => {}
+        };
+    }
+
+    let complete = completeness.complete();
+    if !complete {
+        // The round that was being played when the demo was cut off never ended
+        sides.retain(|s| s.round < finished_rounds);
+    }
+
+    Ok(Setups { sides, complete })
+}
+
+/// Setups that only differ in this many positions are counted as the same setup, as a single
+/// player playing a different position does not make it a different setup
+pub const SIMILAR_POSITIONS: usize = 1;
+
+/// The number of positions that differ between two setups, which are compared as multisets of
+/// their callouts
+pub fn position_difference(places1: &[String], places2: &[String]) -> usize {
+    let mut counts = HashMap::<&str, isize>::new();
+    for place in places1 {
+        *counts.entry(place.as_str()).or_default() += 1;
+    }
+    for place in places2 {
+        *counts.entry(place.as_str()).or_default() -= 1;
+    }
+
+    let only_first: isize = counts.values().filter(|c| **c > 0).sum();
+    let only_second: isize = counts.values().filter(|c| **c < 0).map(|c| -c).sum();
+    only_first.max(only_second) as usize
+}
+
+/// Groups the setups by side and positions. The most common setups are taken as is, less common
+/// ones that only differ in [`SIMILAR_POSITIONS`] positions from one of them are added to it
+pub fn setup_stats<'s, I>(sides: I) -> Vec<SetupStats>
+where
+    I: IntoIterator<Item = &'s SideSetup>,
+{
+    let mut exact = HashMap::<(i32, &[String]), SetupStats>::new();
+    for setup in sides {
+        let entry = exact
+            .entry((setup.side, setup.places.as_slice()))
+            .or_insert_with(|| SetupStats {
+                side: setup.side,
+                places: setup.places.clone(),
+                rounds: 0,
+                wins: 0,
+                similar: 0,
+            });
+        entry.rounds += 1;
+        entry.wins += setup.won as usize;
+    }
+
+    let mut exact: Vec<_> = exact.into_values().collect();
+    sort_stats(&mut exact);
+
+    let mut result: Vec<SetupStats> = Vec::new();
+    for stats in exact {
+        let similar = result.iter_mut().find(|s| {
+            s.side == stats.side
+                && position_difference(&s.places, &stats.places) <= SIMILAR_POSITIONS
+        });
+        match similar {
+            Some(similar) => {
+                similar.rounds += stats.rounds;
+                similar.wins += stats.wins;
+                similar.similar += stats.rounds;
+            }
+            None => result.push(stats),
+        };
+    }
+
+    sort_stats(&mut result);
+    result
+}
+
+fn sort_stats(stats: &mut [SetupStats]) {
+    stats.sort_unstable_by(|s1, s2| {
+        s1.side
+            .cmp(&s2.side)
+            .then(s2.rounds.cmp(&s1.rounds))
+            .then(s1.places.cmp(&s2.places))
+    });
+}
+
+/// The callout of the position, or the zone it is in outside of any named callout
+fn zone_name(pawn: &PawnState) -> String {
+    if !pawn.place.is_empty() {
+        return pawn.place.clone();
+    }
+
+    let (x, y, _) = pawn.position;
+    format!(
+        "Zone {}:{}",
+        (x / ZONE_SIZE).floor() as i32,
+        (y / ZONE_SIZE).floor() as i32
+    )
+}
+
+fn snapshot(
+    round: usize,
+    controllers: &HashMap<PawnID, crate::heatmap::ControllerState>,
+    pawns: &HashMap<PawnID, PawnState>,
+    participants: &crate::players::Participants,
+    starting_teams: &mut HashMap<u64, i32>,
+    sides: &mut Vec<SideSetup>,
+) {
+    for side in [2, 3] {
+        let mut players: Vec<(String, u64)> = controllers
+            .values()
+            .filter_map(|c| {
+                let pawn = pawns.get(&c.pawn?)?;
+                let steam_id = c.steam_id.filter(|s| participants.playing(*s))?;
+                (pawn.alive && pawn.team == side as u32).then(|| (zone_name(pawn), steam_id))
+            })
+            .collect();
+        if players.is_empty() {
+            continue;
+        }
+        players.sort_unstable();
+
+        for (_, steam_id) in players.iter() {
+            starting_teams.entry(*steam_id).or_insert(side);
+        }
+        let team = crate::roundtypes::majority(
+            players
+                .iter()
+                .filter_map(|(_, steam_id)| starting_teams.get(steam_id).copied()),
+        )
+        .unwrap_or(side);
+
+        let (places, players) = players.into_iter().unzip();
+        sides.push(SideSetup {
+            round,
+            team,
+            side,
+            places,
+            players,
+            won: false,
+        });
+    }
+}
+
+fn update_pawn(
+    state: &csdemo::parser::entities::EntityState,
+    position: (f32, f32, f32),
+    pawn: &mut PawnState,
+) {
+    pawn.position = position;
+
+    for prop in state.props.iter() {
+        match prop.prop_info.prop_name.as_ref() {
+            "CCSPlayerPawn.m_iTeamNum" => {
+                pawn.team = prop.value.as_u32().unwrap_or(pawn.team);
+            }
+            "CCSPlayerPawn.m_lifeState" => {
+                // 0 means alive
+                if let Some(v) = prop.value.as_u32() {
+                    pawn.alive = v == 0;
+                }
+            }
+            _ => {}
+        };
+    }
+
+    if let Some(place) = crate::places::pawn_place(state) {
+        pawn.place = place.to_owned();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn side(side: i32, places: &[&str], won: bool) -> SideSetup {
+        SideSetup {
+            round: 0,
+            team: 2,
+            side,
+            places: places.iter().map(|p| p.to_string()).collect(),
+            players: vec![1, 2, 3, 4, 5],
+            won,
+        }
+    }
+
+    #[test]
+    fn difference() {
+        let places = |places: &[&str]| places.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+
+        let setup = places(&["A", "A", "B", "Mid", "Mid"]);
+        assert_eq!(0, position_difference(&setup, &setup));
+        assert_eq!(
+            1,
+            position_difference(&setup, &places(&["A", "B", "B", "Mid", "Mid"]))
+        );
+        assert_eq!(
+            1,
+            position_difference(&setup, &places(&["A", "B", "Mid", "Mid"]))
+        );
+        assert_eq!(
+            2,
+            position_difference(&setup, &places(&["A", "B", "B", "B", "Mid"]))
+        );
+    }
+
+    #[test]
+    fn similar_setups() {
+        let sides = [
+            side(3, &["A", "A", "B", "Mid", "Mid"], true),
+            side(3, &["A", "A", "B", "Mid", "Mid"], false),
+            side(3, &["A", "B", "B", "Mid", "Mid"], true),
+            side(3, &["B", "B", "B", "Mid", "Mid"], true),
+            // The same positions on the other side are a different setup
+            side(2, &["A", "A", "B", "Mid", "Mid"], false),
+        ];
+
+        let result = setup_stats(sides.iter());
+        assert_eq!(3, result.len());
+
+        assert_eq!(2, result[0].side);
+
+        assert_eq!(3, result[1].side);
+        assert_eq!(vec!["A", "A", "B", "Mid", "Mid"], result[1].places);
+        assert_eq!(3, result[1].rounds);
+        assert_eq!(2, result[1].wins);
+        assert_eq!(1, result[1].similar);
+
+        assert_eq!(vec!["B", "B", "B", "Mid", "Mid"], result[2].places);
+        assert_eq!(1, result[2].rounds);
+    }
+
+    #[test]
+    fn played_by() {
+        let setup = side(2, &[], false);
+        assert!(setup.played_by(&[1, 2, 3, 9]));
+        assert!(!setup.played_by(&[1, 2, 8, 9]));
+    }
+}
@@ -0,0 +1,364 @@
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum VoteIssue {
+    Kick,
+    Surrender,
+    PauseMatch,
+    UnpauseMatch,
+    Timeout,
+    Other(i32),
+}
+
+// The issue indices used by the CS vote controller, issues we don't care about are kept as `Other`
+pub static VOTE_ISSUES: phf::Map<i32, VoteIssue> = phf::phf_map! {
+    0_i32 => VoteIssue::Kick,
+    6_i32 => VoteIssue::Surrender,
+    9_i32 => VoteIssue::PauseMatch,
+    10_i32 => VoteIssue::UnpauseMatch,
+    13_i32 => VoteIssue::Timeout,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum TimelineEvent {
+    Chat {
+        sender: u64,
+        team_only: bool,
+        message: String,
+    },
+    TimeoutStarted {
+        team: i32,
+    },
+    TimeoutEnded {
+        team: i32,
+    },
+    TechnicalPauseStarted,
+    TechnicalPauseEnded,
+    VoteStarted {
+        issue: VoteIssue,
+        /// The team that is allowed to vote, `-1` if everyone is allowed to vote
+        team: i32,
+    },
+    VoteCast {
+        voter: u64,
+        yes: bool,
+    },
+    VoteEnded {
+        issue: VoteIssue,
+        yes: usize,
+        no: usize,
+    },
+    Surrender {
+        team: i32,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TimelineEntry {
+    pub tick: u32,
+    /// The index of the round in which the event happened, `None` if it happened before the
+    /// first round started
+    pub round: Option<usize>,
+    pub event: TimelineEvent,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct Timeline {
+    pub entries: Vec<TimelineEntry>,
+    /// Whether the demo could be read until the end of the match
+    pub complete: bool,
+}
+
+#[derive(Debug, Default)]
+struct GameRulesState {
+    t_timeout: bool,
+    ct_timeout: bool,
+    technical_pause: bool,
+    active_issue: Option<VoteIssue>,
+    vote_team: i32,
+    round_win_reason: i32,
+}
+
+impl GameRulesState {
+    /// The surrender for an update of the round win reason. The reason can be sent again without
+    /// changing, so only a change to one of the surrender reasons is a new surrender
+    fn update_win_reason(&mut self, reason: i32) -> Option<TimelineEvent> {
+        let previous = std::mem::replace(&mut self.round_win_reason, reason);
+        if previous == reason {
+            return None;
+        }
+
+        match reason {
+            // TSurrender
+            17 => Some(TimelineEvent::Surrender { team: 2 }),
+            // CTSurrender
+            18 => Some(TimelineEvent::Surrender { team: 3 }),
+            _ => None,
+        }
+    }
+}
+
+pub fn parse(buf: &[u8]) -> Result<Timeline, ()> {
+    let tmp = csdemo::Container::parse(buf).map_err(|e| ())?;
+    let output = csdemo::lazyparser::LazyParser::new(tmp);
+
+    let player_info = output.player_info();
+
+    let mut round_starts: Vec<u32> = Vec::new();
+    let mut entries: Vec<(u32, TimelineEvent)> = Vec::new();
+    let mut game_state = GameRulesState::default();
+    let mut completeness = crate::Completeness::default();
+    for entity in output.entities() {
+        let (tick, state) = match completeness.ok(entity) {
+            Some(e) => e,
+            None => continue,
+        };
+        completeness.entity(&state);
+
+        match state.class.as_ref() {
+            "CCSGameRulesProxy" => {
+                game_rules(
+                    tick,
+                    &state,
+                    &mut round_starts,
+                    &mut game_state,
+                    &mut entries,
+                );
+            }
+            "CVoteController" => {
+                vote_controller(tick, &state, &mut game_state, &mut entries);
+            }
+            _ => {}
+        };
+    }
+
+    let mut current_tick = 0;
+    for event in output.events() {
+        let ge = match completeness.ok(event) {
+            None => continue,
+            Some(csdemo::DemoEvent::Tick(tick)) => {
+                current_tick = tick.tick();
+                continue;
+            }
+            Some(csdemo::DemoEvent::GameEvent(ge)) => ge,
+            Some(_) => continue,
+        };
+
+        let event = match *ge {
+            csdemo::game_event::GameEvent::PlayerChat(chat) => {
+                let sender = match chat.userid.and_then(|u| player_info.get(&u)) {
+                    Some(p) => p.xuid,
+                    None => continue,
+                };
+
+                TimelineEvent::Chat {
+                    sender,
+                    team_only: chat.teamonly.unwrap_or(false),
+                    message: chat.text.unwrap_or_default(),
+                }
+            }
+            csdemo::game_event::GameEvent::VoteCast(vote) => {
+                let voter = match vote.userid.and_then(|u| player_info.get(&u)) {
+                    Some(p) => p.xuid,
+                    None => continue,
+                };
+
+                // Option 0 is always "Yes" for the yes/no votes used in matches
+                TimelineEvent::VoteCast {
+                    voter,
+                    yes: vote.vote_option == Some(0),
+                }
+            }
+            _ => continue,
+        };
+
+        entries.push((current_tick, event));
+    }
+
+    // The entity and event passes are done separately, so we need to restore the order of all
+    // the events
+    entries.sort_by_key(|(tick, _)| *tick);
+    count_votes(&mut entries);
+
+    let entries = entries
+        .into_iter()
+        .map(|(tick, event)| TimelineEntry {
+            tick,
+            round: round_starts.iter().rposition(|start| *start <= tick),
+            event,
+        })
+        .collect();
+
+    Ok(Timeline {
+        entries,
+        complete: completeness.complete(),
+    })
+}
+
+fn game_rules(
+    tick: u32,
+    state: &csdemo::parser::entities::EntityState,
+    round_starts: &mut Vec<u32>,
+    game_state: &mut GameRulesState,
+    entries: &mut Vec<(u32, TimelineEvent)>,
+) {
+    for prop in state.props.iter() {
+        match (prop.prop_info.prop_name.as_ref(), &prop.value) {
+            ("CCSGameRulesProxy.CCSGameRules.m_nRoundStartCount", value) => {
+                if let Some(count) = value.as_u32() {
+                    while round_starts.len() < count.saturating_sub(1) as usize {
+                        round_starts.push(tick);
+                    }
+                }
+            }
+            (
+                "CCSGameRulesProxy.CCSGameRules.m_bTerroristTimeOutActive",
+                csdemo::parser::Variant::Bool(active),
+            ) => {
+                if *active != game_state.t_timeout {
+                    game_state.t_timeout = *active;
+                    entries.push((tick, timeout_event(*active, 2)));
+                }
+            }
+            (
+                "CCSGameRulesProxy.CCSGameRules.m_bCTTimeOutActive",
+                csdemo::parser::Variant::Bool(active),
+            ) => {
+                if *active != game_state.ct_timeout {
+                    game_state.ct_timeout = *active;
+                    entries.push((tick, timeout_event(*active, 3)));
+                }
+            }
+            (
+                "CCSGameRulesProxy.CCSGameRules.m_bTechnicalTimeOut",
+                csdemo::parser::Variant::Bool(active),
+            ) => {
+                if *active != game_state.technical_pause {
+                    game_state.technical_pause = *active;
+                    entries.push((
+                        tick,
+                        if *active {
+                            TimelineEvent::TechnicalPauseStarted
+                        } else {
+                            TimelineEvent::TechnicalPauseEnded
+                        },
+                    ));
+                }
+            }
+            ("CCSGameRulesProxy.CCSGameRules.m_eRoundWinReason", value) => {
+                let event = value
+                    .as_i32()
+                    .and_then(|reason| game_state.update_win_reason(reason));
+                if let Some(event) = event {
+                    entries.push((tick, event));
+                }
+            }
+            _ => {}
+        };
+    }
+}
+
+fn vote_controller(
+    tick: u32,
+    state: &csdemo::parser::entities::EntityState,
+    game_state: &mut GameRulesState,
+    entries: &mut Vec<(u32, TimelineEvent)>,
+) {
+    if let Some(team) = state
+        .get_prop("CVoteController.m_iOnlyTeamToVote")
+        .map(|p| p.value.as_i32())
+        .flatten()
+    {
+        game_state.vote_team = team;
+    }
+
+    let issue_index = match state
+        .get_prop("CVoteController.m_iActiveIssueIndex")
+        .map(|p| p.value.as_i32())
+        .flatten()
+    {
+        Some(i) => i,
+        None => return,
+    };
+
+    // The votes themselves are only known once the events have been processed, so they are
+    // counted afterwards
+    if let Some(issue) = game_state.active_issue.take() {
+        entries.push((
+            tick,
+            TimelineEvent::VoteEnded {
+                issue,
+                yes: 0,
+                no: 0,
+            },
+        ));
+    }
+
+    if issue_index < 0 {
+        return;
+    }
+
+    let issue = VOTE_ISSUES
+        .get(&issue_index)
+        .cloned()
+        .unwrap_or(VoteIssue::Other(issue_index));
+    game_state.active_issue = Some(issue.clone());
+    entries.push((
+        tick,
+        TimelineEvent::VoteStarted {
+            issue,
+            team: game_state.vote_team,
+        },
+    ));
+}
+
+fn count_votes(entries: &mut [(u32, TimelineEvent)]) {
+    let (mut yes_votes, mut no_votes) = (0, 0);
+    for (_, event) in entries.iter_mut() {
+        match event {
+            TimelineEvent::VoteStarted { .. } => {
+                yes_votes = 0;
+                no_votes = 0;
+            }
+            TimelineEvent::VoteCast { yes: true, .. } => {
+                yes_votes += 1;
+            }
+            TimelineEvent::VoteCast { yes: false, .. } => {
+                no_votes += 1;
+            }
+            TimelineEvent::VoteEnded { yes, no, .. } => {
+                *yes = yes_votes;
+                *no = no_votes;
+            }
+            _ => {}
+        };
+    }
+}
+
+fn timeout_event(active: bool, team: i32) -> TimelineEvent {
+    if active {
+        TimelineEvent::TimeoutStarted { team }
+    } else {
+        TimelineEvent::TimeoutEnded { team }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_surrender_per_change() {
+        let mut state = GameRulesState::default();
+
+        let events: Vec<_> = [0, 17, 17, 17, 0, 0, 18, 18]
+            .into_iter()
+            .filter_map(|reason| state.update_win_reason(reason))
+            .collect();
+        assert_eq!(
+            vec![
+                TimelineEvent::Surrender { team: 2 },
+                TimelineEvent::Surrender { team: 3 }
+            ],
+            events
+        );
+    }
+}
@@ -0,0 +1,28 @@
+//! A small wasm-bindgen API around the analyses, to preview a demo in the browser without
+//! uploading it. All the results are returned as JSON strings of the normal analysis outputs.
+
+use wasm_bindgen::prelude::*;
+
+fn to_json<T>(result: Result<T, ()>) -> Result<String, JsError>
+where
+    T: serde::Serialize,
+{
+    let value = result.map_err(|_| JsError::new("Could not parse demo"))?;
+    serde_json::to_string(&value).map_err(|e| JsError::new(&e.to_string()))
+}
+
+#[wasm_bindgen]
+pub fn metadata(demo: &[u8]) -> Result<String, JsError> {
+    to_json(crate::metadata::parse(demo))
+}
+
+#[wasm_bindgen]
+pub fn scoreboard(demo: &[u8]) -> Result<String, JsError> {
+    to_json(crate::endofgame::parse(demo))
+}
+
+#[wasm_bindgen]
+pub fn heatmaps(demo: &[u8], cell_size: f32) -> Result<String, JsError> {
+    let config = crate::heatmap::Config { cell_size };
+    to_json(crate::heatmap::parse(&config, demo))
+}
@@ -0,0 +1,337 @@
+//! A simple logistic model for the chance of the CT side winning a round, based on the alive
+//! players, the equipment bought at the start of the round, whether the bomb was planted and the
+//! score of the match.
+
+/// The shift towards the side with more alive players, per player
+pub const ALIVE_WEIGHT: f32 = 0.85;
+/// The shift towards the side with the better equipment, per 1000 of average equipment value
+pub const ECONOMY_WEIGHT: f32 = 0.6;
+/// The shift towards the T side once the bomb is planted
+pub const PLANT_WEIGHT: f32 = 0.8;
+/// The shift towards the side that leads, per round of difference in the score, as the leading
+/// team is more likely the stronger one
+pub const SCORE_WEIGHT: f32 = 0.04;
+/// The shift towards a side that is on match point, while the other one is not. The team facing
+/// the loss of the match has to force buy and play riskier
+pub const MATCH_POINT_WEIGHT: f32 = 0.15;
+/// The advantage of the CT side in an even situation
+pub const CT_BASE: f32 = 0.1;
+
+/// The rounds needed to win a match in regulation
+const REGULATION_WIN: usize = 13;
+/// The rounds of an overtime, which is played until one team won more than half of them
+const OVERTIME_ROUNDS: usize = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoundState {
+    pub t_alive: usize,
+    pub ct_alive: usize,
+    /// The average equipment value per player of the T side at the start of the round
+    pub t_equipment: u32,
+    /// The average equipment value per player of the CT side at the start of the round
+    pub ct_equipment: u32,
+    pub bomb_planted: bool,
+    pub bomb_defused: bool,
+    /// The rounds the T side won before this round
+    pub t_score: usize,
+    /// The rounds the CT side won before this round
+    pub ct_score: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ProbabilityPoint {
+    pub round: usize,
+    /// The index of the event in the round that led to the state, `None` for the start and the end
+    /// of the round
+    pub event: Option<usize>,
+    /// The last point of the round, which is 0 or 1 depending on the side that won it
+    #[serde(default)]
+    pub end: bool,
+    pub ct_win: f32,
+    /// The win probability of the team that started the match on CT, to follow the momentum
+    /// across halftime
+    pub starting_ct_win: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct KillImpact {
+    pub round: usize,
+    /// The index of the kill in the events of the round
+    pub event: usize,
+    pub attacker: u64,
+    pub died: u64,
+    /// The change in the win probability of the attacker's side, negative for team kills
+    pub impact: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PlayerImpact {
+    pub steam_id: u64,
+    pub kills: usize,
+    /// The sum of the impact of all kills
+    pub impact: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct WinProbability {
+    /// The win probability at the start of every round, after every event and at its end
+    pub points: Vec<ProbabilityPoint>,
+    /// Every kill that was not a suicide
+    pub kills: Vec<KillImpact>,
+    /// Ordered by steam id
+    pub players: Vec<PlayerImpact>,
+}
+
+/// The probability of the CT side winning the round in the given state, between 0 and 1
+pub fn ct_win_probability(state: &RoundState) -> f32 {
+    if state.bomb_defused {
+        return 1.0;
+    }
+    if state.ct_alive == 0 {
+        return 0.0;
+    }
+    if state.t_alive == 0 && !state.bomb_planted {
+        return 1.0;
+    }
+
+    let alive = state.ct_alive as f32 - state.t_alive as f32;
+    let economy = (state.ct_equipment as f32 - state.t_equipment as f32) / 1000.0;
+    let plant = if state.bomb_planted {
+        PLANT_WEIGHT
+    } else {
+        0.0
+    };
+    let score = state.ct_score as f32 - state.t_score as f32;
+    let match_point = match (
+        match_point(state.ct_score, state.t_score),
+        match_point(state.t_score, state.ct_score),
+    ) {
+        (true, false) => MATCH_POINT_WEIGHT,
+        (false, true) => -MATCH_POINT_WEIGHT,
+        _ => 0.0,
+    };
+
+    let z = CT_BASE + ALIVE_WEIGHT * alive + ECONOMY_WEIGHT * economy - plant
+        + SCORE_WEIGHT * score
+        + match_point;
+    1.0 / (1.0 + (-z).exp())
+}
+
+/// Whether a team with the score only needs to win one more round to win the match, assuming
+/// matches of 24 rounds with overtimes of 6 rounds
+fn match_point(score: usize, other: usize) -> bool {
+    let tie = REGULATION_WIN - 1;
+    let needed = if score >= tie && other >= tie {
+        let overtime = (score.min(other) - tie) / (OVERTIME_ROUNDS / 2);
+        REGULATION_WIN + OVERTIME_ROUNDS / 2 + overtime * (OVERTIME_ROUNDS / 2)
+    } else {
+        REGULATION_WIN
+    };
+    score + 1 == needed
+}
+
+/// Evaluates the model at the start of every round and after every event
+pub fn evaluate(rounds: &[crate::perround::Round]) -> WinProbability {
+    let starting_teams = crate::roundtypes::starting_teams(rounds);
+    let first_ct_team = rounds
+        .first()
+        .and_then(|r| crate::roundtypes::side_team(r, &starting_teams, 3));
+
+    let mut points = Vec::new();
+    let mut kills = Vec::new();
+    let mut players = std::collections::BTreeMap::<u64, PlayerImpact>::new();
+    // The rounds won by every team, identified by the side it started on
+    let mut scores = std::collections::HashMap::<i32, usize>::new();
+    for (round_idx, round) in rounds.iter().enumerate() {
+        let side = |steam_id: u64| {
+            round
+                .players
+                .iter()
+                .find(|p| p.steam_id == steam_id)
+                .map(|p| p.team)
+        };
+        let count = |side: i32| round.players.iter().filter(|p| p.team == side).count();
+        let equipment = |side: i32| {
+            let values: Vec<_> = round
+                .players
+                .iter()
+                .filter(|p| p.team == side)
+                .map(|p| p.start_equipment)
+                .collect();
+            values.iter().sum::<u32>() / (values.len().max(1) as u32)
+        };
+
+        let t_team = crate::roundtypes::side_team(round, &starting_teams, 2);
+        let ct_team = crate::roundtypes::side_team(round, &starting_teams, 3);
+        let score = |team: Option<i32>| team.and_then(|t| scores.get(&t)).copied().unwrap_or(0);
+
+        let ct_is_first = first_ct_team.is_none() || ct_team == first_ct_team;
+        let point = |event: Option<usize>, ct_win: f32| ProbabilityPoint {
+            round: round_idx,
+            event,
+            end: false,
+            ct_win,
+            starting_ct_win: if ct_is_first { ct_win } else { 1.0 - ct_win },
+        };
+
+        let mut state = RoundState {
+            t_alive: count(2),
+            ct_alive: count(3),
+            t_equipment: equipment(2),
+            ct_equipment: equipment(3),
+            bomb_planted: false,
+            bomb_defused: false,
+            t_score: score(t_team),
+            ct_score: score(ct_team),
+        };
+        let mut current = ct_win_probability(&state);
+        points.push(point(None, current));
+
+        for (event_idx, event) in round.events.iter().enumerate() {
+            match event {
+                crate::perround::RoundEvent::BombPlanted
+                | crate::perround::RoundEvent::BombPlantedAt { .. } => {
+                    state.bomb_planted = true;
+                }
+                crate::perround::RoundEvent::BombDefused => {
+                    state.bomb_defused = true;
+                }
+                crate::perround::RoundEvent::Kill { died, .. } => match side(*died) {
+                    Some(2) => state.t_alive = state.t_alive.saturating_sub(1),
+                    Some(3) => state.ct_alive = state.ct_alive.saturating_sub(1),
+                    _ => {}
+                },
+            };
+            let next = ct_win_probability(&state);
+
+            if let crate::perround::RoundEvent::Kill { attacker, died, .. } = event {
+                let impact = match side(*attacker) {
+                    Some(2) => Some(current - next),
+                    Some(3) => Some(next - current),
+                    _ => None,
+                };
+                if let Some(impact) = impact.filter(|_| attacker != died) {
+                    kills.push(KillImpact {
+                        round: round_idx,
+                        event: event_idx,
+                        attacker: *attacker,
+                        died: *died,
+                        impact,
+                    });
+
+                    let player = players.entry(*attacker).or_insert_with(|| PlayerImpact {
+                        steam_id: *attacker,
+                        kills: 0,
+                        impact: 0.0,
+                    });
+                    player.kills += 1;
+                    player.impact += impact;
+                }
+            }
+
+            points.push(point(Some(event_idx), next));
+            current = next;
+        }
+
+        // The round is decided by how it ended, like the bomb exploding with CTs still alive
+        if let Some(winner) = round.winreason.winning_team() {
+            points.push(ProbabilityPoint {
+                end: true,
+                ..point(None, if winner == 3 { 1.0 } else { 0.0 })
+            });
+
+            let team = if winner == 3 { ct_team } else { t_team };
+            if let Some(team) = team {
+                *scores.entry(team).or_default() += 1;
+            }
+        }
+    }
+
+    WinProbability {
+        points,
+        kills,
+        players: players.into_values().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(t_alive: usize, ct_alive: usize) -> RoundState {
+        RoundState {
+            t_alive,
+            ct_alive,
+            t_equipment: 4000,
+            ct_equipment: 4000,
+            bomb_planted: false,
+            bomb_defused: false,
+            t_score: 0,
+            ct_score: 0,
+        }
+    }
+
+    #[test]
+    fn probabilities() {
+        let even = ct_win_probability(&state(5, 5));
+        assert!(even > 0.5 && even < 0.6);
+
+        assert!(ct_win_probability(&state(4, 5)) > even);
+        assert!(ct_win_probability(&state(5, 4)) < even);
+        assert_eq!(1.0, ct_win_probability(&state(0, 3)));
+        assert_eq!(0.0, ct_win_probability(&state(3, 0)));
+
+        let planted = RoundState {
+            bomb_planted: true,
+            ..state(5, 5)
+        };
+        assert!(ct_win_probability(&planted) < 0.5);
+        assert!(
+            ct_win_probability(&RoundState {
+                t_alive: 0,
+                ..planted
+            }) < 1.0
+        );
+
+        let eco = RoundState {
+            t_equipment: 800,
+            ..state(5, 5)
+        };
+        assert!(ct_win_probability(&eco) > 0.85);
+    }
+
+    #[test]
+    fn score() {
+        let even = ct_win_probability(&state(5, 5));
+        let leading = RoundState {
+            ct_score: 8,
+            t_score: 3,
+            ..state(5, 5)
+        };
+        assert!(ct_win_probability(&leading) > even);
+
+        // Neither team is on match point at the start of the overtime
+        let tied = RoundState {
+            ct_score: 12,
+            t_score: 12,
+            ..state(5, 5)
+        };
+        assert_eq!(even, ct_win_probability(&tied));
+        let match_point = RoundState {
+            ct_score: 11,
+            t_score: 12,
+            ..state(5, 5)
+        };
+        assert!(ct_win_probability(&match_point) < even - SCORE_WEIGHT);
+    }
+
+    #[test]
+    fn match_points() {
+        assert!(match_point(12, 5));
+        assert!(!match_point(11, 5));
+        assert!(!match_point(12, 12));
+        assert!(match_point(15, 13));
+        assert!(!match_point(15, 15));
+        assert!(match_point(18, 15));
+    }
+}
@@ -17,6 +17,7 @@ fn endofgame_nuke() {
                 endofgame::TeamInfo {
                     end_score: 13,
                     start_side: "CT".into(),
+                    clan_name: None,
                 },
             ),
             (
@@ -24,6 +25,7 @@ fn endofgame_nuke() {
                 endofgame::TeamInfo {
                     end_score: 8,
                     start_side: "TERRORIST".into(),
+                    clan_name: None,
                 },
             ),
         ]
@@ -215,7 +217,23 @@ fn endofgame_nuke() {
                 },
             ),
         ],
+        stat_mismatches: Vec::new(),
+        complete: true,
     };
 
     assert_eq!(result, expected);
 }
+
+#[test]
+fn endofgame_serde_roundtrip() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../testfiles/nuke.dem");
+    dbg!(path);
+    let input_bytes = std::fs::read(path).unwrap();
+
+    let result = endofgame::parse(&input_bytes).unwrap();
+
+    let serialized = serde_json::to_string(&result).unwrap();
+    let deserialized: endofgame::EndOfGame = serde_json::from_str(&serialized).unwrap();
+
+    assert_eq!(result, deserialized);
+}
@@ -13,24 +13,23 @@ fn head_to_head_nuke() {
     let result = head_to_head::parse(&input_bytes).unwrap();
 
     let expected = head_to_head::Output {
-        players: [(csdemo::UserId(0), csdemo::parser::Player {
-            xuid: 0,
+        players: [(0, head_to_head::Player {
             name: "".to_owned(),
             team: 0,
-            color: 0,
         })].into_iter().collect(),
         head_to_head: [
-            (csdemo::UserId(0), HashMap::new()),
-            (csdemo::UserId(1), HashMap::new()),
-            (csdemo::UserId(2), HashMap::new()),
-            (csdemo::UserId(3), HashMap::new()),
-            (csdemo::UserId(4), HashMap::new()),
-            (csdemo::UserId(5), HashMap::new()),
-            (csdemo::UserId(6), HashMap::new()),
-            (csdemo::UserId(7), HashMap::new()),
-            (csdemo::UserId(8), HashMap::new()),
-            (csdemo::UserId(9), HashMap::new()),
+            (0, HashMap::new()),
+            (1, HashMap::new()),
+            (2, HashMap::new()),
+            (3, HashMap::new()),
+            (4, HashMap::new()),
+            (5, HashMap::new()),
+            (6, HashMap::new()),
+            (7, HashMap::new()),
+            (8, HashMap::new()),
+            (9, HashMap::new()),
         ].into_iter().collect(),
+        complete: true,
     };
 
     dbg!(&expected, &result);
@@ -39,3 +39,17 @@ fn heatmap_dust2() {
 
     assert_eq!(result.player_heatmaps.len(), 20);
 }
+
+#[test]
+#[traced_test]
+fn heatmap_nuke_truncated() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../testfiles/nuke.dem");
+    dbg!(path);
+    let input_bytes = std::fs::read(path).unwrap();
+
+    let config = heatmap::Config { cell_size: 5.0 };
+    let result = heatmap::parse(&config, &input_bytes[..input_bytes.len() / 2]).unwrap();
+
+    assert!(!result.complete);
+    assert!(!result.player_heatmaps.is_empty());
+}
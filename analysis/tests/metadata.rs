@@ -0,0 +1,19 @@
+use analysis::metadata;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn metadata_nuke() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../testfiles/nuke.dem");
+    dbg!(path);
+    let input_bytes = std::fs::read(path).unwrap();
+
+    let result = metadata::parse(&input_bytes).unwrap();
+    dbg!(&result);
+
+    assert_eq!("de_nuke", result.map);
+    assert_eq!(metadata::DemoType::Gotv, result.demo_type);
+    assert_eq!(64, result.tick_rate);
+    assert!(result.match_start_tick.is_some());
+    assert!(result.match_start_tick.unwrap() < result.playback_ticks);
+    assert!(result.duration() > 0);
+}
@@ -11,4 +11,196 @@ fn perround_nuke() {
     dbg!(&result);
 
     assert_eq!(21, result.rounds.len());
+    assert!(result.complete);
+}
+
+#[test]
+fn perround_nuke_truncated() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../testfiles/nuke.dem");
+    dbg!(path);
+    let input_bytes = std::fs::read(path).unwrap();
+
+    // Simulates an interrupted upload
+    let result = perround::parse(&input_bytes[..input_bytes.len() / 2]).unwrap();
+    dbg!(&result);
+
+    assert!(!result.complete);
+    assert!(!result.rounds.is_empty());
+    assert!(result.rounds.len() < 21);
+    assert!(result.rounds.iter().all(|r| r.end != u32::MAX));
+}
+
+#[test]
+fn perround_nuke_players() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../testfiles/nuke.dem");
+    dbg!(path);
+    let input_bytes = std::fs::read(path).unwrap();
+
+    let result = perround::parse(&input_bytes).unwrap();
+
+    for round in result.rounds.iter() {
+        assert_eq!(10, round.players.len());
+
+        let kill_events = round
+            .events
+            .iter()
+            .filter(|e| matches!(e, perround::RoundEvent::Kill { .. }))
+            .count();
+        let player_kills: usize = round.players.iter().map(|p| p.kills).sum();
+        assert!(player_kills <= kill_events);
+
+        let deaths = round.players.iter().filter(|p| !p.survived).count();
+        assert!(deaths <= kill_events);
+        assert!(round
+            .players
+            .iter()
+            .filter(|p| !p.survived)
+            .all(|p| p.end_health == 0 && p.equipment_saved == 0));
+    }
+}
+
+#[test]
+fn perround_nuke_places() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../testfiles/nuke.dem");
+    dbg!(path);
+    let input_bytes = std::fs::read(path).unwrap();
+
+    let result = perround::parse(&input_bytes).unwrap();
+
+    let events: Vec<_> = result.rounds.iter().flat_map(|r| r.events.iter()).collect();
+    assert!(events.iter().any(|e| matches!(
+        e,
+        perround::RoundEvent::Kill {
+            died_place: Some(_),
+            ..
+        }
+    )));
+    assert!(!events
+        .iter()
+        .any(|e| matches!(e, perround::RoundEvent::BombPlanted)));
+
+    let stats = analysis::places::callout_stats(events.iter().copied());
+    let deaths: usize = stats.iter().map(|s| s.deaths).sum();
+    let kill_events = events
+        .iter()
+        .filter(|e| matches!(e, perround::RoundEvent::Kill { .. }))
+        .count();
+    assert!(deaths > 0);
+    assert!(deaths <= kill_events);
+}
+
+#[test]
+fn perround_nuke_weapons() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../testfiles/nuke.dem");
+    dbg!(path);
+    let input_bytes = std::fs::read(path).unwrap();
+
+    let result = perround::parse(&input_bytes).unwrap();
+
+    let events: Vec<_> = result.rounds.iter().flat_map(|r| r.events.iter()).collect();
+    assert!(events.iter().any(|e| matches!(
+        e,
+        perround::RoundEvent::Kill {
+            distance: Some(_),
+            ..
+        }
+    )));
+
+    let kills = events
+        .iter()
+        .filter(
+            |e| matches!(e, perround::RoundEvent::Kill { attacker, died, .. } if attacker != died),
+        )
+        .count();
+    let weapons = analysis::kills::weapon_stats(events.iter().copied());
+    assert_eq!(kills, weapons.iter().map(|w| w.kills).sum::<usize>());
+    assert!(weapons
+        .iter()
+        .all(|w| w.ranges.iter().sum::<usize>() <= w.kills));
+}
+
+#[test]
+fn perround_nuke_roundtypes() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../testfiles/nuke.dem");
+    dbg!(path);
+    let input_bytes = std::fs::read(path).unwrap();
+
+    let result = perround::parse(&input_bytes).unwrap();
+
+    let types = analysis::roundtypes::classify(&result.rounds);
+    assert_eq!(result.rounds.len(), types.len());
+    assert!(types[0]
+        .teams
+        .iter()
+        .all(|t| t.buy == analysis::roundtypes::BuyType::Pistol));
+    assert!(types
+        .iter()
+        .all(|t| t.teams.iter().filter(|t| t.won).count() <= 1));
+
+    let summary = analysis::roundtypes::team_summary(&result.rounds, &types);
+    assert_eq!(2, summary.len());
+    assert_eq!(
+        types.len() * 2,
+        types.iter().map(|t| t.teams.len()).sum::<usize>()
+    );
+    for team in summary.iter() {
+        assert_eq!(2, team.pistol_rounds);
+        assert!(team.pistol_conversions <= team.pistol_wins);
+        assert!(team.force_wins <= team.force_rounds);
+        assert!(team.advantage_wins <= team.advantage_rounds);
+    }
+}
+
+#[test]
+fn perround_nuke_alive() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../testfiles/nuke.dem");
+    dbg!(path);
+    let input_bytes = std::fs::read(path).unwrap();
+
+    let result = perround::parse(&input_bytes).unwrap();
+
+    for round in result.rounds.iter() {
+        let start = round.alive.first().unwrap();
+        assert_eq!(round.start, start.tick);
+        assert_eq!((5, 5), (start.t, start.ct));
+
+        let deaths = round.players.iter().filter(|p| !p.survived).count();
+        assert!(round.alive.len() <= deaths + 1);
+        assert!(round
+            .alive
+            .windows(2)
+            .all(|w| w[0].tick <= w[1].tick && w[0].t + w[0].ct == w[1].t + w[1].ct + 1));
+    }
+
+    let stats = analysis::advantage::advantage_stats(&result.rounds);
+    assert!(stats
+        .iter()
+        .any(|s| s.players == 5 && s.enemies == 4 && s.rounds > 0));
+    assert!(stats
+        .iter()
+        .all(|s| s.players > s.enemies && s.won + s.lost <= s.rounds));
+}
+
+#[test]
+fn perround_nuke_winprob() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../testfiles/nuke.dem");
+    dbg!(path);
+    let input_bytes = std::fs::read(path).unwrap();
+
+    let result = perround::parse(&input_bytes).unwrap();
+
+    let events: usize = result.rounds.iter().map(|r| r.events.len()).sum();
+    let probability = analysis::winprob::evaluate(&result.rounds);
+    assert_eq!(result.rounds.len() + events, probability.points.len());
+    assert!(probability
+        .points
+        .iter()
+        .all(|p| (0.0..=1.0).contains(&p.ct_win) && (0.0..=1.0).contains(&p.starting_ct_win)));
+
+    // The model is deterministic
+    assert_eq!(probability, analysis::winprob::evaluate(&result.rounds));
+
+    let kills: usize = probability.players.iter().map(|p| p.kills).sum();
+    assert_eq!(probability.kills.len(), kills);
+    assert!(probability.kills.iter().any(|k| k.impact > 0.0));
 }
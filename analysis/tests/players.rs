@@ -0,0 +1,18 @@
+use analysis::players;
+
+#[test]
+fn players_nuke() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../testfiles/nuke.dem");
+    dbg!(path);
+    let input_bytes = std::fs::read(path).unwrap();
+
+    let result = players::parse(&input_bytes).unwrap();
+    dbg!(&result);
+
+    assert_eq!(10, result.players.len());
+    for player in result.players.iter() {
+        assert_eq!(1, player.user_ids.len());
+        assert_eq!(None, player.leave_tick);
+        assert_eq!(21, player.rounds_played);
+    }
+}
@@ -0,0 +1,42 @@
+use analysis::postplant;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn postplant_nuke() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../testfiles/nuke.dem");
+    dbg!(path);
+    let input_bytes = std::fs::read(path).unwrap();
+
+    let result = postplant::parse(&input_bytes).unwrap();
+    assert!(result.complete);
+
+    let rounds = analysis::perround::parse(&input_bytes).unwrap().rounds;
+    let planted_rounds = rounds
+        .iter()
+        .filter(|r| {
+            r.events.iter().any(|e| {
+                matches!(
+                    e,
+                    analysis::perround::RoundEvent::BombPlanted
+                        | analysis::perround::RoundEvent::BombPlantedAt { .. }
+                )
+            })
+        })
+        .count();
+    assert_eq!(planted_rounds, result.plants.len());
+
+    for plant in result.plants.iter() {
+        assert!(plant.t_alive > 0);
+        assert!(plant.t_positions.len() <= plant.t_alive);
+        assert!(plant.time_remaining >= 0.0);
+        assert!(plant.t_team != plant.ct_team);
+    }
+
+    let stats = postplant::post_plant_stats(result.plants.iter());
+    let totals: usize = stats
+        .iter()
+        .filter(|s| s.players.is_none())
+        .map(|s| s.rounds)
+        .sum();
+    assert_eq!(result.plants.len(), totals);
+}
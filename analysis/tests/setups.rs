@@ -0,0 +1,31 @@
+use analysis::setups;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn setups_nuke() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../testfiles/nuke.dem");
+    dbg!(path);
+    let input_bytes = std::fs::read(path).unwrap();
+
+    let result = setups::parse(&setups::Config::default(), &input_bytes).unwrap();
+    assert!(result.complete);
+    assert!(!result.sides.is_empty());
+
+    let rounds = analysis::perround::parse(&input_bytes).unwrap().rounds;
+    for side in result.sides.iter() {
+        assert!(side.round < rounds.len());
+        assert!(matches!(side.side, 2 | 3));
+        assert_eq!(side.places.len(), side.players.len());
+        assert!(side.places.windows(2).all(|w| w[0] <= w[1]));
+
+        let winner = rounds[side.round].winreason.winning_team();
+        assert_eq!(winner == Some(side.side), side.won);
+    }
+
+    let stats = setups::setup_stats(result.sides.iter());
+    assert_eq!(
+        result.sides.len(),
+        stats.iter().map(|s| s.rounds).sum::<usize>()
+    );
+    assert!(stats.iter().all(|s| s.wins <= s.rounds));
+}
@@ -0,0 +1,274 @@
+//! Runs every analysis on the bundled demos and compares the results with the JSON snapshots in
+//! `tests/snapshots/<analysis>/<demo>.json`.
+//!
+//! Missing snapshots are written on the first run (and the test fails, so they get reviewed).
+//! To create or update the snapshots after an intended change, run the tests with `BLESS=1` and
+//! review the diff of the snapshot files.
+
+use pretty_assertions::assert_eq;
+
+const DEMOS: &[&str] = &["dust2", "inferno", "nuke"];
+
+fn load_demo(name: &str) -> Vec<u8> {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../testfiles/")
+        .join(format!("{}.dem", name));
+    let data = std::fs::read(&path).unwrap();
+
+    // Without git-lfs the demos are only the pointer files, which would fail in the parser
+    assert!(
+        !data.starts_with(b"version https://git-lfs"),
+        "{:?} is a git-lfs pointer, fetch the demos with `git lfs pull`",
+        path
+    );
+    data
+}
+
+fn check_snapshot<T>(analysis: &str, demo: &str, value: &T)
+where
+    T: serde::Serialize,
+{
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots/")
+        .join(analysis)
+        .join(format!("{}.json", demo));
+
+    // Going through a Value sorts the keys of all the maps, so the output does not depend on the
+    // iteration order of a HashMap
+    let value = serde_json::to_value(value).unwrap();
+    let actual = serde_json::to_string_pretty(&value).unwrap() + "\n";
+
+    let bless = std::env::var("BLESS").map(|v| v == "1").unwrap_or(false);
+    let expected = match std::fs::read_to_string(&path) {
+        Ok(e) if !bless => e,
+        Ok(_) => {
+            std::fs::write(&path, actual).unwrap();
+            return;
+        }
+        Err(_) => {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, actual).unwrap();
+            if bless {
+                return;
+            }
+            panic!("Wrote missing snapshot {:?}, review and commit it", path);
+        }
+    };
+
+    assert_eq!(expected, actual, "Snapshot {:?} does not match", path);
+}
+
+#[test]
+fn snapshot_endofgame() {
+    for demo in DEMOS {
+        let result = analysis::endofgame::parse(&load_demo(demo)).unwrap();
+        check_snapshot("endofgame", demo, &result);
+    }
+}
+
+#[test]
+fn snapshot_perround() {
+    for demo in DEMOS {
+        let result = analysis::perround::parse(&load_demo(demo)).unwrap();
+        check_snapshot("perround", demo, &result);
+    }
+}
+
+#[test]
+fn snapshot_heatmap() {
+    #[derive(serde::Serialize)]
+    struct Summary {
+        steam_id: u64,
+        team: String,
+        cells: usize,
+        samples: usize,
+        max_value: usize,
+        coords: ((f32, f32), (f32, f32)),
+    }
+
+    for demo in DEMOS {
+        let config = analysis::heatmap::Config { cell_size: 5.0 };
+        let result = analysis::heatmap::parse(&config, &load_demo(demo)).unwrap();
+
+        // The full heatmaps are too large to check in, so only a summary of each is compared
+        let summaries: Vec<_> = result
+            .player_heatmaps
+            .iter()
+            .map(|player| Summary {
+                steam_id: player.steam_id,
+                team: player.team.clone(),
+                cells: player.heatmap.cells().count(),
+                samples: player.heatmap.cells().map(|(_, count)| count).sum(),
+                max_value: player
+                    .heatmap
+                    .cells()
+                    .map(|(_, count)| count)
+                    .max()
+                    .unwrap_or(0),
+                coords: player.heatmap.coords(),
+            })
+            .collect();
+
+        check_snapshot("heatmap", demo, &(result.complete, summaries));
+    }
+}
+
+#[test]
+fn snapshot_head_to_head() {
+    for demo in DEMOS {
+        let result = analysis::head_to_head::parse(&load_demo(demo)).unwrap();
+        check_snapshot("head_to_head", demo, &result);
+    }
+}
+
+#[test]
+fn snapshot_metadata() {
+    for demo in DEMOS {
+        let result = analysis::metadata::parse(&load_demo(demo)).unwrap();
+        check_snapshot("metadata", demo, &result);
+    }
+}
+
+#[test]
+fn snapshot_timeline() {
+    for demo in DEMOS {
+        let result = analysis::timeline::parse(&load_demo(demo)).unwrap();
+        check_snapshot("timeline", demo, &result);
+    }
+}
+
+#[test]
+fn snapshot_players() {
+    for demo in DEMOS {
+        let result = analysis::players::parse(&load_demo(demo)).unwrap();
+        check_snapshot("players", demo, &result);
+    }
+}
+
+#[test]
+fn snapshot_quality() {
+    for demo in DEMOS {
+        let result = analysis::quality::parse(&load_demo(demo)).unwrap();
+        check_snapshot("quality", demo, &result);
+    }
+}
+
+#[test]
+fn snapshot_grenades() {
+    for demo in DEMOS {
+        let result = analysis::grenades::parse(&load_demo(demo)).unwrap();
+        check_snapshot("grenades", demo, &result);
+    }
+}
+
+#[test]
+fn snapshot_hitgroups() {
+    for demo in DEMOS {
+        let result = analysis::hitgroups::parse(&load_demo(demo)).unwrap();
+        check_snapshot("hitgroups", demo, &result);
+    }
+}
+
+#[test]
+fn snapshot_replay() {
+    #[derive(serde::Serialize)]
+    struct Summary {
+        start: u32,
+        end: u32,
+        freeze_end: Option<u32>,
+        players: Vec<u64>,
+        frames: usize,
+        samples: usize,
+        kills: Vec<analysis::replay::Kill>,
+        grenades: usize,
+    }
+
+    for demo in DEMOS {
+        let config = analysis::replay::Config::default();
+        let result = analysis::replay::parse(&config, &load_demo(demo)).unwrap();
+
+        // The samples of every round are too large to check in, so only a summary of each round
+        // and the kills are compared
+        let summaries: Vec<_> = result
+            .rounds
+            .iter()
+            .map(|round| Summary {
+                start: round.start,
+                end: round.end,
+                freeze_end: round.freeze_end,
+                players: round.players.clone(),
+                frames: round.frames.len(),
+                samples: round
+                    .frames
+                    .iter()
+                    .map(|f| f.players.iter().flatten().count())
+                    .sum(),
+                kills: round.kills.clone(),
+                grenades: round.grenades.len(),
+            })
+            .collect();
+
+        check_snapshot(
+            "replay",
+            demo,
+            &(
+                result.tick_rate,
+                result.sample_rate,
+                result.complete,
+                summaries,
+            ),
+        );
+    }
+}
+
+#[test]
+fn snapshot_roundtypes() {
+    for demo in DEMOS {
+        let result = analysis::perround::parse(&load_demo(demo)).unwrap();
+        let types = analysis::roundtypes::classify(&result.rounds);
+        let summary = analysis::roundtypes::team_summary(&result.rounds, &types);
+        check_snapshot("roundtypes", demo, &(types, summary));
+    }
+}
+
+#[test]
+fn snapshot_advantage() {
+    for demo in DEMOS {
+        let result = analysis::perround::parse(&load_demo(demo)).unwrap();
+        let advantages = analysis::advantage::advantage_stats(&result.rounds);
+        check_snapshot("advantage", demo, &advantages);
+    }
+}
+
+#[test]
+fn snapshot_winprob() {
+    for demo in DEMOS {
+        let result = analysis::perround::parse(&load_demo(demo)).unwrap();
+        let win_probability = analysis::winprob::evaluate(&result.rounds);
+        check_snapshot("winprob", demo, &win_probability);
+    }
+}
+
+#[test]
+fn snapshot_setups() {
+    for demo in DEMOS {
+        let config = analysis::setups::Config::default();
+        let result = analysis::setups::parse(&config, &load_demo(demo)).unwrap();
+        let stats: Vec<_> = [2, 3]
+            .into_iter()
+            .map(|team| {
+                analysis::setups::setup_stats(result.sides.iter().filter(|s| s.team == team))
+            })
+            .collect();
+        check_snapshot("setups", demo, &(result, stats));
+    }
+}
+
+#[test]
+fn snapshot_postplant() {
+    for demo in DEMOS {
+        let result = analysis::postplant::parse(&load_demo(demo)).unwrap();
+        let stats = analysis::postplant::post_plant_stats(result.plants.iter());
+        check_snapshot("postplant", demo, &(result, stats));
+    }
+}
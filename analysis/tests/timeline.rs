@@ -0,0 +1,28 @@
+use analysis::timeline;
+
+#[test]
+fn timeline_nuke() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../testfiles/nuke.dem");
+    dbg!(path);
+    let input_bytes = std::fs::read(path).unwrap();
+
+    let result = timeline::parse(&input_bytes).unwrap();
+    dbg!(&result);
+
+    assert!(result
+        .entries
+        .windows(2)
+        .all(|entries| entries[0].tick <= entries[1].tick));
+    assert!(result
+        .entries
+        .windows(2)
+        .all(|entries| entries[0].round <= entries[1].round));
+
+    // A match can only be surrendered once
+    let surrenders = result
+        .entries
+        .iter()
+        .filter(|e| matches!(e.event, timeline::TimelineEvent::Surrender { .. }))
+        .count();
+    assert!(surrenders <= 1);
+}
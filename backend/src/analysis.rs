@@ -5,6 +5,13 @@ pub mod base;
 pub mod heatmap;
 pub mod perround;
 pub mod head_to_head;
+pub mod timeline;
+pub mod quality;
+pub mod replay;
+pub mod grenades;
+pub mod hitgroups;
+pub mod setups;
+pub mod postplant;
 
 #[derive(Debug, Clone)]
 pub enum AnalysisData {
@@ -62,13 +69,20 @@ pub trait Analysis {
     >;
 }
 
-pub static ANALYSIS_METHODS: std::sync::LazyLock<[std::sync::Arc<dyn Analysis + Send + Sync>; 4]> =
+pub static ANALYSIS_METHODS: std::sync::LazyLock<[std::sync::Arc<dyn Analysis + Send + Sync>; 11]> =
     std::sync::LazyLock::new(|| {
         [
             std::sync::Arc::new(base::BaseAnalysis::new()),
             std::sync::Arc::new(heatmap::HeatmapAnalysis::new()),
             std::sync::Arc::new(perround::PerRoundAnalysis::new()),
             std::sync::Arc::new(head_to_head::HeadToHeadAnalysis::new()),
+            std::sync::Arc::new(timeline::TimelineAnalysis::new()),
+            std::sync::Arc::new(quality::QualityAnalysis::new()),
+            std::sync::Arc::new(replay::ReplayAnalysis::new()),
+            std::sync::Arc::new(grenades::GrenadesAnalysis::new()),
+            std::sync::Arc::new(hitgroups::HitgroupsAnalysis::new()),
+            std::sync::Arc::new(setups::SetupsAnalysis::new()),
+            std::sync::Arc::new(postplant::PostPlantAnalysis::new()),
         ]
     });
 
@@ -11,6 +11,7 @@ struct BaseInfo {
 struct BaseTeamInfo {
     pub start_side: String,
     pub end_score: usize,
+    pub clan_name: Option<String>,
 }
 
 #[derive(Debug)]
@@ -59,7 +60,33 @@ impl Analysis for BaseAnalysis {
     > {
         tracing::info!("Performing Base analysis");
 
-        let result = analysis::endofgame::parse(input.data()).unwrap();
+        let result = analysis::endofgame::parse(input.data())
+            .inspect_err(|e| {
+                tracing::error!("{:?}", e);
+            })
+            .map_err(|e| ())?;
+        let metadata = analysis::metadata::parse(input.data())
+            .inspect_err(|e| {
+                tracing::error!("{:?}", e);
+            })
+            .map_err(|e| ())?;
+        let connections = analysis::players::parse(input.data())
+            .inspect_err(|e| {
+                tracing::error!("{:?}", e);
+            })
+            .map_err(|e| ())?;
+
+        if !result.complete {
+            tracing::warn!("Demo is incomplete, only storing the partial results");
+        }
+
+        if !result.stat_mismatches.is_empty() {
+            tracing::warn!(
+                "Stats differ from the ones tracked by the game: {:?}",
+                result.stat_mismatches
+            );
+        }
+        let stat_mismatches = serde_json::to_value(&result.stat_mismatches).unwrap();
 
         let base_result = BaseInfo {
             map: result.map,
@@ -72,6 +99,7 @@ impl Analysis for BaseAnalysis {
                         BaseTeamInfo {
                             end_score: team.end_score,
                             start_side: team.start_side,
+                            clan_name: team.clan_name,
                         },
                     )
                 })
@@ -103,6 +131,12 @@ impl Analysis for BaseAnalysis {
             .players
             .into_iter()
             .map(|(info, stats)| {
+                let connection = info
+                    .steam_id
+                    .parse::<u64>()
+                    .ok()
+                    .and_then(|steam_id| connections.get(steam_id));
+
                 (
                     crate::models::DemoPlayer {
                         demo_id: input.demoid.clone(),
@@ -110,6 +144,9 @@ impl Analysis for BaseAnalysis {
                         steam_id: info.steam_id.clone(),
                         team: info.team as i16,
                         color: info.color as i16,
+                        join_tick: connection.map(|c| c.join_tick as i32),
+                        leave_tick: connection.and_then(|c| c.leave_tick).map(|t| t as i32),
+                        rounds_played: connection.map(|c| c.rounds_played as i16),
                     },
                     crate::models::DemoPlayerStats {
                         demo_id: input.demoid.clone(),
@@ -131,12 +168,23 @@ impl Analysis for BaseAnalysis {
                 team: numb as i16,
                 end_score: team.end_score as i16,
                 start_name: team.start_side,
+                clan_name: team.clan_name,
             })
             .collect::<Vec<_>>();
 
         let demo_info = crate::models::DemoInfo {
             demo_id: input.demoid.clone(),
             map: base_result.map,
+            server_name: Some(metadata.server_name),
+            demo_type: Some(serde_json::to_string(&metadata.demo_type).unwrap()),
+            tick_rate: Some(metadata.tick_rate as i32),
+            playback_ticks: Some(metadata.playback_ticks as i32),
+            game_mode: Some(serde_json::to_string(&metadata.game_mode).unwrap()),
+            match_start_tick: metadata.match_start_tick.map(|t| t as i32),
+            network_protocol: Some(metadata.network_protocol),
+            build_number: Some(metadata.build_number),
+            partial: !result.complete,
+            stat_mismatches: Some(stat_mismatches),
         };
 
         Ok(Box::new(move |connection| {
@@ -145,14 +193,76 @@ impl Analysis for BaseAnalysis {
                     .values(demo_info)
                     .on_conflict(crate::schema::demo_info::dsl::demo_id)
                     .do_update()
-                    .set(
+                    .set((
                         crate::schema::demo_info::dsl::map
                             .eq(diesel::upsert::excluded(crate::schema::demo_info::dsl::map)),
-                    );
+                        crate::schema::demo_info::dsl::server_name.eq(diesel::upsert::excluded(
+                            crate::schema::demo_info::dsl::server_name,
+                        )),
+                        crate::schema::demo_info::dsl::demo_type.eq(diesel::upsert::excluded(
+                            crate::schema::demo_info::dsl::demo_type,
+                        )),
+                        crate::schema::demo_info::dsl::tick_rate.eq(diesel::upsert::excluded(
+                            crate::schema::demo_info::dsl::tick_rate,
+                        )),
+                        crate::schema::demo_info::dsl::playback_ticks.eq(diesel::upsert::excluded(
+                            crate::schema::demo_info::dsl::playback_ticks,
+                        )),
+                        crate::schema::demo_info::dsl::game_mode.eq(diesel::upsert::excluded(
+                            crate::schema::demo_info::dsl::game_mode,
+                        )),
+                        crate::schema::demo_info::dsl::match_start_tick.eq(
+                            diesel::upsert::excluded(
+                                crate::schema::demo_info::dsl::match_start_tick,
+                            ),
+                        ),
+                        crate::schema::demo_info::dsl::network_protocol.eq(
+                            diesel::upsert::excluded(
+                                crate::schema::demo_info::dsl::network_protocol,
+                            ),
+                        ),
+                        crate::schema::demo_info::dsl::build_number.eq(diesel::upsert::excluded(
+                            crate::schema::demo_info::dsl::build_number,
+                        )),
+                        crate::schema::demo_info::dsl::partial.eq(diesel::upsert::excluded(
+                            crate::schema::demo_info::dsl::partial,
+                        )),
+                        crate::schema::demo_info::dsl::stat_mismatches.eq(
+                            diesel::upsert::excluded(
+                                crate::schema::demo_info::dsl::stat_mismatches,
+                            ),
+                        ),
+                    ));
             let store_demo_players_query =
                 diesel::dsl::insert_into(crate::schema::demo_players::dsl::demo_players)
                     .values(player_info)
-                    .on_conflict_do_nothing();
+                    .on_conflict((
+                        crate::schema::demo_players::dsl::demo_id,
+                        crate::schema::demo_players::dsl::steam_id,
+                    ))
+                    .do_update()
+                    .set((
+                        crate::schema::demo_players::dsl::name.eq(diesel::upsert::excluded(
+                            crate::schema::demo_players::dsl::name,
+                        )),
+                        crate::schema::demo_players::dsl::team.eq(diesel::upsert::excluded(
+                            crate::schema::demo_players::dsl::team,
+                        )),
+                        crate::schema::demo_players::dsl::color.eq(diesel::upsert::excluded(
+                            crate::schema::demo_players::dsl::color,
+                        )),
+                        crate::schema::demo_players::dsl::join_tick.eq(diesel::upsert::excluded(
+                            crate::schema::demo_players::dsl::join_tick,
+                        )),
+                        crate::schema::demo_players::dsl::leave_tick.eq(diesel::upsert::excluded(
+                            crate::schema::demo_players::dsl::leave_tick,
+                        )),
+                        crate::schema::demo_players::dsl::rounds_played.eq(
+                            diesel::upsert::excluded(
+                                crate::schema::demo_players::dsl::rounds_played,
+                            ),
+                        ),
+                    ));
 
             let store_demo_player_stats_query =
                 diesel::dsl::insert_into(crate::schema::demo_player_stats::dsl::demo_player_stats)
@@ -193,6 +303,9 @@ impl Analysis for BaseAnalysis {
                     crate::schema::demo_teams::dsl::end_score.eq(diesel::upsert::excluded(
                         crate::schema::demo_teams::dsl::end_score,
                     )),
+                    crate::schema::demo_teams::dsl::clan_name.eq(diesel::upsert::excluded(
+                        crate::schema::demo_teams::dsl::clan_name,
+                    )),
                 ));
 
             Box::pin(async move {
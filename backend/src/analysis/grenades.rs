@@ -0,0 +1,61 @@
+use super::*;
+
+pub struct GrenadesAnalysis {}
+
+impl GrenadesAnalysis {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Analysis for GrenadesAnalysis {
+    #[tracing::instrument(name = "Grenades", skip(self, input))]
+    fn analyse(
+        &self,
+        input: AnalysisInput,
+    ) -> Result<
+        Box<
+            dyn FnOnce(
+                    &mut diesel_async::pg::AsyncPgConnection,
+                ) -> core::pin::Pin<
+                    Box<
+                        (dyn core::future::Future<Output = Result<(), diesel::result::Error>>
+                             + Send
+                             + '_),
+                    >,
+                > + Send,
+        >,
+        (),
+    > {
+        tracing::info!("Tracking Grenades");
+
+        let result = analysis::grenades::parse(input.data())
+            .inspect_err(|e| {
+                tracing::error!("{:?}", e);
+            })
+            .map_err(|e| ())?;
+
+        let value = crate::models::DemoGrenades {
+            demo_id: input.demoid.clone(),
+            grenades: serde_json::to_value(&result.grenades).unwrap(),
+        };
+
+        Ok(Box::new(move |connection| {
+            let query = diesel::dsl::insert_into(crate::schema::demo_grenades::dsl::demo_grenades)
+                .values(value)
+                .on_conflict(crate::schema::demo_grenades::dsl::demo_id)
+                .do_update()
+                .set(
+                    crate::schema::demo_grenades::dsl::grenades.eq(diesel::upsert::excluded(
+                        crate::schema::demo_grenades::dsl::grenades,
+                    )),
+                );
+
+            Box::pin(async move {
+                query.execute(connection).await?;
+
+                Ok(())
+            })
+        }))
+    }
+}
@@ -33,19 +33,16 @@ impl Analysis for HeadToHeadAnalysis {
             tracing::error!("{:?}", e);
         }).map_err(|e| ())?;
 
-        let values_to_insert: Vec<_> = result.head_to_head.into_iter().flat_map(|(user_id, enemies)| {
-            enemies.into_iter().map(move |(enemy, kills)| (user_id, enemy, kills))
-        }).filter_map(|(user_id, enemy_id, kills)| {
-                let player = result.players.get(&user_id)?;
-                let enemy = result.players.get(&enemy_id)?;
-                
-                Some(crate::models::DemoHeadToHead {
-                    demo_id: input.demoid.clone(),
-                    player: player.xuid.to_string(),
-                    enemy: enemy.xuid.to_string(),
-                    kills: kills as i16,
-                })
-            }).collect();
+        let values_to_insert: Vec<_> = result.head_to_head.into_iter().flat_map(|(player, enemies)| {
+            enemies.into_iter().map(move |(enemy, kills)| (player, enemy, kills))
+        }).map(|(player, enemy, kills)| {
+            crate::models::DemoHeadToHead {
+                demo_id: input.demoid.clone(),
+                player: player.to_string(),
+                enemy: enemy.to_string(),
+                kills: kills as i16,
+            }
+        }).collect();
 
         Ok(Box::new(move |connection| {
             // TODO
@@ -30,35 +30,28 @@ impl Analysis for HeatmapAnalysis {
         tracing::info!("Generating HEATMAPs");
 
         let config = analysis::heatmap::Config { cell_size: 5.0 };
-        let result = analysis::heatmap::parse(&config, input.data()).unwrap();
+        let result = analysis::heatmap::parse(&config, input.data())
+            .inspect_err(|e| {
+                tracing::error!("{:?}", e);
+            })
+            .map_err(|e| ())?;
 
         tracing::info!("Got {} Entity-Heatmaps", result.player_heatmaps.len());
-        let heatmap_result: Vec<_> = result
+        let player_heatmaps: Vec<_> = result
             .player_heatmaps
             .into_iter()
-            .filter_map(|((userid, team), heatmap)| {
-                let player = match result.player_info.get(&userid) {
-                    Some(p) => p,
-                    None => {
-                        tracing::warn!("Could not find player: {:?}", userid);
-                        return None;
-                    }
-                };
-
-                Some(((player.xuid.to_string(), team), heatmap))
-            })
-            .collect();
-
-        let player_heatmaps: Vec<_> = heatmap_result
-            .into_iter()
-            .map(|((player, team), heatmap)| {
-                tracing::trace!("HeatMap for Player: {:?} in Team {:?}", player, team);
+            .map(|player_heatmap| {
+                tracing::trace!(
+                    "HeatMap for Player: {:?} in Team {:?}",
+                    player_heatmap.steam_id,
+                    player_heatmap.team
+                );
 
                 crate::models::DemoPlayerHeatmap {
                     demo_id: input.demoid.clone(),
-                    steam_id: player,
-                    team,
-                    data: serde_json::to_string(&heatmap).unwrap(),
+                    steam_id: player_heatmap.steam_id.to_string(),
+                    team: player_heatmap.team,
+                    data: serde_json::to_string(&player_heatmap.heatmap).unwrap(),
                 }
             })
             .collect();
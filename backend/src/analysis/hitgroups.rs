@@ -0,0 +1,62 @@
+use super::*;
+
+pub struct HitgroupsAnalysis {}
+
+impl HitgroupsAnalysis {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Analysis for HitgroupsAnalysis {
+    #[tracing::instrument(name = "Hitgroups", skip(self, input))]
+    fn analyse(
+        &self,
+        input: AnalysisInput,
+    ) -> Result<
+        Box<
+            dyn FnOnce(
+                    &mut diesel_async::pg::AsyncPgConnection,
+                ) -> core::pin::Pin<
+                    Box<
+                        (dyn core::future::Future<Output = Result<(), diesel::result::Error>>
+                             + Send
+                             + '_),
+                    >,
+                > + Send,
+        >,
+        (),
+    > {
+        tracing::info!("Collecting Hitgroups");
+
+        let result = analysis::hitgroups::parse(input.data())
+            .inspect_err(|e| {
+                tracing::error!("{:?}", e);
+            })
+            .map_err(|e| ())?;
+
+        let value = crate::models::DemoHitgroups {
+            demo_id: input.demoid.clone(),
+            players: serde_json::to_value(&result.players).unwrap(),
+        };
+
+        Ok(Box::new(move |connection| {
+            let query =
+                diesel::dsl::insert_into(crate::schema::demo_hitgroups::dsl::demo_hitgroups)
+                    .values(value)
+                    .on_conflict(crate::schema::demo_hitgroups::dsl::demo_id)
+                    .do_update()
+                    .set(
+                        crate::schema::demo_hitgroups::dsl::players.eq(diesel::upsert::excluded(
+                            crate::schema::demo_hitgroups::dsl::players,
+                        )),
+                    );
+
+            Box::pin(async move {
+                query.execute(connection).await?;
+
+                Ok(())
+            })
+        }))
+    }
+}
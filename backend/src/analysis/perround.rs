@@ -27,7 +27,35 @@ impl Analysis for PerRoundAnalysis {
         >,
         (),
     > {
-        let result = analysis::perround::parse(input.data()).unwrap();
+        let result = analysis::perround::parse(input.data())
+            .inspect_err(|e| {
+                tracing::error!("{:?}", e);
+            })
+            .map_err(|e| ())?;
+
+        let player_values: Vec<crate::models::DemoRoundPlayer> = result
+            .rounds
+            .iter()
+            .enumerate()
+            .flat_map(|(i, r)| {
+                let demo_id = input.demoid.clone();
+                r.players
+                    .iter()
+                    .map(move |p| crate::models::DemoRoundPlayer {
+                        demo_id: demo_id.clone(),
+                        round_number: i as i16,
+                        steam_id: p.steam_id.to_string(),
+                        team: p.team as i16,
+                        kills: p.kills as i16,
+                        damage_dealt: p.damage_dealt as i16,
+                        damage_received: p.damage_received as i16,
+                        survived: p.survived,
+                        end_health: p.end_health as i16,
+                        equipment_saved: p.equipment_saved as i32,
+                        start_equipment: Some(p.start_equipment as i32),
+                    })
+            })
+            .collect();
 
         let values: Vec<crate::models::DemoRound> = result
             .rounds
@@ -40,6 +68,7 @@ impl Analysis for PerRoundAnalysis {
                 end_tick: r.end as i64,
                 win_reason: serde_json::to_string(&r.winreason).unwrap(),
                 events: serde_json::to_value(&r.events).unwrap(),
+                alive: Some(serde_json::to_value(&r.alive).unwrap()),
             })
             .collect();
 
@@ -52,14 +81,66 @@ impl Analysis for PerRoundAnalysis {
                         crate::schema::demo_round::dsl::round_number,
                     ))
                     .do_update()
-                    .set(
+                    .set((
                         crate::schema::demo_round::dsl::events.eq(diesel::upsert::excluded(
                             crate::schema::demo_round::dsl::events,
                         )),
-                    );
+                        crate::schema::demo_round::dsl::alive.eq(diesel::upsert::excluded(
+                            crate::schema::demo_round::dsl::alive,
+                        )),
+                    ));
 
                 query.execute(connection).await?;
 
+                let players_query = diesel::dsl::insert_into(
+                    crate::schema::demo_round_players::dsl::demo_round_players,
+                )
+                .values(&player_values)
+                .on_conflict((
+                    crate::schema::demo_round_players::dsl::demo_id,
+                    crate::schema::demo_round_players::dsl::round_number,
+                    crate::schema::demo_round_players::dsl::steam_id,
+                ))
+                .do_update()
+                .set((
+                    crate::schema::demo_round_players::dsl::team.eq(diesel::upsert::excluded(
+                        crate::schema::demo_round_players::dsl::team,
+                    )),
+                    crate::schema::demo_round_players::dsl::kills.eq(diesel::upsert::excluded(
+                        crate::schema::demo_round_players::dsl::kills,
+                    )),
+                    crate::schema::demo_round_players::dsl::damage_dealt.eq(
+                        diesel::upsert::excluded(
+                            crate::schema::demo_round_players::dsl::damage_dealt,
+                        ),
+                    ),
+                    crate::schema::demo_round_players::dsl::damage_received.eq(
+                        diesel::upsert::excluded(
+                            crate::schema::demo_round_players::dsl::damage_received,
+                        ),
+                    ),
+                    crate::schema::demo_round_players::dsl::survived.eq(diesel::upsert::excluded(
+                        crate::schema::demo_round_players::dsl::survived,
+                    )),
+                    crate::schema::demo_round_players::dsl::end_health.eq(
+                        diesel::upsert::excluded(
+                            crate::schema::demo_round_players::dsl::end_health,
+                        ),
+                    ),
+                    crate::schema::demo_round_players::dsl::equipment_saved.eq(
+                        diesel::upsert::excluded(
+                            crate::schema::demo_round_players::dsl::equipment_saved,
+                        ),
+                    ),
+                    crate::schema::demo_round_players::dsl::start_equipment.eq(
+                        diesel::upsert::excluded(
+                            crate::schema::demo_round_players::dsl::start_equipment,
+                        ),
+                    ),
+                ));
+
+                players_query.execute(connection).await?;
+
                 Ok(())
             })
         }))
@@ -0,0 +1,60 @@
+use super::*;
+
+pub struct PostPlantAnalysis {}
+
+impl PostPlantAnalysis {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Analysis for PostPlantAnalysis {
+    #[tracing::instrument(name = "PostPlant", skip(self, input))]
+    fn analyse(
+        &self,
+        input: AnalysisInput,
+    ) -> Result<
+        Box<
+            dyn FnOnce(
+                    &mut diesel_async::pg::AsyncPgConnection,
+                ) -> core::pin::Pin<
+                    Box<
+                        (dyn core::future::Future<Output = Result<(), diesel::result::Error>>
+                             + Send
+                             + '_),
+                    >,
+                > + Send,
+        >,
+        (),
+    > {
+        tracing::info!("Tracking Post Plants");
+
+        let result = analysis::postplant::parse(input.data())
+            .inspect_err(|e| {
+                tracing::error!("{:?}", e);
+            })
+            .map_err(|e| ())?;
+
+        let value = crate::models::DemoPostPlants {
+            demo_id: input.demoid.clone(),
+            plants: serde_json::to_value(&result.plants).unwrap(),
+        };
+
+        Ok(Box::new(move |connection| {
+            let query =
+                diesel::dsl::insert_into(crate::schema::demo_post_plants::dsl::demo_post_plants)
+                    .values(value)
+                    .on_conflict(crate::schema::demo_post_plants::dsl::demo_id)
+                    .do_update()
+                    .set(crate::schema::demo_post_plants::dsl::plants.eq(
+                        diesel::upsert::excluded(crate::schema::demo_post_plants::dsl::plants),
+                    ));
+
+            Box::pin(async move {
+                query.execute(connection).await?;
+
+                Ok(())
+            })
+        }))
+    }
+}
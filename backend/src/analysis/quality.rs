@@ -0,0 +1,65 @@
+use super::*;
+
+pub struct QualityAnalysis {}
+
+impl QualityAnalysis {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Analysis for QualityAnalysis {
+    #[tracing::instrument(name = "Quality", skip(self, input))]
+    fn analyse(
+        &self,
+        input: AnalysisInput,
+    ) -> Result<
+        Box<
+            dyn FnOnce(
+                    &mut diesel_async::pg::AsyncPgConnection,
+                ) -> core::pin::Pin<
+                    Box<
+                        (dyn core::future::Future<Output = Result<(), diesel::result::Error>>
+                             + Send
+                             + '_),
+                    >,
+                > + Send,
+        >,
+        (),
+    > {
+        tracing::info!("Checking Data Quality");
+
+        let result = analysis::quality::parse(input.data())
+            .inspect_err(|e| {
+                tracing::error!("{:?}", e);
+            })
+            .map_err(|e| ())?;
+
+        if !result.is_clean() {
+            tracing::warn!("Demo has data quality issues: {:?}", result);
+        }
+
+        let value = crate::models::DemoQuality {
+            demo_id: input.demoid.clone(),
+            report: serde_json::to_value(&result).unwrap(),
+        };
+
+        Ok(Box::new(move |connection| {
+            let query = diesel::dsl::insert_into(crate::schema::demo_quality::dsl::demo_quality)
+                .values(value)
+                .on_conflict(crate::schema::demo_quality::dsl::demo_id)
+                .do_update()
+                .set(
+                    crate::schema::demo_quality::dsl::report.eq(diesel::upsert::excluded(
+                        crate::schema::demo_quality::dsl::report,
+                    )),
+                );
+
+            Box::pin(async move {
+                query.execute(connection).await?;
+
+                Ok(())
+            })
+        }))
+    }
+}
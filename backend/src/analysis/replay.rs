@@ -0,0 +1,79 @@
+use super::*;
+
+pub struct ReplayAnalysis {}
+
+impl ReplayAnalysis {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Analysis for ReplayAnalysis {
+    #[tracing::instrument(name = "Replay", skip(self, input))]
+    fn analyse(
+        &self,
+        input: AnalysisInput,
+    ) -> Result<
+        Box<
+            dyn FnOnce(
+                    &mut diesel_async::pg::AsyncPgConnection,
+                ) -> core::pin::Pin<
+                    Box<
+                        (dyn core::future::Future<Output = Result<(), diesel::result::Error>>
+                             + Send
+                             + '_),
+                    >,
+                > + Send,
+        >,
+        (),
+    > {
+        tracing::info!("Sampling Round Replays");
+
+        let config = analysis::replay::Config::default();
+        let result = analysis::replay::parse(&config, input.data())
+            .inspect_err(|e| {
+                tracing::error!("{:?}", e);
+            })
+            .map_err(|e| ())?;
+
+        let values: Vec<crate::models::DemoRoundReplay> = result
+            .rounds
+            .into_iter()
+            .enumerate()
+            .map(|(i, r)| crate::models::DemoRoundReplay {
+                demo_id: input.demoid.clone(),
+                round_number: i as i16,
+                sample_rate: result.sample_rate as i16,
+                replay: serde_json::to_value(&r).unwrap(),
+            })
+            .collect();
+
+        Ok(Box::new(move |connection| {
+            Box::pin(async move {
+                let query = diesel::dsl::insert_into(
+                    crate::schema::demo_round_replay::dsl::demo_round_replay,
+                )
+                .values(&values)
+                .on_conflict((
+                    crate::schema::demo_round_replay::dsl::demo_id,
+                    crate::schema::demo_round_replay::dsl::round_number,
+                ))
+                .do_update()
+                .set((
+                    crate::schema::demo_round_replay::dsl::sample_rate.eq(
+                        diesel::upsert::excluded(
+                            crate::schema::demo_round_replay::dsl::sample_rate,
+                        ),
+                    ),
+                    crate::schema::demo_round_replay::dsl::replay.eq(diesel::upsert::excluded(
+                        crate::schema::demo_round_replay::dsl::replay,
+                    )),
+                ));
+
+                query.execute(connection).await?;
+
+                Ok(())
+            })
+        }))
+    }
+}
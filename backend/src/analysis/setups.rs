@@ -0,0 +1,61 @@
+use super::*;
+
+pub struct SetupsAnalysis {}
+
+impl SetupsAnalysis {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Analysis for SetupsAnalysis {
+    #[tracing::instrument(name = "Setups", skip(self, input))]
+    fn analyse(
+        &self,
+        input: AnalysisInput,
+    ) -> Result<
+        Box<
+            dyn FnOnce(
+                    &mut diesel_async::pg::AsyncPgConnection,
+                ) -> core::pin::Pin<
+                    Box<
+                        (dyn core::future::Future<Output = Result<(), diesel::result::Error>>
+                             + Send
+                             + '_),
+                    >,
+                > + Send,
+        >,
+        (),
+    > {
+        tracing::info!("Detecting Setups");
+
+        let result = analysis::setups::parse(&analysis::setups::Config::default(), input.data())
+            .inspect_err(|e| {
+                tracing::error!("{:?}", e);
+            })
+            .map_err(|e| ())?;
+
+        let value = crate::models::DemoSetups {
+            demo_id: input.demoid.clone(),
+            sides: serde_json::to_value(&result.sides).unwrap(),
+        };
+
+        Ok(Box::new(move |connection| {
+            let query = diesel::dsl::insert_into(crate::schema::demo_setups::dsl::demo_setups)
+                .values(value)
+                .on_conflict(crate::schema::demo_setups::dsl::demo_id)
+                .do_update()
+                .set(
+                    crate::schema::demo_setups::dsl::sides.eq(diesel::upsert::excluded(
+                        crate::schema::demo_setups::dsl::sides,
+                    )),
+                );
+
+            Box::pin(async move {
+                query.execute(connection).await?;
+
+                Ok(())
+            })
+        }))
+    }
+}
@@ -0,0 +1,61 @@
+use super::*;
+
+pub struct TimelineAnalysis {}
+
+impl TimelineAnalysis {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Analysis for TimelineAnalysis {
+    #[tracing::instrument(name = "Timeline", skip(self, input))]
+    fn analyse(
+        &self,
+        input: AnalysisInput,
+    ) -> Result<
+        Box<
+            dyn FnOnce(
+                    &mut diesel_async::pg::AsyncPgConnection,
+                ) -> core::pin::Pin<
+                    Box<
+                        (dyn core::future::Future<Output = Result<(), diesel::result::Error>>
+                             + Send
+                             + '_),
+                    >,
+                > + Send,
+        >,
+        (),
+    > {
+        tracing::info!("Extracting Timeline");
+
+        let result = analysis::timeline::parse(input.data())
+            .inspect_err(|e| {
+                tracing::error!("{:?}", e);
+            })
+            .map_err(|e| ())?;
+
+        let value = crate::models::DemoTimeline {
+            demo_id: input.demoid.clone(),
+            events: serde_json::to_value(&result.entries).unwrap(),
+        };
+
+        Ok(Box::new(move |connection| {
+            let query = diesel::dsl::insert_into(crate::schema::demo_timeline::dsl::demo_timeline)
+                .values(value)
+                .on_conflict(crate::schema::demo_timeline::dsl::demo_id)
+                .do_update()
+                .set(
+                    crate::schema::demo_timeline::dsl::events.eq(diesel::upsert::excluded(
+                        crate::schema::demo_timeline::dsl::events,
+                    )),
+                );
+
+            Box::pin(async move {
+                query.execute(connection).await?;
+
+                Ok(())
+            })
+        }))
+    }
+}
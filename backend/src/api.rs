@@ -1,4 +1,5 @@
 pub mod demos;
+pub mod players;
 
 pub mod steam {
     use axum::extract::State;
@@ -168,6 +169,7 @@ pub fn router(config: RouterConfig) -> axum::Router {
         )
         .nest("/demos/", demos::router(config.storage))
         .nest("/user/", user::router())
+        .nest("/players/", players::router())
 }
 
 // Save a `Stream` to a file
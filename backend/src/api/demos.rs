@@ -23,6 +23,17 @@ pub fn router(storage: Box<dyn crate::storage::DemoStorage>) -> axum::Router {
         .route("/:id/analysis/perround", axum::routing::get(perround))
         .route("/:id/analysis/heatmap", axum::routing::get(heatmap))
         .route("/:id/analysis/headtohead", axum::routing::get(head_to_head))
+        .route("/:id/analysis/timeline", axum::routing::get(timeline))
+        .route("/:id/analysis/quality", axum::routing::get(quality))
+        .route("/:id/analysis/grenades", axum::routing::get(grenades))
+        .route("/:id/analysis/callouts", axum::routing::get(callouts))
+        .route("/:id/analysis/weapons", axum::routing::get(weapons))
+        .route("/:id/analysis/hitgroups", axum::routing::get(hitgroups))
+        .route("/:id/analysis/roundtypes", axum::routing::get(roundtypes))
+        .route("/:id/analysis/setups", axum::routing::get(setups))
+        .route("/:id/analysis/movement", axum::routing::get(movement))
+        .route("/:id/analysis/postplant", axum::routing::get(post_plant))
+        .route("/:id/rounds/:n/replay", axum::routing::get(round_replay))
         .with_state(Arc::new(DemoState { storage }))
 }
 
@@ -96,6 +107,7 @@ async fn list(
 
     let mut demos = std::collections::HashMap::new();
     for (demo, info, team, player) in results.into_iter() {
+        let duration = match_duration(&info);
         let entry = demos
             .entry(demo.demo_id.clone())
             .or_insert(common::BaseDemoInfo {
@@ -105,6 +117,7 @@ async fn list(
                 team2_score: 0,
                 team3_score: 0,
                 player_team: player.team,
+                duration,
             });
 
         if team.team == 2 {
@@ -277,13 +290,58 @@ async fn info(
     }
 
     let result = results.pop().unwrap();
+    let metadata = demo_metadata(&result);
 
     Ok(axum::Json(common::DemoInfo {
         id: result.demo_id,
         map: result.map,
+        metadata,
+        partial: result.partial,
     }))
 }
 
+/// Converts the stored metadata, returns `None` for demos that were analysed before the metadata
+/// was extracted
+fn demo_metadata(info: &crate::models::DemoInfo) -> Option<common::DemoMetadata> {
+    let tick_rate = info.tick_rate? as u32;
+    let demo_type = match serde_json::from_str(info.demo_type.as_ref()?) {
+        Ok(analysis::metadata::DemoType::Gotv) => "GOTV",
+        Ok(analysis::metadata::DemoType::Pov) => "POV",
+        Err(e) => {
+            tracing::error!("Deserializing Demo Type: {:?}", e);
+            return None;
+        }
+    };
+    let game_mode = match serde_json::from_str(info.game_mode.as_ref()?) {
+        Ok(analysis::metadata::GameMode::Matchmaking) => "Matchmaking",
+        Ok(analysis::metadata::GameMode::ValveServer) => "Valve Server",
+        Ok(analysis::metadata::GameMode::Community) => "Community Server",
+        Err(e) => {
+            tracing::error!("Deserializing Game Mode: {:?}", e);
+            return None;
+        }
+    };
+
+    Some(common::DemoMetadata {
+        server_name: info.server_name.clone()?,
+        demo_type: demo_type.to_owned(),
+        game_mode: game_mode.to_owned(),
+        tick_rate,
+        playback_duration: info.playback_ticks? as u32 / tick_rate.max(1),
+        match_start: info.match_start_tick.map(|t| t as u32 / tick_rate.max(1)),
+        network_protocol: info.network_protocol?,
+        build_number: info.build_number?,
+    })
+}
+
+/// The duration of the match in seconds, excluding the warmup before the match started
+fn match_duration(info: &crate::models::DemoInfo) -> Option<u32> {
+    let tick_rate = info.tick_rate? as u32;
+    let ticks = info.playback_ticks? - info.match_start_tick.unwrap_or(0);
+
+    Some(ticks.max(0) as u32 / tick_rate.max(1))
+}
+
 #[tracing::instrument(skip(session))]
 async fn scoreboard(
     session: UserSession,
@@ -303,7 +361,10 @@ async fn scoreboard(
         .filter(crate::schema::demo_players::dsl::demo_id.eq(demo_id.clone()));
 
     let team_query = crate::schema::demo_teams::dsl::demo_teams
-        .filter(crate::schema::demo_teams::dsl::demo_id.eq(demo_id));
+        .filter(crate::schema::demo_teams::dsl::demo_id.eq(demo_id.clone()));
+    let events_query = crate::schema::demo_round::dsl::demo_round
+        .select(crate::schema::demo_round::dsl::events)
+        .filter(crate::schema::demo_round::dsl::demo_id.eq(demo_id));
 
     let mut db_con = crate::db_connection().await;
 
@@ -315,13 +376,14 @@ async fn scoreboard(
                 let players: Vec<(crate::models::DemoPlayer, crate::models::DemoPlayerStats)> =
                     query.load(con).await?;
                 let teams: Vec<crate::models::DemoTeam> = team_query.load(con).await?;
+                let events: Vec<serde_json::Value> = events_query.load(con).await?;
 
-                Ok((players, teams))
+                Ok((players, teams, events))
             })
         })
         .await;
 
-    let (response, team_response) = match db_result {
+    let (response, team_response, raw_events) = match db_result {
         Ok(d) => d,
         Err(e) => {
             tracing::error!("Querying DB {:?}", e);
@@ -334,8 +396,25 @@ async fn scoreboard(
         return Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR);
     }
 
+    // The rounds are stored by a separate analysis, that might not have finished yet
+    let kill_stats = if raw_events.is_empty() {
+        None
+    } else {
+        Some(analysis::kills::player_stats(
+            round_events(raw_events)?.iter(),
+        ))
+    };
+
     let mut teams = std::collections::BTreeMap::new();
     for (player, stats) in response {
+        let player_kills = kill_stats.as_ref().map(|kill_stats| {
+            kill_stats
+                .iter()
+                .find(|k| k.steam_id.to_string() == player.steam_id)
+                .cloned()
+                .unwrap_or_default()
+        });
+
         let team =
             teams
                 .entry(player.team as u32)
@@ -343,6 +422,7 @@ async fn scoreboard(
                     number: player.team as u32,
                     players: Vec::new(),
                     score: 0,
+                    clan_name: None,
                 });
 
         team.players.push(common::demo_analysis::ScoreBoardPlayer {
@@ -351,6 +431,10 @@ async fn scoreboard(
             deaths: stats.deaths as usize,
             damage: stats.damage as usize,
             assists: stats.assists as usize,
+            rounds_played: player.rounds_played.map(|r| r as usize),
+            wallbangs: player_kills.as_ref().map(|k| k.wallbangs),
+            smoke_kills: player_kills.as_ref().map(|k| k.smoke_kills),
+            blind_kills: player_kills.as_ref().map(|k| k.blind_kills),
         });
     }
 
@@ -358,6 +442,7 @@ async fn scoreboard(
         let number = team.team as u32;
         if let Some(entry) = teams.get_mut(&number) {
             entry.score = team.end_score;
+            entry.clan_name = team.clan_name;
         }
     }
 
@@ -409,7 +494,7 @@ async fn heatmap(
         };
 
     let demo_map = &demo_info.map;
-    let minimap_coords = match MINIMAP_COORDINATES.get(demo_map) {
+    let minimap_coords = match analysis::minimap::MINIMAP_COORDINATES.get(demo_map) {
         Some(c) => c,
         None => {
             tracing::error!("Unknown Map in Demo: {:?}", demo_map);
@@ -423,10 +508,7 @@ async fn heatmap(
             let team = heatmap.team.clone();
             let mut heatmap: analysis::heatmap::HeatMap =
                 serde_json::from_str(&heatmap.data).unwrap();
-            heatmap.fit(
-                minimap_coords.x_coord(0.0)..minimap_coords.x_coord(1024.0),
-                minimap_coords.y_coord(1024.0)..minimap_coords.y_coord(0.0),
-            );
+            minimap_coords.fit(&mut heatmap);
             let h_image = heatmap.as_image();
 
             let mut buffer = std::io::Cursor::new(Vec::new());
@@ -451,7 +533,10 @@ async fn perround(
     Path(demo_id): Path<String>,
 ) -> Result<axum::response::Json<common::demo_analysis::PerRoundResult>, axum::http::StatusCode> {
     let rounds_query = crate::schema::demo_round::dsl::demo_round
-        .filter(crate::schema::demo_round::dsl::demo_id.eq(demo_id.clone()));
+        .filter(crate::schema::demo_round::dsl::demo_id.eq(demo_id.clone()))
+        .order(crate::schema::demo_round::dsl::round_number.asc());
+    let round_player_stats_query = crate::schema::demo_round_players::dsl::demo_round_players
+        .filter(crate::schema::demo_round_players::dsl::demo_id.eq(demo_id.clone()));
     let round_players_query = crate::schema::demo_players::dsl::demo_players
         .filter(crate::schema::demo_players::dsl::demo_id.eq(demo_id.clone()));
     let demo_teams = crate::schema::demo_teams::dsl::demo_teams
@@ -463,9 +548,28 @@ async fn perround(
     let players: Vec<crate::models::DemoPlayer> =
         round_players_query.load(&mut db_con).await.unwrap();
     let raw_teams: Vec<crate::models::DemoTeam> = demo_teams.load(&mut db_con).await.unwrap();
+    let round_player_stats: Vec<crate::models::DemoRoundPlayer> =
+        round_player_stats_query.load(&mut db_con).await.unwrap();
+
+    let analysed_rounds = analysis_rounds(&raw_rounds, &round_player_stats)?;
+    let advantages = analysis::advantage::advantage_stats(&analysed_rounds)
+        .into_iter()
+        .map(|s| common::demo_analysis::TeamAdvantage {
+            team: s.team as u32,
+            situation: format!("{}v{}", s.players, s.enemies),
+            rounds: s.rounds,
+            won: s.won,
+            lost: s.lost,
+        })
+        .collect();
+    let win_probability = analysis::winprob::evaluate(&analysed_rounds);
 
     let mut result = Vec::with_capacity(raw_rounds.len());
-    for raw_round in raw_rounds.into_iter() {
+    for (round_idx, (raw_round, analysed_round)) in raw_rounds
+        .into_iter()
+        .zip(analysed_rounds.iter())
+        .enumerate()
+    {
         let reason = match serde_json::from_str(&raw_round.win_reason) {
             Ok(analysis::perround::WinReason::StillInProgress) => {
                 common::demo_analysis::RoundWinReason::StillInProgress
@@ -499,9 +603,13 @@ async fn perround(
             serde_json::from_value(raw_round.events).unwrap();
         let events: Vec<_> = parsed_events
             .into_iter()
-            .map(|event| match event {
+            .enumerate()
+            .map(|(event_idx, event)| match event {
                 analysis::perround::RoundEvent::BombPlanted => {
-                    common::demo_analysis::RoundEvent::BombPlanted
+                    common::demo_analysis::RoundEvent::BombPlanted { place: None }
+                }
+                analysis::perround::RoundEvent::BombPlantedAt { place } => {
+                    common::demo_analysis::RoundEvent::BombPlanted { place }
                 }
                 analysis::perround::RoundEvent::BombDefused => {
                     common::demo_analysis::RoundEvent::BombDefused
@@ -512,6 +620,12 @@ async fn perround(
                     weapon,
                     noscope,
                     headshot,
+                    attacker_place,
+                    died_place,
+                    penetrated,
+                    thrusmoke,
+                    attackerblind,
+                    distance,
                 } => {
                     let attacker_name = players
                         .iter()
@@ -524,18 +638,64 @@ async fn perround(
                         .map(|p| p.name.clone())
                         .unwrap();
 
+                    let impact = win_probability
+                        .kills
+                        .iter()
+                        .find(|k| k.round == round_idx && k.event == event_idx)
+                        .map(|k| (k.impact * 100.0).round() as i32);
+
                     common::demo_analysis::RoundEvent::Killed {
                         attacker: attacker_name,
                         died: died_name,
                         weapon,
                         headshot,
                         noscope,
+                        attacker_place,
+                        died_place,
+                        penetrated,
+                        thrusmoke,
+                        attackerblind,
+                        distance: distance.map(|d| d.round() as u32),
+                        impact,
                     }
                 }
             })
             .collect();
 
-        result.push(common::demo_analysis::DemoRound { reason, events });
+        let round_players = round_player_stats
+            .iter()
+            .filter(|p| p.round_number == raw_round.round_number)
+            .filter_map(|stats| {
+                let player = players.iter().find(|p| p.steam_id == stats.steam_id)?;
+
+                Some(common::demo_analysis::RoundPlayer {
+                    name: player.name.clone(),
+                    team: stats.team as u32,
+                    kills: stats.kills as usize,
+                    damage_dealt: stats.damage_dealt as usize,
+                    damage_received: stats.damage_received as usize,
+                    survived: stats.survived,
+                    end_health: stats.end_health as u32,
+                    equipment_saved: stats.equipment_saved as u32,
+                })
+            })
+            .collect();
+
+        result.push(common::demo_analysis::DemoRound {
+            reason,
+            events,
+            players: round_players,
+            alive: analysed_round
+                .alive
+                .iter()
+                .map(|a| common::demo_analysis::AlivePlayers {
+                    tick: a.tick.saturating_sub(analysed_round.start),
+                    t: a.t,
+                    ct: a.ct,
+                })
+                .collect(),
+            length: analysed_round.end.saturating_sub(analysed_round.start),
+        });
     }
 
     let teams = raw_teams
@@ -548,12 +708,24 @@ async fn perround(
                 .filter(|p| p.team == dteam.team)
                 .map(|p| p.name.clone())
                 .collect(),
+            clan_name: dteam.clan_name,
+        })
+        .collect();
+
+    let momentum = win_probability
+        .points
+        .iter()
+        .map(|p| common::demo_analysis::MomentumPoint {
+            round: p.round,
+            starting_ct_win: (p.starting_ct_win * 100.0).round() as u32,
         })
         .collect();
 
     Ok(axum::Json(common::demo_analysis::PerRoundResult {
         rounds: result,
         teams,
+        advantages,
+        momentum,
     }))
 }
 
@@ -600,73 +772,1105 @@ async fn head_to_head(
     }))
 }
 
-// The corresponding values for each map can be found using the Source2 Viewer and opening the
-// files in 'game/csgo/pak01_dir.vpk' and then 'resource/overviews/{map}.txt'
-static MINIMAP_COORDINATES: phf::Map<&str, MiniMapDefinition> = phf::phf_map! {
-    "cs_italy" => MiniMapDefinition {
-        pos_x: -2647.0,
-        pos_y: 2592.0,
-        scale: 4.6
-    },
-    "cs_office" => MiniMapDefinition {
-        pos_x: -1838.0,
-        pos_y: 1858.0,
-        scale: 4.1,
-    },
-    "de_ancient" => MiniMapDefinition {
-        pos_x: -2953.0,
-        pos_y: 2164.0,
-        scale: 5.0,
-    },
-    "de_anubis" => MiniMapDefinition {
-        pos_x: -2796.0,
-        pos_y: 3328.0,
-        scale: 5.22,
-    },
-    "de_dust2" => MiniMapDefinition {
-        pos_x: -2476.0,
-        pos_y: 3239.0,
-        scale: 4.4
-    },
-    "de_inferno" => MiniMapDefinition {
-        pos_x: -2087.0,
-        pos_y: 3870.0,
-        scale: 4.9,
-    },
-    "de_mirage" => MiniMapDefinition {
-        pos_x: -3230.0,
-        pos_y: 1713.0,
-        scale: 5.0,
-    },
-    "de_nuke" => MiniMapDefinition {
-        pos_x: -3453.0,
-        pos_y: 2887.0,
-        scale: 7.0,
-    },
-    "de_overpass" => MiniMapDefinition {
-        pos_x: -4831.0,
-        pos_y: 1781.0,
-        scale: 5.2,
-    },
-    "de_vertigo" => MiniMapDefinition {
-        pos_x: -3168.0,
-        pos_y: 1762.0,
-        scale: 4.0,
-    },
-};
-
-#[derive(Debug, PartialEq)]
-struct MiniMapDefinition {
-    pos_x: f32,
-    pos_y: f32,
-    scale: f32,
+#[tracing::instrument(skip(session))]
+async fn timeline(
+    session: UserSession,
+    Path(demo_id): Path<String>,
+) -> Result<axum::response::Json<Vec<common::demo_analysis::TimelineEntry>>, axum::http::StatusCode>
+{
+    let timeline_query = crate::schema::demo_timeline::dsl::demo_timeline
+        .filter(crate::schema::demo_timeline::dsl::demo_id.eq(demo_id.clone()));
+    let player_query = crate::schema::demo_players::dsl::demo_players
+        .filter(crate::schema::demo_players::dsl::demo_id.eq(demo_id));
+
+    let mut db_con = crate::db_connection().await;
+
+    let (timeline, players) = db_con
+        .build_transaction()
+        .read_only()
+        .run(|connection| {
+            Box::pin(async move {
+                let timeline: crate::models::DemoTimeline =
+                    timeline_query.first(connection).await?;
+                let players: Vec<crate::models::DemoPlayer> = player_query.load(connection).await?;
+
+                Ok::<_, diesel::result::Error>((timeline, players))
+            })
+        })
+        .await
+        .map_err(|e| {
+            tracing::error!("Querying DB: {:?}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let entries: Vec<analysis::timeline::TimelineEntry> = serde_json::from_value(timeline.events)
+        .map_err(|e| {
+        tracing::error!("Deserializing Timeline: {:?}", e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let player_name = |steam_id: u64| {
+        players
+            .iter()
+            .find(|p| p.steam_id == steam_id.to_string())
+            .map(|p| p.name.clone())
+            .unwrap_or_else(|| steam_id.to_string())
+    };
+
+    let result = entries
+        .into_iter()
+        .map(|entry| {
+            let event = match entry.event {
+                analysis::timeline::TimelineEvent::Chat {
+                    sender,
+                    team_only,
+                    message,
+                } => common::demo_analysis::TimelineEvent::Chat {
+                    sender: player_name(sender),
+                    team_only,
+                    message,
+                },
+                analysis::timeline::TimelineEvent::TimeoutStarted { team } => {
+                    common::demo_analysis::TimelineEvent::TimeoutStarted {
+                        side: side_name(team).to_owned(),
+                    }
+                }
+                analysis::timeline::TimelineEvent::TimeoutEnded { team } => {
+                    common::demo_analysis::TimelineEvent::TimeoutEnded {
+                        side: side_name(team).to_owned(),
+                    }
+                }
+                analysis::timeline::TimelineEvent::TechnicalPauseStarted => {
+                    common::demo_analysis::TimelineEvent::TechnicalPauseStarted
+                }
+                analysis::timeline::TimelineEvent::TechnicalPauseEnded => {
+                    common::demo_analysis::TimelineEvent::TechnicalPauseEnded
+                }
+                analysis::timeline::TimelineEvent::VoteStarted { issue, .. } => {
+                    common::demo_analysis::TimelineEvent::VoteStarted {
+                        issue: vote_issue_name(&issue),
+                    }
+                }
+                analysis::timeline::TimelineEvent::VoteCast { voter, yes } => {
+                    common::demo_analysis::TimelineEvent::VoteCast {
+                        voter: player_name(voter),
+                        yes,
+                    }
+                }
+                analysis::timeline::TimelineEvent::VoteEnded { issue, yes, no } => {
+                    common::demo_analysis::TimelineEvent::VoteEnded {
+                        issue: vote_issue_name(&issue),
+                        yes,
+                        no,
+                    }
+                }
+                analysis::timeline::TimelineEvent::Surrender { team } => {
+                    common::demo_analysis::TimelineEvent::Surrender {
+                        side: side_name(team).to_owned(),
+                    }
+                }
+            };
+
+            common::demo_analysis::TimelineEntry {
+                tick: entry.tick,
+                round: entry.round,
+                event,
+            }
+        })
+        .collect();
+
+    Ok(axum::Json(result))
+}
+
+/// Parses the stored events of all the rounds of a demo
+fn round_events(
+    raw_events: Vec<serde_json::Value>,
+) -> Result<Vec<analysis::perround::RoundEvent>, axum::http::StatusCode> {
+    let mut events = Vec::new();
+    for raw in raw_events {
+        let round_events: Vec<analysis::perround::RoundEvent> = serde_json::from_value(raw)
+            .map_err(|e| {
+                tracing::error!("Deserializing Round Events: {:?}", e);
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+        events.extend(round_events);
+    }
+    Ok(events)
+}
+
+/// Rebuilds the result of the per round analysis from the stored rounds
+pub(crate) fn analysis_rounds(
+    raw_rounds: &[crate::models::DemoRound],
+    round_players: &[crate::models::DemoRoundPlayer],
+) -> Result<Vec<analysis::perround::Round>, axum::http::StatusCode> {
+    let mut rounds = Vec::with_capacity(raw_rounds.len());
+    for raw_round in raw_rounds {
+        let winreason = serde_json::from_str(&raw_round.win_reason).map_err(|e| {
+            tracing::error!("Deserializing Win Reason: {:?}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        // Rounds that were stored before the alive players were recorded don't have them
+        let alive = match raw_round.alive.clone() {
+            Some(raw) => serde_json::from_value(raw).map_err(|e| {
+                tracing::error!("Deserializing Alive Players: {:?}", e);
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR
+            })?,
+            None => Vec::new(),
+        };
+        let players = round_players
+            .iter()
+            .filter(|p| p.round_number == raw_round.round_number)
+            .map(|p| analysis::perround::RoundPlayer {
+                steam_id: p.steam_id.parse().unwrap_or(0),
+                team: p.team as i32,
+                kills: p.kills as usize,
+                damage_dealt: p.damage_dealt as usize,
+                damage_received: p.damage_received as usize,
+                survived: p.survived,
+                end_health: p.end_health as u32,
+                equipment_saved: p.equipment_saved as u32,
+                start_equipment: p.start_equipment.unwrap_or(0) as u32,
+            })
+            .collect();
+
+        rounds.push(analysis::perround::Round {
+            winreason,
+            start: raw_round.start_tick as u32,
+            end: raw_round.end_tick as u32,
+            events: round_events(vec![raw_round.events.clone()])?,
+            players,
+            alive,
+        });
+    }
+    Ok(rounds)
+}
+
+pub(crate) fn side_name(team: i32) -> &'static str {
+    match team {
+        2 => "T",
+        3 => "CT",
+        _ => "Unknown",
+    }
+}
+
+fn vote_issue_name(issue: &analysis::timeline::VoteIssue) -> String {
+    match issue {
+        analysis::timeline::VoteIssue::Kick => "Kick".to_owned(),
+        analysis::timeline::VoteIssue::Surrender => "Surrender".to_owned(),
+        analysis::timeline::VoteIssue::PauseMatch => "Pause Match".to_owned(),
+        analysis::timeline::VoteIssue::UnpauseMatch => "Unpause Match".to_owned(),
+        analysis::timeline::VoteIssue::Timeout => "Timeout".to_owned(),
+        analysis::timeline::VoteIssue::Other(idx) => format!("Issue {}", idx),
+    }
+}
+
+#[tracing::instrument(skip(session))]
+async fn quality(
+    session: UserSession,
+    Path(demo_id): Path<String>,
+) -> Result<axum::response::Json<common::demo_analysis::DataQuality>, axum::http::StatusCode> {
+    let quality_query = crate::schema::demo_quality::dsl::demo_quality
+        .filter(crate::schema::demo_quality::dsl::demo_id.eq(demo_id.clone()));
+    let info_query = crate::schema::demo_info::dsl::demo_info
+        .filter(crate::schema::demo_info::dsl::demo_id.eq(demo_id.clone()));
+    let player_query = crate::schema::demo_players::dsl::demo_players
+        .filter(crate::schema::demo_players::dsl::demo_id.eq(demo_id));
+
+    let mut db_con = crate::db_connection().await;
+
+    let (quality, info, players) = db_con
+        .build_transaction()
+        .read_only()
+        .run(|connection| {
+            Box::pin(async move {
+                let quality: Option<crate::models::DemoQuality> =
+                    quality_query.first(connection).await.optional()?;
+                let info: Option<crate::models::DemoInfo> =
+                    info_query.first(connection).await.optional()?;
+                let players: Vec<crate::models::DemoPlayer> = player_query.load(connection).await?;
+
+                Ok::<_, diesel::result::Error>((quality, info, players))
+            })
+        })
+        .await
+        .map_err(|e| {
+            tracing::error!("Querying DB: {:?}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    // Demos that were analysed before the data quality was checked have no report
+    let quality = quality.ok_or(axum::http::StatusCode::NOT_FOUND)?;
+    let report: analysis::quality::QualityReport =
+        serde_json::from_value(quality.report).map_err(|e| {
+            tracing::error!("Deserializing Quality Report: {:?}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let players_missing_info = report
+        .players_missing_info
+        .iter()
+        .map(|missing| match missing {
+            analysis::quality::MissingPlayerInfo::UserId(id) => format!("Ingame Id {}", id),
+            analysis::quality::MissingPlayerInfo::SteamId(steam_id) => players
+                .iter()
+                .find(|p| p.steam_id == steam_id.to_string())
+                .map(|p| p.name.clone())
+                .unwrap_or_else(|| steam_id.to_string()),
+        })
+        .collect();
+
+    let stat_mismatches: Vec<analysis::endofgame::StatMismatch> =
+        match info.and_then(|i| i.stat_mismatches) {
+            Some(raw) => serde_json::from_value(raw).map_err(|e| {
+                tracing::error!("Deserializing Stat Mismatches: {:?}", e);
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR
+            })?,
+            None => Vec::new(),
+        };
+    let stat_mismatches = stat_mismatches
+        .into_iter()
+        .map(|mismatch| common::demo_analysis::StatMismatch {
+            player: players
+                .iter()
+                .find(|p| p.steam_id == mismatch.steam_id.to_string())
+                .map(|p| p.name.clone())
+                .unwrap_or_else(|| mismatch.steam_id.to_string()),
+            stat: mismatch.stat,
+            computed: mismatch.events,
+            game: mismatch.controller,
+        })
+        .collect();
+
+    Ok(axum::Json(common::demo_analysis::DataQuality {
+        failed_events: report.failed_events.into_iter().collect(),
+        failed_entities: report.failed_entities.into_iter().collect(),
+        players_missing_info,
+        rounds_missing_winreason: report.rounds_missing_winreason,
+        unknown_map: report.unknown_map,
+        stat_mismatches,
+    }))
+}
+
+#[tracing::instrument(skip(session))]
+async fn round_replay(
+    session: UserSession,
+    Path((demo_id, round_number)): Path<(String, i16)>,
+) -> Result<axum::response::Json<common::demo_analysis::RoundReplay>, axum::http::StatusCode> {
+    let info_query = crate::schema::demo_info::dsl::demo_info
+        .select(crate::models::DemoInfo::as_select())
+        .filter(crate::schema::demo_info::dsl::demo_id.eq(demo_id.clone()));
+    let replay_query = crate::schema::demo_round_replay::dsl::demo_round_replay
+        .filter(crate::schema::demo_round_replay::dsl::demo_id.eq(demo_id.clone()))
+        .filter(crate::schema::demo_round_replay::dsl::round_number.eq(round_number));
+    let player_query = crate::schema::demo_players::dsl::demo_players
+        .filter(crate::schema::demo_players::dsl::demo_id.eq(demo_id));
+
+    let mut db_con = crate::db_connection().await;
+
+    let (info, replay, players) = db_con
+        .build_transaction()
+        .read_only()
+        .run(|connection| {
+            Box::pin(async move {
+                let info: crate::models::DemoInfo = info_query.first(connection).await?;
+                let replay: Option<crate::models::DemoRoundReplay> =
+                    replay_query.first(connection).await.optional()?;
+                let players: Vec<crate::models::DemoPlayer> = player_query.load(connection).await?;
+
+                Ok::<_, diesel::result::Error>((info, replay, players))
+            })
+        })
+        .await
+        .map_err(|e| {
+            tracing::error!("Querying DB: {:?}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let replay = replay.ok_or(axum::http::StatusCode::NOT_FOUND)?;
+    let minimap = match analysis::minimap::MINIMAP_COORDINATES.get(&info.map) {
+        Some(m) => m,
+        None => {
+            tracing::warn!("No minimap for {:?}", info.map);
+            return Err(axum::http::StatusCode::NOT_FOUND);
+        }
+    };
+    let round: analysis::replay::RoundReplay =
+        serde_json::from_value(replay.replay).map_err(|e| {
+            tracing::error!("Deserializing Replay: {:?}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let player_name = |steam_id: u64| {
+        players
+            .iter()
+            .find(|p| p.steam_id == steam_id.to_string())
+            .map(|p| p.name.clone())
+            .unwrap_or_else(|| steam_id.to_string())
+    };
+
+    let frames = round
+        .frames
+        .into_iter()
+        .map(|frame| common::demo_analysis::ReplayFrame {
+            tick: frame.tick,
+            players: frame
+                .players
+                .into_iter()
+                .map(|sample| {
+                    sample.map(|sample| {
+                        let (x, y) = minimap.radar_position(sample.position.0, sample.position.1);
+                        common::demo_analysis::ReplayPlayerState {
+                            team: sample.team,
+                            x,
+                            y,
+                            yaw: sample.yaw,
+                            health: sample.health,
+                            armor: sample.armor,
+                            weapon: sample.weapon,
+                            alive: sample.alive,
+                            place: sample.place,
+                        }
+                    })
+                })
+                .collect(),
+        })
+        .collect();
+
+    let kills = round
+        .kills
+        .into_iter()
+        .map(|kill| common::demo_analysis::ReplayKill {
+            tick: kill.tick,
+            attacker: kill.attacker.map(player_name),
+            died: player_name(kill.died),
+            weapon: kill.weapon,
+            position: kill.position.map(|(x, y, _)| minimap.radar_position(x, y)),
+        })
+        .collect();
+
+    let grenades = round
+        .grenades
+        .into_iter()
+        .map(|grenade| common::demo_analysis::ReplayGrenade {
+            kind: grenade.kind,
+            thrower: grenade.thrower.map(player_name),
+            points: grenade
+                .points
+                .into_iter()
+                .map(|(tick, x, y, _)| {
+                    let (x, y) = minimap.radar_position(x, y);
+                    (tick, x, y)
+                })
+                .collect(),
+        })
+        .collect();
+
+    Ok(axum::Json(common::demo_analysis::RoundReplay {
+        sample_rate: replay.sample_rate as u32,
+        players: round.players.into_iter().map(player_name).collect(),
+        frames,
+        kills,
+        grenades,
+    }))
+}
+
+#[tracing::instrument(skip(session))]
+async fn grenades(
+    session: UserSession,
+    Path(demo_id): Path<String>,
+) -> Result<axum::response::Json<Vec<common::demo_analysis::GrenadeThrow>>, axum::http::StatusCode>
+{
+    let info_query = crate::schema::demo_info::dsl::demo_info
+        .select(crate::models::DemoInfo::as_select())
+        .filter(crate::schema::demo_info::dsl::demo_id.eq(demo_id.clone()));
+    let grenades_query = crate::schema::demo_grenades::dsl::demo_grenades
+        .filter(crate::schema::demo_grenades::dsl::demo_id.eq(demo_id.clone()));
+    let player_query = crate::schema::demo_players::dsl::demo_players
+        .filter(crate::schema::demo_players::dsl::demo_id.eq(demo_id));
+
+    let mut db_con = crate::db_connection().await;
+
+    let (info, grenades, players) = db_con
+        .build_transaction()
+        .read_only()
+        .run(|connection| {
+            Box::pin(async move {
+                let info: crate::models::DemoInfo = info_query.first(connection).await?;
+                let grenades: crate::models::DemoGrenades =
+                    grenades_query.first(connection).await?;
+                let players: Vec<crate::models::DemoPlayer> = player_query.load(connection).await?;
+
+                Ok::<_, diesel::result::Error>((info, grenades, players))
+            })
+        })
+        .await
+        .map_err(|e| {
+            tracing::error!("Querying DB: {:?}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let minimap = match analysis::minimap::MINIMAP_COORDINATES.get(&info.map) {
+        Some(m) => m,
+        None => {
+            tracing::warn!("No minimap for {:?}", info.map);
+            return Err(axum::http::StatusCode::NOT_FOUND);
+        }
+    };
+    let grenades: Vec<analysis::grenades::GrenadeThrow> = serde_json::from_value(grenades.grenades)
+        .map_err(|e| {
+            tracing::error!("Deserializing Grenades: {:?}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let result = grenades
+        .into_iter()
+        .map(|grenade| common::demo_analysis::GrenadeThrow {
+            round: grenade.round,
+            kind: grenade.kind,
+            thrower: grenade
+                .thrower
+                .and_then(|steam_id| {
+                    players
+                        .iter()
+                        .find(|p| p.steam_id == steam_id.to_string())
+                        .map(|p| p.name.clone())
+                })
+                .unwrap_or_default(),
+            side: side_name(grenade.team as i32).to_owned(),
+            throw_position: minimap
+                .radar_position(grenade.throw_position.0, grenade.throw_position.1),
+            land_position: minimap.radar_position(grenade.land_position.0, grenade.land_position.1),
+        })
+        .collect();
+
+    Ok(axum::Json(result))
+}
+
+#[tracing::instrument(skip(session))]
+async fn callouts(
+    session: UserSession,
+    Path(demo_id): Path<String>,
+) -> Result<axum::response::Json<Vec<common::demo_analysis::CalloutStats>>, axum::http::StatusCode>
+{
+    let rounds_query = crate::schema::demo_round::dsl::demo_round
+        .select(crate::schema::demo_round::dsl::events)
+        .filter(crate::schema::demo_round::dsl::demo_id.eq(demo_id.clone()));
+    let player_query = crate::schema::demo_players::dsl::demo_players
+        .filter(crate::schema::demo_players::dsl::demo_id.eq(demo_id));
+
+    let mut db_con = crate::db_connection().await;
+
+    let (raw_events, players) = db_con
+        .build_transaction()
+        .read_only()
+        .run(|connection| {
+            Box::pin(async move {
+                let events: Vec<serde_json::Value> = rounds_query.load(connection).await?;
+                let players: Vec<crate::models::DemoPlayer> = player_query.load(connection).await?;
+
+                Ok::<_, diesel::result::Error>((events, players))
+            })
+        })
+        .await
+        .map_err(|e| {
+            tracing::error!("Querying DB: {:?}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let events = round_events(raw_events)?;
+
+    let result = analysis::places::callout_stats(events.iter())
+        .into_iter()
+        .filter_map(|stats| {
+            let player = players
+                .iter()
+                .find(|p| p.steam_id == stats.steam_id.to_string())?;
+
+            Some(common::demo_analysis::CalloutStats {
+                player: player.name.clone(),
+                place: stats.place,
+                kills: stats.kills,
+                deaths: stats.deaths,
+            })
+        })
+        .collect();
+
+    Ok(axum::Json(result))
+}
+
+#[tracing::instrument(skip(session))]
+async fn weapons(
+    session: UserSession,
+    Path(demo_id): Path<String>,
+) -> Result<axum::response::Json<Vec<common::demo_analysis::WeaponStats>>, axum::http::StatusCode> {
+    let rounds_query = crate::schema::demo_round::dsl::demo_round
+        .select(crate::schema::demo_round::dsl::events)
+        .filter(crate::schema::demo_round::dsl::demo_id.eq(demo_id));
+
+    let mut db_con = crate::db_connection().await;
+
+    let raw_events: Vec<serde_json::Value> = rounds_query.load(&mut db_con).await.map_err(|e| {
+        tracing::error!("Querying DB: {:?}", e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let events = round_events(raw_events)?;
+
+    let result = analysis::kills::weapon_stats(events.iter())
+        .into_iter()
+        .map(|stats| common::demo_analysis::WeaponStats {
+            weapon: stats.weapon,
+            kills: stats.kills,
+            headshots: stats.headshots,
+            noscopes: stats.noscopes,
+            wallbangs: stats.wallbangs,
+            smoke_kills: stats.smoke_kills,
+            blind_kills: stats.blind_kills,
+            ranges: stats
+                .ranges
+                .into_iter()
+                .enumerate()
+                .map(|(idx, kills)| (analysis::kills::range_name(idx), kills))
+                .collect(),
+        })
+        .collect();
+
+    Ok(axum::Json(result))
 }
 
-impl MiniMapDefinition {
-    pub fn x_coord(&self, map_coord: f32) -> f32 {
-        (map_coord * self.scale) + self.pos_x + analysis::heatmap::MAX_COORD
+#[tracing::instrument(skip(session))]
+async fn hitgroups(
+    session: UserSession,
+    Path(demo_id): Path<String>,
+) -> Result<axum::response::Json<Vec<common::demo_analysis::PlayerHitgroups>>, axum::http::StatusCode>
+{
+    let hitgroups_query = crate::schema::demo_hitgroups::dsl::demo_hitgroups
+        .filter(crate::schema::demo_hitgroups::dsl::demo_id.eq(demo_id.clone()));
+    let player_query = crate::schema::demo_players::dsl::demo_players
+        .filter(crate::schema::demo_players::dsl::demo_id.eq(demo_id));
+
+    let mut db_con = crate::db_connection().await;
+
+    let (hitgroups, players) = db_con
+        .build_transaction()
+        .read_only()
+        .run(|connection| {
+            Box::pin(async move {
+                let hitgroups: Option<crate::models::DemoHitgroups> =
+                    hitgroups_query.first(connection).await.optional()?;
+                let players: Vec<crate::models::DemoPlayer> = player_query.load(connection).await?;
+
+                Ok::<_, diesel::result::Error>((hitgroups, players))
+            })
+        })
+        .await
+        .map_err(|e| {
+            tracing::error!("Querying DB: {:?}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let hitgroups = hitgroups.ok_or(axum::http::StatusCode::NOT_FOUND)?;
+    let player_hits: Vec<analysis::hitgroups::PlayerHits> =
+        serde_json::from_value(hitgroups.players).map_err(|e| {
+            tracing::error!("Deserializing Hitgroups: {:?}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let convert = |hits: Vec<analysis::hitgroups::HitStats>| {
+        hits.into_iter()
+            .map(|h| common::demo_analysis::HitgroupDamage {
+                hitgroup: format!("{:?}", h.hitgroup),
+                weapon: h.weapon,
+                hits: h.hits,
+                damage: h.damage,
+                armor_damage: h.armor_damage,
+            })
+            .collect()
+    };
+
+    let result = player_hits
+        .into_iter()
+        .filter_map(|p| {
+            let player = players
+                .iter()
+                .find(|player| player.steam_id == p.steam_id.to_string())?;
+
+            Some(common::demo_analysis::PlayerHitgroups {
+                name: player.name.clone(),
+                dealt: convert(p.dealt),
+                received: convert(p.received),
+            })
+        })
+        .collect();
+
+    Ok(axum::Json(result))
+}
+
+#[tracing::instrument(skip(session))]
+async fn roundtypes(
+    session: UserSession,
+    Path(demo_id): Path<String>,
+) -> Result<axum::response::Json<common::demo_analysis::RoundTypes>, axum::http::StatusCode> {
+    let rounds_query = crate::schema::demo_round::dsl::demo_round
+        .filter(crate::schema::demo_round::dsl::demo_id.eq(demo_id.clone()))
+        .order(crate::schema::demo_round::dsl::round_number.asc());
+    let round_players_query = crate::schema::demo_round_players::dsl::demo_round_players
+        .filter(crate::schema::demo_round_players::dsl::demo_id.eq(demo_id.clone()));
+    let teams_query = crate::schema::demo_teams::dsl::demo_teams
+        .filter(crate::schema::demo_teams::dsl::demo_id.eq(demo_id));
+
+    let mut db_con = crate::db_connection().await;
+
+    let (raw_rounds, round_players, teams) = db_con
+        .build_transaction()
+        .read_only()
+        .run(|connection| {
+            Box::pin(async move {
+                let raw_rounds: Vec<crate::models::DemoRound> =
+                    rounds_query.load(connection).await?;
+                let round_players: Vec<crate::models::DemoRoundPlayer> =
+                    round_players_query.load(connection).await?;
+                let teams: Vec<crate::models::DemoTeam> = teams_query.load(connection).await?;
+
+                Ok::<_, diesel::result::Error>((raw_rounds, round_players, teams))
+            })
+        })
+        .await
+        .map_err(|e| {
+            tracing::error!("Querying DB: {:?}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    // Rounds that were analysed before the equipment at the start of the round was recorded
+    // can't be classified
+    if round_players.iter().all(|p| p.start_equipment.is_none()) {
+        return Err(axum::http::StatusCode::NOT_FOUND);
     }
-    pub fn y_coord(&self, map_coord: f32) -> f32 {
-        -(map_coord * self.scale) + self.pos_y + analysis::heatmap::MAX_COORD
+
+    let rounds = analysis_rounds(&raw_rounds, &round_players)?;
+    let types = analysis::roundtypes::classify(&rounds);
+    let summary = analysis::roundtypes::team_summary(&rounds, &types);
+
+    let team_name = |number: i32| {
+        let clan_name = teams
+            .iter()
+            .find(|t| t.team as i32 == number)
+            .and_then(|t| t.clan_name.clone());
+        clan_name.unwrap_or_else(|| format!("Team {}", number))
+    };
+
+    Ok(axum::Json(common::demo_analysis::RoundTypes {
+        rounds: types
+            .into_iter()
+            .map(|round| common::demo_analysis::RoundTypeEntry {
+                teams: round
+                    .teams
+                    .into_iter()
+                    .map(|t| common::demo_analysis::TeamBuy {
+                        team: t.team as u32,
+                        side: side_name(t.side).to_owned(),
+                        buy: format!("{:?}", t.buy),
+                        equipment_value: t.equipment_value,
+                        won: t.won,
+                    })
+                    .collect(),
+                post_plant: round.post_plant,
+            })
+            .collect(),
+        teams: summary
+            .into_iter()
+            .map(|t| common::demo_analysis::TeamConversions {
+                team: t.team as u32,
+                name: team_name(t.team),
+                pistol_rounds: t.pistol_rounds,
+                pistol_wins: t.pistol_wins,
+                pistol_conversions: t.pistol_conversions,
+                force_rounds: t.force_rounds,
+                force_wins: t.force_wins,
+                advantage_rounds: t.advantage_rounds,
+                advantage_wins: t.advantage_wins,
+            })
+            .collect(),
+    }))
+}
+
+async fn setups(
+    session: UserSession,
+    Path(demo_id): Path<String>,
+) -> Result<axum::response::Json<common::demo_analysis::Setups>, axum::http::StatusCode> {
+    let steam_id = session.data().steam_id;
+
+    let info_query = crate::schema::demo_info::dsl::demo_info
+        .filter(crate::schema::demo_info::dsl::demo_id.eq(demo_id.clone()));
+    let setups_query = crate::schema::demo_setups::dsl::demo_setups
+        .filter(crate::schema::demo_setups::dsl::demo_id.eq(demo_id.clone()));
+    let teams_query = crate::schema::demo_teams::dsl::demo_teams
+        .filter(crate::schema::demo_teams::dsl::demo_id.eq(demo_id.clone()));
+    let players_query = crate::schema::demo_players::dsl::demo_players
+        .filter(crate::schema::demo_players::dsl::demo_id.eq(demo_id))
+        .select(crate::schema::demo_players::dsl::steam_id);
+
+    let mut db_con = crate::db_connection().await;
+
+    let (info, setups, teams, own_setups, player_setups) = db_con
+        .build_transaction()
+        .read_only()
+        .run(|connection| {
+            Box::pin(async move {
+                let info: Option<crate::models::DemoInfo> =
+                    info_query.first(connection).await.optional()?;
+                let setups: Option<crate::models::DemoSetups> =
+                    setups_query.first(connection).await.optional()?;
+                let teams: Vec<crate::models::DemoTeam> = teams_query.load(connection).await?;
+
+                // The setups of every demo the user uploaded on the same map
+                let own_setups: Vec<crate::models::DemoSetups> = match (&info, steam_id) {
+                    (Some(info), Some(steam_id)) => {
+                        crate::schema::demo_setups::dsl::demo_setups
+                            .inner_join(
+                                crate::schema::demos::table
+                                    .on(crate::schema::demo_setups::dsl::demo_id
+                                        .eq(crate::schema::demos::dsl::demo_id)),
+                            )
+                            .inner_join(
+                                crate::schema::demo_info::table
+                                    .on(crate::schema::demo_setups::dsl::demo_id
+                                        .eq(crate::schema::demo_info::dsl::demo_id)),
+                            )
+                            .select(crate::models::DemoSetups::as_select())
+                            .filter(
+                                crate::schema::demos::dsl::steam_id
+                                    .eq(steam_id.to_string())
+                                    .and(crate::schema::demo_info::dsl::map.eq(info.map.clone())),
+                            )
+                            .load(connection)
+                            .await?
+                    }
+                    _ => Vec::new(),
+                };
+
+                // The setups of every demo on the same map, that one of the players of this demo
+                // played in, to find the teams in them
+                let player_setups: Vec<crate::models::DemoSetups> = match &info {
+                    Some(info) => {
+                        let players: Vec<String> = players_query.load(connection).await?;
+                        let demo_ids: Vec<String> = crate::schema::demo_players::dsl::demo_players
+                            .filter(crate::schema::demo_players::dsl::steam_id.eq_any(players))
+                            .select(crate::schema::demo_players::dsl::demo_id)
+                            .distinct()
+                            .load(connection)
+                            .await?;
+
+                        crate::schema::demo_setups::dsl::demo_setups
+                            .inner_join(
+                                crate::schema::demo_info::table
+                                    .on(crate::schema::demo_setups::dsl::demo_id
+                                        .eq(crate::schema::demo_info::dsl::demo_id)),
+                            )
+                            .select(crate::models::DemoSetups::as_select())
+                            .filter(
+                                crate::schema::demo_setups::dsl::demo_id
+                                    .eq_any(demo_ids)
+                                    .and(crate::schema::demo_info::dsl::map.eq(info.map.clone())),
+                            )
+                            .load(connection)
+                            .await?
+                    }
+                    None => Vec::new(),
+                };
+
+                Ok::<_, diesel::result::Error>((info, setups, teams, own_setups, player_setups))
+            })
+        })
+        .await
+        .map_err(|e| {
+            tracing::error!("Querying DB: {:?}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let info = info.ok_or(axum::http::StatusCode::NOT_FOUND)?;
+    let setups = setups.ok_or(axum::http::StatusCode::NOT_FOUND)?;
+
+    let parse_sides = |value: serde_json::Value| {
+        serde_json::from_value::<Vec<analysis::setups::SideSetup>>(value).map_err(|e| {
+            tracing::error!("Deserializing Setups: {:?}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })
+    };
+    let convert = |stats: Vec<analysis::setups::SetupStats>| {
+        stats
+            .into_iter()
+            .map(|s| common::demo_analysis::SetupEntry {
+                side: side_name(s.side).to_owned(),
+                places: s.places,
+                rounds: s.rounds,
+                wins: s.wins,
+                similar: s.similar,
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let sides = parse_sides(setups.sides)?;
+    let mut team_numbers: Vec<i32> = sides.iter().map(|s| s.team).collect();
+    team_numbers.sort_unstable();
+    team_numbers.dedup();
+
+    let team_name = |number: i32| {
+        let clan_name = teams
+            .iter()
+            .find(|t| t.team as i32 == number)
+            .and_then(|t| t.clan_name.clone());
+        clan_name.unwrap_or_else(|| format!("Team {}", number))
+    };
+
+    let player_sides = player_setups
+        .into_iter()
+        .map(|s| parse_sides(s.sides))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // A team is found in the other demos by its players, as the team numbers differ between demos
+    let teams = team_numbers
+        .into_iter()
+        .map(|team| {
+            let mut roster: Vec<u64> = sides
+                .iter()
+                .filter(|s| s.team == team)
+                .flat_map(|s| s.players.iter().copied())
+                .collect();
+            roster.sort_unstable();
+            roster.dedup();
+
+            let mut demos = 0;
+            let mut all_sides = Vec::new();
+            for demo_sides in player_sides.iter() {
+                let len = all_sides.len();
+                all_sides.extend(demo_sides.iter().filter(|s| s.played_by(&roster)));
+                demos += (all_sides.len() > len) as usize;
+            }
+
+            common::demo_analysis::TeamSetups {
+                team: team as u32,
+                name: team_name(team),
+                setups: convert(analysis::setups::setup_stats(
+                    sides.iter().filter(|s| s.team == team),
+                )),
+                all_demos: convert(analysis::setups::setup_stats(all_sides)),
+                demos,
+            }
+        })
+        .collect();
+
+    // The user's team is the one they played in, which can be a different team number in every
+    // demo
+    let own_demos = own_setups.len();
+    let own_sides: Vec<analysis::setups::SideSetup> = match steam_id {
+        Some(steam_id) => own_setups
+            .into_iter()
+            .map(|s| parse_sides(s.sides))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .flatten()
+            .filter(|s| s.players.contains(&steam_id))
+            .collect(),
+        None => Vec::new(),
+    };
+
+    Ok(axum::Json(common::demo_analysis::Setups {
+        map: info.map,
+        teams,
+        own: convert(analysis::setups::setup_stats(own_sides.iter())),
+        own_demos,
+    }))
+}
+
+async fn movement(
+    session: UserSession,
+    Path(demo_id): Path<String>,
+) -> Result<axum::response::Json<common::demo_analysis::Movement>, axum::http::StatusCode> {
+    let replay_query = crate::schema::demo_round_replay::dsl::demo_round_replay
+        .filter(crate::schema::demo_round_replay::dsl::demo_id.eq(demo_id.clone()))
+        .order(crate::schema::demo_round_replay::dsl::round_number.asc());
+    let info_query = crate::schema::demo_info::dsl::demo_info
+        .filter(crate::schema::demo_info::dsl::demo_id.eq(demo_id.clone()));
+    let player_query = crate::schema::demo_players::dsl::demo_players
+        .filter(crate::schema::demo_players::dsl::demo_id.eq(demo_id));
+
+    let mut db_con = crate::db_connection().await;
+
+    let (replays, info, players) = db_con
+        .build_transaction()
+        .read_only()
+        .run(|connection| {
+            Box::pin(async move {
+                let replays: Vec<crate::models::DemoRoundReplay> =
+                    replay_query.load(connection).await?;
+                let info: Option<crate::models::DemoInfo> =
+                    info_query.first(connection).await.optional()?;
+                let players: Vec<crate::models::DemoPlayer> = player_query.load(connection).await?;
+
+                Ok::<_, diesel::result::Error>((replays, info, players))
+            })
+        })
+        .await
+        .map_err(|e| {
+            tracing::error!("Querying DB: {:?}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    if replays.is_empty() {
+        return Err(axum::http::StatusCode::NOT_FOUND);
     }
+    let tick_rate = info
+        .and_then(|i| i.tick_rate)
+        .map(|t| t as u32)
+        .unwrap_or(analysis::metadata::DEFAULT_TICK_RATE);
+
+    let rounds = replays
+        .into_iter()
+        .map(|replay| {
+            let round: analysis::replay::RoundReplay = serde_json::from_value(replay.replay)
+                .map_err(|e| {
+                    tracing::error!("Deserializing Replay: {:?}", e);
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+            Ok((
+                replay.round_number as u32,
+                analysis::movement::round_movement(&round, tick_rate),
+            ))
+        })
+        .collect::<Result<Vec<_>, axum::http::StatusCode>>()?;
+    let total = analysis::movement::total_movement(rounds.iter().map(|(_, m)| m.as_slice()));
+
+    let convert = |movement: &[analysis::movement::PlayerMovement]| {
+        movement
+            .iter()
+            .map(|m| common::demo_analysis::PlayerMovement {
+                name: players
+                    .iter()
+                    .find(|p| p.steam_id == m.steam_id.to_string())
+                    .map(|p| p.name.clone())
+                    .unwrap_or_else(|| m.steam_id.to_string()),
+                distance: m.distance,
+                average_speed: m.average_speed(),
+                still: m.still,
+                crouched: m.crouched,
+                walking: m.walking,
+                running: m.running,
+                places: m
+                    .places
+                    .iter()
+                    .map(|(place, seconds)| common::demo_analysis::PlaceTime {
+                        place: place.clone(),
+                        seconds: *seconds,
+                    })
+                    .collect(),
+            })
+            .collect::<Vec<_>>()
+    };
+
+    Ok(axum::Json(common::demo_analysis::Movement {
+        rounds: rounds
+            .iter()
+            .map(|(round, movement)| common::demo_analysis::RoundMovement {
+                round: *round,
+                players: convert(movement),
+            })
+            .collect(),
+        total: convert(&total),
+    }))
+}
+
+async fn post_plant(
+    session: UserSession,
+    Path(demo_id): Path<String>,
+) -> Result<axum::response::Json<common::demo_analysis::PostPlants>, axum::http::StatusCode> {
+    let plants_query = crate::schema::demo_post_plants::dsl::demo_post_plants
+        .filter(crate::schema::demo_post_plants::dsl::demo_id.eq(demo_id.clone()));
+    let teams_query = crate::schema::demo_teams::dsl::demo_teams
+        .filter(crate::schema::demo_teams::dsl::demo_id.eq(demo_id));
+
+    let mut db_con = crate::db_connection().await;
+
+    let (plants, teams) = db_con
+        .build_transaction()
+        .read_only()
+        .run(|connection| {
+            Box::pin(async move {
+                let plants: Option<crate::models::DemoPostPlants> =
+                    plants_query.first(connection).await.optional()?;
+                let teams: Vec<crate::models::DemoTeam> = teams_query.load(connection).await?;
+
+                Ok::<_, diesel::result::Error>((plants, teams))
+            })
+        })
+        .await
+        .map_err(|e| {
+            tracing::error!("Querying DB: {:?}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let plants = plants.ok_or(axum::http::StatusCode::NOT_FOUND)?;
+    let plants: Vec<analysis::postplant::PostPlant> = serde_json::from_value(plants.plants)
+        .map_err(|e| {
+            tracing::error!("Deserializing Post Plants: {:?}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let team_name = |number: i32| {
+        let clan_name = teams
+            .iter()
+            .find(|t| t.team as i32 == number)
+            .and_then(|t| t.clan_name.clone());
+        clan_name.unwrap_or_else(|| format!("Team {}", number))
+    };
+    let site_name = |site: Option<String>| site.unwrap_or_else(|| "Unknown".to_owned());
+    let convert = |stats: Vec<analysis::postplant::PostPlantStats>| {
+        stats
+            .into_iter()
+            .map(|s| common::demo_analysis::PostPlantSituation {
+                site: site_name(s.site),
+                players: s.players,
+                rounds: s.rounds,
+                defused: s.defused,
+                exploded: s.exploded,
+                eliminated: s.eliminated,
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut team_numbers: Vec<i32> = plants.iter().flat_map(|p| [p.t_team, p.ct_team]).collect();
+    team_numbers.sort_unstable();
+    team_numbers.dedup();
+
+    Ok(axum::Json(common::demo_analysis::PostPlants {
+        teams: team_numbers
+            .into_iter()
+            .map(|team| common::demo_analysis::TeamPostPlants {
+                team: team as u32,
+                name: team_name(team),
+                post_plants: convert(analysis::postplant::post_plant_stats(
+                    plants.iter().filter(|p| p.t_team == team),
+                )),
+                retakes: convert(analysis::postplant::post_plant_stats(
+                    plants.iter().filter(|p| p.ct_team == team),
+                )),
+            })
+            .collect(),
+        plants: plants
+            .into_iter()
+            .map(|p| common::demo_analysis::PlantEntry {
+                round: p.round,
+                site: site_name(p.site),
+                team: team_name(p.t_team),
+                t_alive: p.t_alive,
+                ct_alive: p.ct_alive,
+                time_remaining: p.time_remaining,
+                t_places: p
+                    .t_positions
+                    .into_iter()
+                    .filter_map(|position| position.place)
+                    .collect(),
+                outcome: format!("{:?}", p.outcome),
+            })
+            .collect(),
+    }))
 }
@@ -0,0 +1,421 @@
+use crate::UserSession;
+use axum::extract::{Path, Query};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use std::collections::HashMap;
+
+pub fn router() -> axum::Router {
+    axum::Router::new()
+        .route("/:steamid/stats", axum::routing::get(stats))
+        .route("/:steamid/heatmaps/:map", axum::routing::get(heatmap))
+}
+
+/// The date filters use the day a demo was first uploaded, the date of the match itself is not
+/// stored
+#[derive(Debug, serde::Deserialize)]
+struct StatsFilter {
+    /// Only include demos uploaded on or after this day
+    from: Option<chrono::NaiveDate>,
+    /// Only include demos uploaded on or before this day
+    to: Option<chrono::NaiveDate>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct HeatmapFilter {
+    /// Only include demos uploaded on or after this day
+    from: Option<chrono::NaiveDate>,
+    /// Only include demos uploaded on or before this day
+    to: Option<chrono::NaiveDate>,
+    /// Only include the positions on one side, `T` or `CT`
+    side: Option<String>,
+    /// Also include the positions of the players who were on the same team as the player in every
+    /// demo
+    #[serde(default)]
+    team: bool,
+}
+
+#[tracing::instrument(skip(session))]
+async fn stats(
+    session: UserSession,
+    Path(steam_id): Path<String>,
+    Query(filter): Query<StatsFilter>,
+) -> Result<axum::response::Json<common::PlayerCareer>, axum::http::StatusCode> {
+    session
+        .data()
+        .steam_id
+        .ok_or(axum::http::StatusCode::UNAUTHORIZED)?;
+
+    let numeric_id: u64 = steam_id
+        .parse()
+        .map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+
+    let player_query = crate::schema::demo_players::dsl::demo_players
+        .inner_join(
+            crate::schema::demos::table
+                .on(crate::schema::demo_players::dsl::demo_id
+                    .eq(crate::schema::demos::dsl::demo_id)),
+        )
+        .inner_join(crate::schema::demo_info::table.on(
+            crate::schema::demo_players::dsl::demo_id.eq(crate::schema::demo_info::dsl::demo_id),
+        ))
+        .select((
+            crate::models::DemoPlayer::as_select(),
+            crate::models::Demo::as_select(),
+            crate::models::DemoInfo::as_select(),
+        ))
+        .filter(crate::schema::demo_players::dsl::steam_id.eq(steam_id.clone()));
+
+    let mut db_con = crate::db_connection().await;
+
+    let (players, player_stats, teams, rounds, round_players) = db_con
+        .build_transaction()
+        .read_only()
+        .run(|connection| {
+            let steam_id = steam_id.clone();
+            Box::pin(async move {
+                let players: Vec<(
+                    crate::models::DemoPlayer,
+                    crate::models::Demo,
+                    crate::models::DemoInfo,
+                )> = player_query.load(connection).await?;
+
+                let demo_ids: Vec<String> =
+                    players.iter().map(|(p, _, _)| p.demo_id.clone()).collect();
+
+                let player_stats: Vec<crate::models::DemoPlayerStats> =
+                    crate::schema::demo_player_stats::dsl::demo_player_stats
+                        .filter(
+                            crate::schema::demo_player_stats::dsl::demo_id
+                                .eq_any(demo_ids.clone())
+                                .and(
+                                    crate::schema::demo_player_stats::dsl::steam_id
+                                        .eq(steam_id.clone()),
+                                ),
+                        )
+                        .load(connection)
+                        .await?;
+                let teams: Vec<crate::models::DemoTeam> =
+                    crate::schema::demo_teams::dsl::demo_teams
+                        .filter(crate::schema::demo_teams::dsl::demo_id.eq_any(demo_ids.clone()))
+                        .load(connection)
+                        .await?;
+                let rounds: Vec<crate::models::DemoRound> =
+                    crate::schema::demo_round::dsl::demo_round
+                        .filter(crate::schema::demo_round::dsl::demo_id.eq_any(demo_ids.clone()))
+                        .order(crate::schema::demo_round::dsl::round_number.asc())
+                        .load(connection)
+                        .await?;
+                let round_players: Vec<crate::models::DemoRoundPlayer> =
+                    crate::schema::demo_round_players::dsl::demo_round_players
+                        .filter(
+                            crate::schema::demo_round_players::dsl::demo_id
+                                .eq_any(demo_ids)
+                                .and(crate::schema::demo_round_players::dsl::steam_id.eq(steam_id)),
+                        )
+                        .load(connection)
+                        .await?;
+
+                Ok::<_, diesel::result::Error>((
+                    players,
+                    player_stats,
+                    teams,
+                    rounds,
+                    round_players,
+                ))
+            })
+        })
+        .await
+        .map_err(|e| {
+            tracing::error!("Querying DB: {:?}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    // A demo can be uploaded by several users, the first upload is used for the date filter
+    let mut demos =
+        HashMap::<String, (crate::models::DemoPlayer, chrono::NaiveDateTime, String)>::new();
+    for (player, demo, info) in players {
+        match demos.get_mut(&player.demo_id) {
+            Some(entry) => {
+                entry.1 = entry.1.min(demo.uploaded_at);
+            }
+            None => {
+                demos.insert(player.demo_id.clone(), (player, demo.uploaded_at, info.map));
+            }
+        };
+    }
+    demos.retain(|_, (_, uploaded_at, _)| in_range(filter.from, filter.to, uploaded_at));
+    if demos.is_empty() {
+        return Err(axum::http::StatusCode::NOT_FOUND);
+    }
+
+    let mut rounds_by_demo = HashMap::<String, Vec<crate::models::DemoRound>>::new();
+    for round in rounds {
+        rounds_by_demo
+            .entry(round.demo_id.clone())
+            .or_default()
+            .push(round);
+    }
+    let rounds = rounds_by_demo;
+    let mut players_by_demo = HashMap::<String, Vec<crate::models::DemoRoundPlayer>>::new();
+    for player in round_players {
+        players_by_demo
+            .entry(player.demo_id.clone())
+            .or_default()
+            .push(player);
+    }
+    let round_players = players_by_demo;
+
+    let mut total = analysis::career::PlayerStats::default();
+    let mut maps = HashMap::<String, analysis::career::PlayerStats>::new();
+    let mut sides = HashMap::<i32, analysis::career::PlayerStats>::new();
+    let mut name = (chrono::NaiveDateTime::MIN, String::new());
+    for (demo_id, (player, uploaded_at, map)) in demos.iter() {
+        if *uploaded_at >= name.0 {
+            name = (*uploaded_at, player.name.clone());
+        }
+
+        let demo_sides = analysis::career::side_stats(
+            &super::demos::analysis_rounds(
+                rounds.get(demo_id).map(|r| r.as_slice()).unwrap_or(&[]),
+                round_players
+                    .get(demo_id)
+                    .map(|p| p.as_slice())
+                    .unwrap_or(&[]),
+            )?,
+            numeric_id,
+        );
+
+        let mut stats = analysis::career::PlayerStats::default();
+        for (side, side_stats) in demo_sides.iter() {
+            stats.add(side_stats);
+            sides.entry(*side).or_default().add(side_stats);
+        }
+
+        // The scoreboard is also available for demos without the per round analysis and includes
+        // the assists, so it is preferred over the sum of the rounds. The headshot percentage and
+        // rating keep using the detailed numbers, as they depend on the rounds
+        if let Some(scoreboard) = player_stats.iter().find(|s| &s.demo_id == demo_id) {
+            stats.kills = scoreboard.kills as usize;
+            stats.deaths = scoreboard.deaths as usize;
+            stats.damage = scoreboard.damage as usize;
+            stats.assists = scoreboard.assists as usize;
+        }
+        if stats.rounds == 0 {
+            stats.rounds = player.rounds_played.unwrap_or(0) as usize;
+        }
+
+        let score = |own: bool| {
+            teams
+                .iter()
+                .filter(|t| &t.demo_id == demo_id && (t.team == player.team) == own)
+                .map(|t| t.end_score)
+                .max()
+        };
+        stats.matches = 1;
+        if let (Some(own), Some(other)) = (score(true), score(false)) {
+            stats.wins = (own > other) as usize;
+            stats.losses = (own < other) as usize;
+        }
+
+        total.add(&stats);
+        maps.entry(map.clone()).or_default().add(&stats);
+    }
+
+    let mut maps: Vec<_> = maps
+        .into_iter()
+        .map(|(map, stats)| common::MapCareer {
+            map,
+            stats: career_stats(&stats),
+        })
+        .collect();
+    maps.sort_unstable_by(|m1, m2| {
+        m2.stats
+            .matches
+            .cmp(&m1.stats.matches)
+            .then(m1.map.cmp(&m2.map))
+    });
+
+    let mut sides: Vec<_> = sides.into_iter().collect();
+    sides.sort_unstable_by_key(|(side, _)| *side);
+    let sides = sides
+        .into_iter()
+        .map(|(side, stats)| common::SideCareer {
+            side: super::demos::side_name(side).to_owned(),
+            stats: career_stats(&stats),
+        })
+        .collect();
+
+    Ok(axum::Json(common::PlayerCareer {
+        steam_id,
+        name: name.1,
+        total: career_stats(&total),
+        maps,
+        sides,
+    }))
+}
+
+fn career_stats(stats: &analysis::career::PlayerStats) -> common::CareerStats {
+    common::CareerStats {
+        matches: stats.matches,
+        wins: stats.wins,
+        losses: stats.losses,
+        rounds: stats.rounds,
+        kills: stats.kills,
+        deaths: stats.deaths,
+        assists: stats.assists,
+        damage: stats.damage,
+        kd: stats.kd(),
+        adr: stats.adr(),
+        headshot_percentage: stats.headshot_percentage(),
+        rating: stats.rating(),
+        win_rate: stats.win_rate(),
+        round_win_rate: stats.round_win_rate(),
+    }
+}
+
+/// Sums up the heatmaps of the player (or their team) across all their demos on the map, and
+/// returns it as a single PNG image that fits the minimap
+#[tracing::instrument(skip(session))]
+async fn heatmap(
+    session: UserSession,
+    Path((steam_id, map)): Path<(String, String)>,
+    Query(filter): Query<HeatmapFilter>,
+) -> Result<impl axum::response::IntoResponse, axum::http::StatusCode> {
+    session
+        .data()
+        .steam_id
+        .ok_or(axum::http::StatusCode::UNAUTHORIZED)?;
+
+    let minimap_coords = match analysis::minimap::MINIMAP_COORDINATES.get(&map) {
+        Some(c) => c,
+        None => {
+            tracing::error!("Unknown Map: {:?}", map);
+            return Err(axum::http::StatusCode::BAD_REQUEST);
+        }
+    };
+    // The heatmaps are stored with the team names used in the demos
+    let team_name = match filter.side.as_deref() {
+        None => None,
+        Some("T") => Some("TERRORIST"),
+        Some("CT") => Some("CT"),
+        Some(other) => {
+            tracing::error!("Unknown Side: {:?}", other);
+            return Err(axum::http::StatusCode::BAD_REQUEST);
+        }
+    };
+
+    let player_query = crate::schema::demo_players::dsl::demo_players
+        .inner_join(
+            crate::schema::demos::table
+                .on(crate::schema::demo_players::dsl::demo_id
+                    .eq(crate::schema::demos::dsl::demo_id)),
+        )
+        .inner_join(crate::schema::demo_info::table.on(
+            crate::schema::demo_players::dsl::demo_id.eq(crate::schema::demo_info::dsl::demo_id),
+        ))
+        .select((
+            crate::models::DemoPlayer::as_select(),
+            crate::models::Demo::as_select(),
+        ))
+        .filter(
+            crate::schema::demo_players::dsl::steam_id
+                .eq(steam_id.clone())
+                .and(crate::schema::demo_info::dsl::map.eq(map.clone())),
+        );
+
+    let mut db_con = crate::db_connection().await;
+
+    let (players, teammates, heatmaps) = db_con
+        .build_transaction()
+        .read_only()
+        .run(|connection| {
+            Box::pin(async move {
+                let players: Vec<(crate::models::DemoPlayer, crate::models::Demo)> =
+                    player_query.load(connection).await?;
+
+                let demo_ids: Vec<String> =
+                    players.iter().map(|(p, _)| p.demo_id.clone()).collect();
+
+                let teammates: Vec<crate::models::DemoPlayer> =
+                    crate::schema::demo_players::dsl::demo_players
+                        .filter(crate::schema::demo_players::dsl::demo_id.eq_any(demo_ids.clone()))
+                        .load(connection)
+                        .await?;
+                let heatmaps: Vec<crate::models::DemoPlayerHeatmap> =
+                    crate::schema::demo_heatmaps::dsl::demo_heatmaps
+                        .filter(crate::schema::demo_heatmaps::dsl::demo_id.eq_any(demo_ids))
+                        .load(connection)
+                        .await?;
+
+                Ok::<_, diesel::result::Error>((players, teammates, heatmaps))
+            })
+        })
+        .await
+        .map_err(|e| {
+            tracing::error!("Querying DB: {:?}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    // The team of the player and the first upload of every demo
+    let mut demos = HashMap::<String, (i16, chrono::NaiveDateTime)>::new();
+    for (player, demo) in players {
+        let entry = demos
+            .entry(player.demo_id)
+            .or_insert((player.team, demo.uploaded_at));
+        entry.1 = entry.1.min(demo.uploaded_at);
+    }
+    demos.retain(|_, (_, uploaded_at)| in_range(filter.from, filter.to, uploaded_at));
+
+    let included = |heatmap: &crate::models::DemoPlayerHeatmap| {
+        let team = match demos.get(&heatmap.demo_id) {
+            Some((team, _)) => *team,
+            None => return false,
+        };
+        let player = if filter.team {
+            teammates.iter().any(|p| {
+                p.demo_id == heatmap.demo_id && p.steam_id == heatmap.steam_id && p.team == team
+            })
+        } else {
+            heatmap.steam_id == steam_id
+        };
+        player && team_name.map(|t| heatmap.team == t).unwrap_or(true)
+    };
+
+    let mut total: Option<analysis::heatmap::HeatMap> = None;
+    for raw in heatmaps.iter().filter(|h| included(h)) {
+        let heatmap: analysis::heatmap::HeatMap = serde_json::from_str(&raw.data).map_err(|e| {
+            tracing::error!("Deserializing Heatmap: {:?}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        match total.as_mut() {
+            Some(total) => total.merge(&heatmap),
+            None => total = Some(heatmap),
+        };
+    }
+    let mut total = total.ok_or(axum::http::StatusCode::NOT_FOUND)?;
+    minimap_coords.fit(&mut total);
+
+    let mut buffer = std::io::Cursor::new(Vec::new());
+    total
+        .as_image()
+        .write_to(&mut buffer, image::ImageFormat::Png)
+        .map_err(|e| {
+            tracing::error!("Encoding Heatmap: {:?}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok((
+        [(axum::http::header::CONTENT_TYPE, "image/png")],
+        buffer.into_inner(),
+    ))
+}
+
+/// Whether the upload time is inside the days of the filter, both ends are included
+fn in_range(
+    from: Option<chrono::NaiveDate>,
+    to: Option<chrono::NaiveDate>,
+    uploaded_at: &chrono::NaiveDateTime,
+) -> bool {
+    let day = uploaded_at.date();
+    from.map(|from| day >= from).unwrap_or(true) && to.map(|to| day <= to).unwrap_or(true)
+}
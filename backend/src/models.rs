@@ -40,6 +40,16 @@ pub struct User {
 pub struct DemoInfo {
     pub demo_id: String,
     pub map: String,
+    pub server_name: Option<String>,
+    pub demo_type: Option<String>,
+    pub tick_rate: Option<i32>,
+    pub playback_ticks: Option<i32>,
+    pub game_mode: Option<String>,
+    pub match_start_tick: Option<i32>,
+    pub network_protocol: Option<i32>,
+    pub build_number: Option<i32>,
+    pub partial: bool,
+    pub stat_mismatches: Option<serde_json::Value>,
 }
 
 #[derive(Queryable, Selectable, Insertable, Debug)]
@@ -50,6 +60,7 @@ pub struct DemoTeam {
     pub team: i16,
     pub end_score: i16,
     pub start_name: String,
+    pub clan_name: Option<String>,
 }
 
 #[derive(Queryable, Selectable, Insertable, Debug)]
@@ -61,6 +72,9 @@ pub struct DemoPlayer {
     pub name: String,
     pub team: i16,
     pub color: i16,
+    pub join_tick: Option<i32>,
+    pub leave_tick: Option<i32>,
+    pub rounds_played: Option<i16>,
 }
 
 #[derive(Queryable, Selectable, Insertable, Debug)]
@@ -119,6 +133,24 @@ pub struct DemoRound {
     pub end_tick: i64,
     pub win_reason: String,
     pub events: serde_json::Value,
+    pub alive: Option<serde_json::Value>,
+}
+
+#[derive(Queryable, Selectable, Insertable, Debug)]
+#[diesel(table_name = crate::schema::demo_round_players)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DemoRoundPlayer {
+    pub demo_id: String,
+    pub round_number: i16,
+    pub steam_id: String,
+    pub team: i16,
+    pub kills: i16,
+    pub damage_dealt: i16,
+    pub damage_received: i16,
+    pub survived: bool,
+    pub end_health: i16,
+    pub equipment_saved: i32,
+    pub start_equipment: Option<i32>,
 }
 
 #[derive(Queryable, Selectable, Insertable, Debug)]
@@ -130,3 +162,61 @@ pub struct DemoHeadToHead {
     pub enemy: String,
     pub kills: i16,
 }
+
+#[derive(Queryable, Selectable, Insertable, Debug)]
+#[diesel(table_name = crate::schema::demo_timeline)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DemoTimeline {
+    pub demo_id: String,
+    pub events: serde_json::Value,
+}
+
+#[derive(Queryable, Selectable, Insertable, Debug)]
+#[diesel(table_name = crate::schema::demo_quality)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DemoQuality {
+    pub demo_id: String,
+    pub report: serde_json::Value,
+}
+
+#[derive(Queryable, Selectable, Insertable, Debug)]
+#[diesel(table_name = crate::schema::demo_round_replay)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DemoRoundReplay {
+    pub demo_id: String,
+    pub round_number: i16,
+    pub sample_rate: i16,
+    pub replay: serde_json::Value,
+}
+
+#[derive(Queryable, Selectable, Insertable, Debug)]
+#[diesel(table_name = crate::schema::demo_grenades)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DemoGrenades {
+    pub demo_id: String,
+    pub grenades: serde_json::Value,
+}
+
+#[derive(Queryable, Selectable, Insertable, Debug)]
+#[diesel(table_name = crate::schema::demo_hitgroups)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DemoHitgroups {
+    pub demo_id: String,
+    pub players: serde_json::Value,
+}
+
+#[derive(Queryable, Selectable, Insertable, Debug)]
+#[diesel(table_name = crate::schema::demo_setups)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DemoSetups {
+    pub demo_id: String,
+    pub sides: serde_json::Value,
+}
+
+#[derive(Queryable, Selectable, Insertable, Debug)]
+#[diesel(table_name = crate::schema::demo_post_plants)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DemoPostPlants {
+    pub demo_id: String,
+    pub plants: serde_json::Value,
+}
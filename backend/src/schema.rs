@@ -8,6 +8,13 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    demo_grenades (demo_id) {
+        demo_id -> Text,
+        grenades -> Json,
+    }
+}
+
 diesel::table! {
     demo_head_to_head (demo_id, player, enemy) {
         demo_id -> Text,
@@ -26,10 +33,27 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    demo_hitgroups (demo_id) {
+        demo_id -> Text,
+        players -> Json,
+    }
+}
+
 diesel::table! {
     demo_info (demo_id) {
         demo_id -> Text,
         map -> Text,
+        server_name -> Nullable<Text>,
+        demo_type -> Nullable<Text>,
+        tick_rate -> Nullable<Int4>,
+        playback_ticks -> Nullable<Int4>,
+        game_mode -> Nullable<Text>,
+        match_start_tick -> Nullable<Int4>,
+        network_protocol -> Nullable<Int4>,
+        build_number -> Nullable<Int4>,
+        partial -> Bool,
+        stat_mismatches -> Nullable<Json>,
     }
 }
 
@@ -51,6 +75,23 @@ diesel::table! {
         name -> Text,
         team -> Int2,
         color -> Int2,
+        join_tick -> Nullable<Int4>,
+        leave_tick -> Nullable<Int4>,
+        rounds_played -> Nullable<Int2>,
+    }
+}
+
+diesel::table! {
+    demo_post_plants (demo_id) {
+        demo_id -> Text,
+        plants -> Json,
+    }
+}
+
+diesel::table! {
+    demo_quality (demo_id) {
+        demo_id -> Text,
+        report -> Json,
     }
 }
 
@@ -62,6 +103,39 @@ diesel::table! {
         end_tick -> Int8,
         win_reason -> Text,
         events -> Json,
+        alive -> Nullable<Json>,
+    }
+}
+
+diesel::table! {
+    demo_round_players (demo_id, round_number, steam_id) {
+        demo_id -> Text,
+        round_number -> Int2,
+        steam_id -> Text,
+        team -> Int2,
+        kills -> Int2,
+        damage_dealt -> Int2,
+        damage_received -> Int2,
+        survived -> Bool,
+        end_health -> Int2,
+        equipment_saved -> Int4,
+        start_equipment -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    demo_round_replay (demo_id, round_number) {
+        demo_id -> Text,
+        round_number -> Int2,
+        sample_rate -> Int2,
+        replay -> Json,
+    }
+}
+
+diesel::table! {
+    demo_setups (demo_id) {
+        demo_id -> Text,
+        sides -> Json,
     }
 }
 
@@ -71,6 +145,14 @@ diesel::table! {
         team -> Int2,
         end_score -> Int2,
         start_name -> Text,
+        clan_name -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    demo_timeline (demo_id) {
+        demo_id -> Text,
+        events -> Json,
     }
 }
 
@@ -106,13 +188,21 @@ diesel::table! {
 
 diesel::allow_tables_to_appear_in_same_query!(
     analysis_queue,
+    demo_grenades,
     demo_head_to_head,
     demo_heatmaps,
+    demo_hitgroups,
     demo_info,
     demo_player_stats,
     demo_players,
+    demo_post_plants,
+    demo_quality,
     demo_round,
+    demo_round_players,
+    demo_round_replay,
+    demo_setups,
     demo_teams,
+    demo_timeline,
     demos,
     processing_status,
     sessions,
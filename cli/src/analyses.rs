@@ -0,0 +1,909 @@
+use crate::output::{Output, Table};
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CliAnalysis {
+    Endofgame,
+    Perround,
+    Heatmap,
+    HeadToHead,
+    Metadata,
+    Timeline,
+    Players,
+    Quality,
+    Replay,
+    Grenades,
+    Hitgroups,
+    Setups,
+    Movement,
+    Postplant,
+}
+
+impl CliAnalysis {
+    pub const ALL: &'static [Self] = &[
+        Self::Endofgame,
+        Self::Perround,
+        Self::Heatmap,
+        Self::HeadToHead,
+        Self::Metadata,
+        Self::Timeline,
+        Self::Players,
+        Self::Quality,
+        Self::Replay,
+        Self::Grenades,
+        Self::Hitgroups,
+        Self::Setups,
+        Self::Movement,
+        Self::Postplant,
+    ];
+
+    /// The name used for the output files
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Endofgame => "endofgame",
+            Self::Perround => "perround",
+            Self::Heatmap => "heatmap",
+            Self::HeadToHead => "head_to_head",
+            Self::Metadata => "metadata",
+            Self::Timeline => "timeline",
+            Self::Players => "players",
+            Self::Quality => "quality",
+            Self::Replay => "replay",
+            Self::Grenades => "grenades",
+            Self::Hitgroups => "hitgroups",
+            Self::Setups => "setups",
+            Self::Movement => "movement",
+            Self::Postplant => "postplant",
+        }
+    }
+
+    pub fn run(&self, data: &[u8]) -> Result<Output, ()> {
+        match self {
+            Self::Endofgame => endofgame(data),
+            Self::Perround => perround(data),
+            Self::Heatmap => heatmap(data),
+            Self::HeadToHead => head_to_head(data),
+            Self::Metadata => metadata(data),
+            Self::Timeline => timeline(data),
+            Self::Players => players(data),
+            Self::Quality => quality(data),
+            Self::Replay => replay(data),
+            Self::Grenades => grenades(data),
+            Self::Hitgroups => hitgroups(data),
+            Self::Setups => setups(data),
+            Self::Movement => movement(data),
+            Self::Postplant => postplant(data),
+        }
+    }
+}
+
+fn endofgame(data: &[u8]) -> Result<Output, ()> {
+    let result = analysis::endofgame::parse(data)?;
+
+    let mut teams: Vec<_> = result.teams.iter().collect();
+    teams.sort_unstable_by_key(|(number, _)| **number);
+
+    Ok(Output {
+        json: serde_json::to_value(&result).map_err(|e| ())?,
+        tables: vec![
+            Table {
+                name: "teams",
+                header: &["team", "clan_name", "start_side", "end_score"],
+                rows: teams
+                    .iter()
+                    .map(|(number, team)| {
+                        vec![
+                            number.to_string(),
+                            team.clan_name.clone().unwrap_or_default(),
+                            team.start_side.clone(),
+                            team.end_score.to_string(),
+                        ]
+                    })
+                    .collect(),
+            },
+            Table {
+                name: "players",
+                header: &[
+                    "name",
+                    "steam_id",
+                    "team",
+                    "kills",
+                    "deaths",
+                    "assists",
+                    "damage",
+                    "team_kills",
+                    "team_damage",
+                    "self_damage",
+                ],
+                rows: result
+                    .players
+                    .iter()
+                    .map(|(info, stats)| {
+                        vec![
+                            info.name.clone(),
+                            info.steam_id.clone(),
+                            info.team.to_string(),
+                            stats.kills.to_string(),
+                            stats.deaths.to_string(),
+                            stats.assists.to_string(),
+                            stats.damage.to_string(),
+                            stats.team_kills.to_string(),
+                            stats.team_damage.to_string(),
+                            stats.self_damage.to_string(),
+                        ]
+                    })
+                    .collect(),
+            },
+            Table {
+                name: "stat_mismatches",
+                header: &["steam_id", "stat", "events", "controller"],
+                rows: result
+                    .stat_mismatches
+                    .iter()
+                    .map(|mismatch| {
+                        vec![
+                            mismatch.steam_id.to_string(),
+                            mismatch.stat.clone(),
+                            mismatch.events.to_string(),
+                            mismatch.controller.to_string(),
+                        ]
+                    })
+                    .collect(),
+            },
+        ],
+        images: Vec::new(),
+    })
+}
+
+fn perround(data: &[u8]) -> Result<Output, ()> {
+    let result = analysis::perround::parse(data)?;
+    let types = analysis::roundtypes::classify(&result.rounds);
+    let summary = analysis::roundtypes::team_summary(&result.rounds, &types);
+    let advantages = analysis::advantage::advantage_stats(&result.rounds);
+    let win_probability = analysis::winprob::evaluate(&result.rounds);
+
+    Ok(Output {
+        json: serde_json::to_value(&result).map_err(|e| ())?,
+        tables: vec![
+            Table {
+                name: "rounds",
+                header: &["round", "start", "end", "winreason"],
+                rows: result
+                    .rounds
+                    .iter()
+                    .enumerate()
+                    .map(|(number, round)| {
+                        vec![
+                            number.to_string(),
+                            round.start.to_string(),
+                            round.end.to_string(),
+                            format!("{:?}", round.winreason),
+                        ]
+                    })
+                    .collect(),
+            },
+            Table {
+                name: "players",
+                header: &[
+                    "round",
+                    "steam_id",
+                    "team",
+                    "kills",
+                    "damage_dealt",
+                    "damage_received",
+                    "survived",
+                    "end_health",
+                    "equipment_saved",
+                    "start_equipment",
+                ],
+                rows: result
+                    .rounds
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(number, round)| {
+                        round.players.iter().map(move |player| {
+                            vec![
+                                number.to_string(),
+                                player.steam_id.to_string(),
+                                player.team.to_string(),
+                                player.kills.to_string(),
+                                player.damage_dealt.to_string(),
+                                player.damage_received.to_string(),
+                                player.survived.to_string(),
+                                player.end_health.to_string(),
+                                player.equipment_saved.to_string(),
+                                player.start_equipment.to_string(),
+                            ]
+                        })
+                    })
+                    .collect(),
+            },
+            Table {
+                name: "callouts",
+                header: &["steam_id", "place", "kills", "deaths"],
+                rows: analysis::places::callout_stats(result.rounds.iter().flat_map(|r| &r.events))
+                    .into_iter()
+                    .map(|stats| {
+                        vec![
+                            stats.steam_id.to_string(),
+                            stats.place,
+                            stats.kills.to_string(),
+                            stats.deaths.to_string(),
+                        ]
+                    })
+                    .collect(),
+            },
+            Table {
+                name: "weapons",
+                header: &[
+                    "weapon",
+                    "kills",
+                    "headshots",
+                    "noscopes",
+                    "wallbangs",
+                    "smoke_kills",
+                    "blind_kills",
+                    "ranges",
+                ],
+                rows: analysis::kills::weapon_stats(result.rounds.iter().flat_map(|r| &r.events))
+                    .into_iter()
+                    .map(|stats| {
+                        let ranges = stats
+                            .ranges
+                            .iter()
+                            .enumerate()
+                            .map(|(idx, kills)| {
+                                format!("{}:{}", analysis::kills::range_name(idx), kills)
+                            })
+                            .collect::<Vec<_>>();
+
+                        vec![
+                            stats.weapon,
+                            stats.kills.to_string(),
+                            stats.headshots.to_string(),
+                            stats.noscopes.to_string(),
+                            stats.wallbangs.to_string(),
+                            stats.smoke_kills.to_string(),
+                            stats.blind_kills.to_string(),
+                            ranges.join(" "),
+                        ]
+                    })
+                    .collect(),
+            },
+            Table {
+                name: "roundtypes",
+                header: &[
+                    "round",
+                    "team",
+                    "side",
+                    "buy",
+                    "equipment_value",
+                    "won",
+                    "post_plant",
+                ],
+                rows: types
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(number, round)| {
+                        round.teams.iter().map(move |team| {
+                            vec![
+                                number.to_string(),
+                                team.team.to_string(),
+                                team.side.to_string(),
+                                format!("{:?}", team.buy),
+                                team.equipment_value.to_string(),
+                                team.won.to_string(),
+                                round.post_plant.to_string(),
+                            ]
+                        })
+                    })
+                    .collect(),
+            },
+            Table {
+                name: "conversions",
+                header: &[
+                    "team",
+                    "pistol_rounds",
+                    "pistol_wins",
+                    "pistol_conversions",
+                    "force_rounds",
+                    "force_wins",
+                    "advantage_rounds",
+                    "advantage_wins",
+                ],
+                rows: summary
+                    .iter()
+                    .map(|team| {
+                        vec![
+                            team.team.to_string(),
+                            team.pistol_rounds.to_string(),
+                            team.pistol_wins.to_string(),
+                            team.pistol_conversions.to_string(),
+                            team.force_rounds.to_string(),
+                            team.force_wins.to_string(),
+                            team.advantage_rounds.to_string(),
+                            team.advantage_wins.to_string(),
+                        ]
+                    })
+                    .collect(),
+            },
+            Table {
+                name: "advantages",
+                header: &["team", "situation", "rounds", "won", "lost"],
+                rows: advantages
+                    .iter()
+                    .map(|stats| {
+                        vec![
+                            stats.team.to_string(),
+                            format!("{}v{}", stats.players, stats.enemies),
+                            stats.rounds.to_string(),
+                            stats.won.to_string(),
+                            stats.lost.to_string(),
+                        ]
+                    })
+                    .collect(),
+            },
+            Table {
+                name: "impact",
+                header: &["steam_id", "kills", "impact"],
+                rows: win_probability
+                    .players
+                    .iter()
+                    .map(|player| {
+                        vec![
+                            player.steam_id.to_string(),
+                            player.kills.to_string(),
+                            format!("{:.2}", player.impact),
+                        ]
+                    })
+                    .collect(),
+            },
+        ],
+        images: Vec::new(),
+    })
+}
+
+fn heatmap(data: &[u8]) -> Result<Output, ()> {
+    let config = analysis::heatmap::Config { cell_size: 5.0 };
+    let result = analysis::heatmap::parse(&config, data)?;
+
+    Ok(Output {
+        json: serde_json::to_value(&result).map_err(|e| ())?,
+        tables: vec![Table {
+            name: "cells",
+            header: &["steam_id", "team", "x", "y", "count"],
+            rows: result
+                .player_heatmaps
+                .iter()
+                .flat_map(|player| {
+                    player.heatmap.cells().map(move |((x, y), count)| {
+                        vec![
+                            player.steam_id.to_string(),
+                            player.team.clone(),
+                            x.to_string(),
+                            y.to_string(),
+                            count.to_string(),
+                        ]
+                    })
+                })
+                .collect(),
+        }],
+        images: result
+            .player_heatmaps
+            .iter()
+            .map(|player| {
+                (
+                    format!("{}_{}", player.steam_id, player.team),
+                    player.heatmap.as_image(),
+                )
+            })
+            .collect(),
+    })
+}
+
+fn head_to_head(data: &[u8]) -> Result<Output, ()> {
+    let result = analysis::head_to_head::parse(data)?;
+
+    let kills: std::collections::BTreeMap<_, _> = result
+        .head_to_head
+        .iter()
+        .flat_map(|(player, enemies)| {
+            enemies
+                .iter()
+                .map(move |(enemy, count)| ((*player, *enemy), *count))
+        })
+        .collect();
+    let name = |steam_id: &u64| {
+        result
+            .players
+            .get(steam_id)
+            .map(|p| p.name.clone())
+            .unwrap_or_default()
+    };
+
+    Ok(Output {
+        json: serde_json::to_value(&result).map_err(|e| ())?,
+        tables: vec![Table {
+            name: "kills",
+            header: &["player", "player_name", "enemy", "enemy_name", "kills"],
+            rows: kills
+                .iter()
+                .map(|((player, enemy), count)| {
+                    vec![
+                        player.to_string(),
+                        name(player),
+                        enemy.to_string(),
+                        name(enemy),
+                        count.to_string(),
+                    ]
+                })
+                .collect(),
+        }],
+        images: Vec::new(),
+    })
+}
+
+fn metadata(data: &[u8]) -> Result<Output, ()> {
+    let result = analysis::metadata::parse(data)?;
+
+    Ok(Output {
+        json: serde_json::to_value(&result).map_err(|e| ())?,
+        tables: vec![Table {
+            name: "metadata",
+            header: &[
+                "map",
+                "server_name",
+                "demo_type",
+                "tick_rate",
+                "playback_ticks",
+                "game_mode",
+                "match_start_tick",
+                "network_protocol",
+                "build_number",
+            ],
+            rows: vec![vec![
+                result.map.clone(),
+                result.server_name.clone(),
+                format!("{:?}", result.demo_type),
+                result.tick_rate.to_string(),
+                result.playback_ticks.to_string(),
+                format!("{:?}", result.game_mode),
+                result
+                    .match_start_tick
+                    .map(|t| t.to_string())
+                    .unwrap_or_default(),
+                result.network_protocol.to_string(),
+                result.build_number.to_string(),
+            ]],
+        }],
+        images: Vec::new(),
+    })
+}
+
+fn timeline(data: &[u8]) -> Result<Output, ()> {
+    let result = analysis::timeline::parse(data)?;
+
+    Ok(Output {
+        json: serde_json::to_value(&result).map_err(|e| ())?,
+        tables: vec![Table {
+            name: "entries",
+            header: &["tick", "round", "event"],
+            rows: result
+                .entries
+                .iter()
+                .map(|entry| {
+                    vec![
+                        entry.tick.to_string(),
+                        entry.round.map(|r| r.to_string()).unwrap_or_default(),
+                        serde_json::to_string(&entry.event).unwrap_or_default(),
+                    ]
+                })
+                .collect(),
+        }],
+        images: Vec::new(),
+    })
+}
+
+fn players(data: &[u8]) -> Result<Output, ()> {
+    let result = analysis::players::parse(data)?;
+
+    Ok(Output {
+        json: serde_json::to_value(&result).map_err(|e| ())?,
+        tables: vec![Table {
+            name: "players",
+            header: &[
+                "steam_id",
+                "name",
+                "user_ids",
+                "join_tick",
+                "leave_tick",
+                "rounds_played",
+            ],
+            rows: result
+                .players
+                .iter()
+                .map(|player| {
+                    vec![
+                        player.steam_id.to_string(),
+                        player.name.clone(),
+                        player
+                            .user_ids
+                            .iter()
+                            .map(|id| id.to_string())
+                            .collect::<Vec<_>>()
+                            .join(";"),
+                        player.join_tick.to_string(),
+                        player.leave_tick.map(|t| t.to_string()).unwrap_or_default(),
+                        player.rounds_played.to_string(),
+                    ]
+                })
+                .collect(),
+        }],
+        images: Vec::new(),
+    })
+}
+
+fn quality(data: &[u8]) -> Result<Output, ()> {
+    let result = analysis::quality::parse(data)?;
+
+    let failures = |kind: &str, failed: &std::collections::BTreeMap<String, usize>| {
+        failed
+            .iter()
+            .map(|(error, count)| vec![kind.to_owned(), error.clone(), count.to_string()])
+            .collect::<Vec<_>>()
+    };
+
+    Ok(Output {
+        json: serde_json::to_value(&result).map_err(|e| ())?,
+        tables: vec![Table {
+            name: "failures",
+            header: &["kind", "error", "count"],
+            rows: failures("event", &result.failed_events)
+                .into_iter()
+                .chain(failures("entity", &result.failed_entities))
+                .collect(),
+        }],
+        images: Vec::new(),
+    })
+}
+
+fn replay(data: &[u8]) -> Result<Output, ()> {
+    let config = analysis::replay::Config::default();
+    let result = analysis::replay::parse(&config, data)?;
+
+    Ok(Output {
+        json: serde_json::to_value(&result).map_err(|e| ())?,
+        tables: vec![Table {
+            name: "samples",
+            header: &[
+                "round", "tick", "steam_id", "team", "x", "y", "z", "yaw", "health", "armor",
+                "weapon", "alive",
+            ],
+            rows: result
+                .rounds
+                .iter()
+                .enumerate()
+                .flat_map(|(number, round)| {
+                    round.frames.iter().flat_map(move |frame| {
+                        frame
+                            .players
+                            .iter()
+                            .zip(round.players.iter())
+                            .filter_map(|(sample, steam_id)| Some((sample.as_ref()?, steam_id)))
+                            .map(move |(sample, steam_id)| {
+                                vec![
+                                    number.to_string(),
+                                    frame.tick.to_string(),
+                                    steam_id.to_string(),
+                                    sample.team.to_string(),
+                                    sample.position.0.to_string(),
+                                    sample.position.1.to_string(),
+                                    sample.position.2.to_string(),
+                                    sample.yaw.to_string(),
+                                    sample.health.to_string(),
+                                    sample.armor.to_string(),
+                                    sample.weapon.clone().unwrap_or_default(),
+                                    sample.alive.to_string(),
+                                ]
+                            })
+                    })
+                })
+                .collect(),
+        }],
+        images: Vec::new(),
+    })
+}
+
+fn grenades(data: &[u8]) -> Result<Output, ()> {
+    let result = analysis::grenades::parse(data)?;
+
+    Ok(Output {
+        json: serde_json::to_value(&result).map_err(|e| ())?,
+        tables: vec![Table {
+            name: "grenades",
+            header: &[
+                "round",
+                "kind",
+                "thrower",
+                "team",
+                "throw_tick",
+                "throw_x",
+                "throw_y",
+                "throw_z",
+                "land_tick",
+                "land_x",
+                "land_y",
+                "land_z",
+            ],
+            rows: result
+                .grenades
+                .iter()
+                .map(|grenade| {
+                    vec![
+                        grenade.round.to_string(),
+                        grenade.kind.clone(),
+                        grenade.thrower.map(|t| t.to_string()).unwrap_or_default(),
+                        grenade.team.to_string(),
+                        grenade.throw_tick.to_string(),
+                        grenade.throw_position.0.to_string(),
+                        grenade.throw_position.1.to_string(),
+                        grenade.throw_position.2.to_string(),
+                        grenade.land_tick.to_string(),
+                        grenade.land_position.0.to_string(),
+                        grenade.land_position.1.to_string(),
+                        grenade.land_position.2.to_string(),
+                    ]
+                })
+                .collect(),
+        }],
+        images: Vec::new(),
+    })
+}
+
+fn hitgroups(data: &[u8]) -> Result<Output, ()> {
+    let result = analysis::hitgroups::parse(data)?;
+
+    let rows = |received: bool| -> Vec<Vec<String>> {
+        result
+            .players
+            .iter()
+            .flat_map(move |player| {
+                let hits = if received {
+                    &player.received
+                } else {
+                    &player.dealt
+                };
+                hits.iter().map(move |hit| {
+                    vec![
+                        player.steam_id.to_string(),
+                        format!("{:?}", hit.hitgroup),
+                        hit.weapon.clone(),
+                        hit.hits.to_string(),
+                        hit.damage.to_string(),
+                        hit.armor_damage.to_string(),
+                    ]
+                })
+            })
+            .collect()
+    };
+
+    Ok(Output {
+        json: serde_json::to_value(&result).map_err(|e| ())?,
+        tables: vec![
+            Table {
+                name: "dealt",
+                header: &[
+                    "steam_id",
+                    "hitgroup",
+                    "weapon",
+                    "hits",
+                    "damage",
+                    "armor_damage",
+                ],
+                rows: rows(false),
+            },
+            Table {
+                name: "received",
+                header: &[
+                    "steam_id",
+                    "hitgroup",
+                    "weapon",
+                    "hits",
+                    "damage",
+                    "armor_damage",
+                ],
+                rows: rows(true),
+            },
+        ],
+        images: Vec::new(),
+    })
+}
+
+fn setups(data: &[u8]) -> Result<Output, ()> {
+    let result = analysis::setups::parse(&analysis::setups::Config::default(), data)?;
+
+    let mut teams: Vec<i32> = result.sides.iter().map(|s| s.team).collect();
+    teams.sort_unstable();
+    teams.dedup();
+
+    Ok(Output {
+        json: serde_json::to_value(&result).map_err(|e| ())?,
+        tables: vec![
+            Table {
+                name: "sides",
+                header: &["round", "team", "side", "won", "places"],
+                rows: result
+                    .sides
+                    .iter()
+                    .map(|side| {
+                        vec![
+                            side.round.to_string(),
+                            side.team.to_string(),
+                            side.side.to_string(),
+                            side.won.to_string(),
+                            side.places.join(" "),
+                        ]
+                    })
+                    .collect(),
+            },
+            Table {
+                name: "setups",
+                header: &["team", "side", "rounds", "wins", "similar", "places"],
+                rows: teams
+                    .iter()
+                    .flat_map(|team| {
+                        analysis::setups::setup_stats(
+                            result.sides.iter().filter(|s| s.team == *team),
+                        )
+                        .into_iter()
+                        .map(move |stats| {
+                            vec![
+                                team.to_string(),
+                                stats.side.to_string(),
+                                stats.rounds.to_string(),
+                                stats.wins.to_string(),
+                                stats.similar.to_string(),
+                                stats.places.join(" "),
+                            ]
+                        })
+                    })
+                    .collect(),
+            },
+        ],
+        images: Vec::new(),
+    })
+}
+
+fn movement(data: &[u8]) -> Result<Output, ()> {
+    let config = analysis::replay::Config::default();
+    let result = analysis::replay::parse(&config, data)?;
+
+    let rounds: Vec<_> = result
+        .rounds
+        .iter()
+        .map(|r| analysis::movement::round_movement(r, result.tick_rate))
+        .collect();
+    let total = analysis::movement::total_movement(rounds.iter().map(|r| r.as_slice()));
+
+    let header: &'static [&'static str] = &[
+        "round",
+        "steam_id",
+        "distance",
+        "average_speed",
+        "still",
+        "crouched",
+        "walking",
+        "running",
+        "top_place",
+    ];
+    let row = |round: String, movement: &analysis::movement::PlayerMovement| {
+        vec![
+            round,
+            movement.steam_id.to_string(),
+            movement.distance.to_string(),
+            movement.average_speed().to_string(),
+            movement.still.to_string(),
+            movement.crouched.to_string(),
+            movement.walking.to_string(),
+            movement.running.to_string(),
+            movement
+                .places
+                .first()
+                .map(|(place, _)| place.clone())
+                .unwrap_or_default(),
+        ]
+    };
+
+    Ok(Output {
+        json: serde_json::to_value(&rounds).map_err(|e| ())?,
+        tables: vec![
+            Table {
+                name: "rounds",
+                header,
+                rows: rounds
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(number, players)| {
+                        players.iter().map(move |p| row(number.to_string(), p))
+                    })
+                    .collect(),
+            },
+            Table {
+                name: "total",
+                header,
+                rows: total.iter().map(|p| row(String::new(), p)).collect(),
+            },
+        ],
+        images: Vec::new(),
+    })
+}
+
+fn postplant(data: &[u8]) -> Result<Output, ()> {
+    let result = analysis::postplant::parse(data)?;
+    let stats = analysis::postplant::post_plant_stats(result.plants.iter());
+
+    Ok(Output {
+        json: serde_json::to_value(&result).map_err(|e| ())?,
+        tables: vec![
+            Table {
+                name: "plants",
+                header: &[
+                    "round",
+                    "site",
+                    "t_team",
+                    "ct_team",
+                    "t_alive",
+                    "ct_alive",
+                    "time_remaining",
+                    "outcome",
+                ],
+                rows: result
+                    .plants
+                    .iter()
+                    .map(|plant| {
+                        vec![
+                            plant.round.to_string(),
+                            plant.site.clone().unwrap_or_default(),
+                            plant.t_team.to_string(),
+                            plant.ct_team.to_string(),
+                            plant.t_alive.to_string(),
+                            plant.ct_alive.to_string(),
+                            plant.time_remaining.to_string(),
+                            format!("{:?}", plant.outcome),
+                        ]
+                    })
+                    .collect(),
+            },
+            Table {
+                name: "stats",
+                header: &[
+                    "site",
+                    "t_alive",
+                    "ct_alive",
+                    "rounds",
+                    "defused",
+                    "exploded",
+                    "eliminated",
+                ],
+                rows: stats
+                    .iter()
+                    .map(|s| {
+                        let (t_alive, ct_alive) = s
+                            .players
+                            .map(|(t, ct)| (t.to_string(), ct.to_string()))
+                            .unwrap_or_default();
+                        vec![
+                            s.site.clone().unwrap_or_default(),
+                            t_alive,
+                            ct_alive,
+                            s.rounds.to_string(),
+                            s.defused.to_string(),
+                            s.exploded.to_string(),
+                            s.eliminated.to_string(),
+                        ]
+                    })
+                    .collect(),
+            },
+        ],
+        images: Vec::new(),
+    })
+}
@@ -0,0 +1,98 @@
+use tracing_subscriber::prelude::__tracing_subscriber_SubscriberExt;
+
+mod analyses;
+mod output;
+
+/// Runs the demo analyses on local demo files, without needing a database or the web server
+#[derive(clap::Parser)]
+#[clap(name = "knifer-cli", version)]
+struct CliArgs {
+    /// The demo files to analyse
+    #[clap(required = true)]
+    demos: Vec<std::path::PathBuf>,
+
+    /// The analyses to run, can be given multiple times. Runs all analyses if not given
+    #[clap(long = "analysis", short = 'a')]
+    analyses: Vec<analyses::CliAnalysis>,
+
+    /// The format of the results, heatmaps are the only results that can be rendered as png
+    #[clap(long = "format", short = 'f', default_value = "json")]
+    format: output::Format,
+
+    /// The folder in which the results are stored, each demo gets its own sub folder
+    #[clap(long = "output", short = 'o', default_value = "analysis/")]
+    output: std::path::PathBuf,
+}
+
+fn main() -> std::process::ExitCode {
+    use clap::Parser;
+
+    let registry = tracing_subscriber::Registry::default()
+        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
+        .with(tracing_subscriber::filter::filter_fn(|meta| {
+            meta.target().contains("knifer_cli") && *meta.level() <= tracing::Level::INFO
+        }));
+    tracing::subscriber::set_global_default(registry).unwrap();
+
+    let args = CliArgs::parse();
+
+    let selected = if args.analyses.is_empty() {
+        analyses::CliAnalysis::ALL.to_vec()
+    } else {
+        args.analyses.clone()
+    };
+
+    let mut failed = false;
+    for demo in args.demos.iter() {
+        if let Err(e) = analyse_demo(demo, &selected, &args) {
+            tracing::error!("Analysing {:?}: {}", demo, e);
+            failed = true;
+        }
+    }
+
+    if failed {
+        std::process::ExitCode::FAILURE
+    } else {
+        std::process::ExitCode::SUCCESS
+    }
+}
+
+#[tracing::instrument(skip(selected, args))]
+fn analyse_demo(
+    demo: &std::path::Path,
+    selected: &[analyses::CliAnalysis],
+    args: &CliArgs,
+) -> Result<(), String> {
+    let data = std::fs::read(demo).map_err(|e| format!("Reading demo: {:?}", e))?;
+
+    let demo_name = demo
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .ok_or_else(|| "Demo path has no file name".to_owned())?;
+    let output_dir = args.output.join(demo_name);
+    std::fs::create_dir_all(&output_dir)
+        .map_err(|e| format!("Creating output folder {:?}: {:?}", output_dir, e))?;
+
+    let mut failed = Vec::new();
+    for analysis in selected {
+        tracing::info!("Running {}", analysis.name());
+
+        let result = match analysis.run(&data) {
+            Ok(r) => r,
+            Err(_) => {
+                failed.push(analysis.name());
+                continue;
+            }
+        };
+
+        result.write(&output_dir, analysis.name(), args.format)?;
+    }
+
+    if !failed.is_empty() {
+        return Err(format!("Failed analyses: {}", failed.join(", ")));
+    }
+
+    tracing::info!("Stored results in {:?}", output_dir);
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+/// The result of a single analysis, in all the forms it can be written to disk
+#[derive(Debug, Default)]
+pub struct Output {
+    pub json: serde_json::Value,
+    pub tables: Vec<Table>,
+    pub images: Vec<(String, image::RgbImage)>,
+}
+
+/// A flat table that gets written as a CSV file
+#[derive(Debug)]
+pub struct Table {
+    pub name: &'static str,
+    pub header: &'static [&'static str],
+    pub rows: Vec<Vec<String>>,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Json,
+    Csv,
+    Png,
+}
+
+impl Output {
+    /// Writes the output in the given format into the directory, the files are prefixed with the
+    /// name of the analysis
+    pub fn write(&self, dir: &std::path::Path, name: &str, format: Format) -> Result<(), String> {
+        match format {
+            Format::Json => {
+                let path = dir.join(format!("{}.json", name));
+                let content =
+                    serde_json::to_string_pretty(&self.json).map_err(|e| format!("{:?}", e))?;
+                std::fs::write(&path, content).map_err(|e| format!("{:?}: {:?}", path, e))?;
+            }
+            Format::Csv => {
+                for table in self.tables.iter() {
+                    let path = dir.join(format!("{}_{}.csv", name, table.name));
+                    std::fs::write(&path, table.to_csv())
+                        .map_err(|e| format!("{:?}: {:?}", path, e))?;
+                }
+            }
+            Format::Png => {
+                if self.images.is_empty() {
+                    tracing::warn!("{} can not be rendered as an image", name);
+                    return Ok(());
+                }
+
+                let image_dir = dir.join(name);
+                std::fs::create_dir_all(&image_dir)
+                    .map_err(|e| format!("{:?}: {:?}", image_dir, e))?;
+
+                for (image_name, image) in self.images.iter() {
+                    let path = image_dir.join(format!("{}.png", image_name));
+                    image
+                        .save_with_format(&path, image::ImageFormat::Png)
+                        .map_err(|e| format!("{:?}: {:?}", path, e))?;
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+impl Table {
+    pub fn to_csv(&self) -> String {
+        let mut result = String::new();
+
+        let header = self
+            .header
+            .iter()
+            .map(|h| h.to_string())
+            .collect::<Vec<_>>();
+        for row in core::iter::once(&header).chain(self.rows.iter()) {
+            let line = row
+                .iter()
+                .map(|field| escape_field(field))
+                .collect::<Vec<_>>()
+                .join(",");
+            result.push_str(&line);
+            result.push('\n');
+        }
+
+        result
+    }
+}
+
+fn escape_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_escaping() {
+        let table = Table {
+            name: "test",
+            header: &["name", "message"],
+            rows: vec![
+                vec!["first".to_owned(), "plain".to_owned()],
+                vec!["second".to_owned(), "with, comma".to_owned()],
+                vec!["third".to_owned(), "with \"quotes\"".to_owned()],
+            ],
+        };
+
+        assert_eq!(
+            "name,message\nfirst,plain\nsecond,\"with, comma\"\nthird,\"with \"\"quotes\"\"\"\n",
+            table.to_csv()
+        );
+    }
+}
@@ -8,6 +8,16 @@ pub struct ScoreBoardTeam {
     pub number: u32,
     pub score: i16,
     pub players: Vec<ScoreBoardPlayer>,
+    /// The real name of the team, only known for league and tournament demos
+    #[serde(default)]
+    pub clan_name: Option<String>,
+}
+
+impl ScoreBoardTeam {
+    /// The real name of the team if it is known, `Team <number>` otherwise
+    pub fn display_name(&self) -> String {
+        team_name(self.number, self.clan_name.as_deref())
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -17,6 +27,16 @@ pub struct ScoreBoardPlayer {
     pub deaths: usize,
     pub damage: usize,
     pub assists: usize,
+    /// `None` for demos that were analysed before rounds played were tracked
+    #[serde(default)]
+    pub rounds_played: Option<usize>,
+    /// `None` if the kills of the demo are not known in detail
+    #[serde(default)]
+    pub wallbangs: Option<usize>,
+    #[serde(default)]
+    pub smoke_kills: Option<usize>,
+    #[serde(default)]
+    pub blind_kills: Option<usize>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -30,6 +50,30 @@ pub struct PlayerHeatmap {
 pub struct PerRoundResult {
     pub teams: Vec<PerRoundTeam>,
     pub rounds: Vec<DemoRound>,
+    /// How often the teams converted or threw a man advantage, ordered by team and situation
+    #[serde(default)]
+    pub advantages: Vec<TeamAdvantage>,
+    /// The win probability at the start of every round, after every event and at its end
+    #[serde(default)]
+    pub momentum: Vec<MomentumPoint>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct MomentumPoint {
+    /// Starting at 0
+    pub round: usize,
+    /// The chance of the team that started on CT winning the round, in percent
+    pub starting_ct_win: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TeamAdvantage {
+    pub team: u32,
+    /// The alive players of the team and of the enemy, like `5v4`
+    pub situation: String,
+    pub rounds: usize,
+    pub won: usize,
+    pub lost: usize,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -37,12 +81,57 @@ pub struct PerRoundTeam {
     pub name: String,
     pub number: u32,
     pub players: std::collections::HashSet<String>,
+    /// The real name of the team, only known for league and tournament demos
+    #[serde(default)]
+    pub clan_name: Option<String>,
+}
+
+impl PerRoundTeam {
+    /// The real name of the team if it is known, `Team <number>` otherwise
+    pub fn display_name(&self) -> String {
+        team_name(self.number, self.clan_name.as_deref())
+    }
+}
+
+fn team_name(number: u32, clan_name: Option<&str>) -> String {
+    match clan_name {
+        Some(name) => name.to_owned(),
+        None => format!("Team {}", number),
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct DemoRound {
     pub reason: RoundWinReason,
     pub events: Vec<RoundEvent>,
+    #[serde(default)]
+    pub players: Vec<RoundPlayer>,
+    /// The alive players of each side, at the start of the round and after every death
+    #[serde(default)]
+    pub alive: Vec<AlivePlayers>,
+    /// The length of the round in ticks
+    #[serde(default)]
+    pub length: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct AlivePlayers {
+    /// The ticks since the start of the round
+    pub tick: u32,
+    pub t: usize,
+    pub ct: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RoundPlayer {
+    pub name: String,
+    pub team: u32,
+    pub kills: usize,
+    pub damage_dealt: usize,
+    pub damage_received: usize,
+    pub survived: bool,
+    pub end_health: u32,
+    pub equipment_saved: u32,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -72,7 +161,10 @@ pub enum RoundWinReason {
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum RoundEvent {
-    BombPlanted,
+    BombPlanted {
+        /// The callout the bomb was planted at
+        place: Option<String>,
+    },
     BombDefused,
     Killed {
         attacker: String,
@@ -80,12 +172,360 @@ pub enum RoundEvent {
         weapon: Option<String>,
         noscope: bool,
         headshot: bool,
+        attacker_place: Option<String>,
+        died_place: Option<String>,
+        penetrated: bool,
+        thrusmoke: bool,
+        attackerblind: bool,
+        /// In game units
+        distance: Option<u32>,
+        /// The change in the win probability of the attacker's side, in percentage points
+        impact: Option<i32>,
     },
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct WeaponStats {
+    pub weapon: String,
+    pub kills: usize,
+    pub headshots: usize,
+    pub noscopes: usize,
+    pub wallbangs: usize,
+    pub smoke_kills: usize,
+    pub blind_kills: usize,
+    /// The number of kills per engagement range, as `(range, kills)` like `("500-1000", 3)`
+    pub ranges: Vec<(String, usize)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PlayerHitgroups {
+    pub name: String,
+    pub dealt: Vec<HitgroupDamage>,
+    pub received: Vec<HitgroupDamage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct HitgroupDamage {
+    /// `Head`, `Chest`, `Stomach`, `LeftArm`, `RightArm`, `LeftLeg`, `RightLeg`, `Neck`, `Gear` or
+    /// `Generic`
+    pub hitgroup: String,
+    pub weapon: String,
+    pub hits: usize,
+    pub damage: usize,
+    pub armor_damage: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RoundTypes {
+    pub rounds: Vec<RoundTypeEntry>,
+    pub teams: Vec<TeamConversions>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RoundTypeEntry {
+    pub teams: Vec<TeamBuy>,
+    pub post_plant: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TeamBuy {
+    pub team: u32,
+    /// `T` or `CT`
+    pub side: String,
+    /// `Pistol`, `AntiEco`, `Eco`, `Force` or `GunRound`
+    pub buy: String,
+    /// The average equipment value per player
+    pub equipment_value: u32,
+    pub won: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TeamConversions {
+    pub team: u32,
+    pub name: String,
+    pub pistol_rounds: usize,
+    pub pistol_wins: usize,
+    /// Pistol round wins that were followed up with a win in the next round
+    pub pistol_conversions: usize,
+    pub force_rounds: usize,
+    pub force_wins: usize,
+    /// Rounds in which the opening kill put the team up 5v4
+    pub advantage_rounds: usize,
+    pub advantage_wins: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Setups {
+    pub map: String,
+    /// The setups of both teams in this demo
+    pub teams: Vec<TeamSetups>,
+    /// The setups of the user's team across all of their demos on the same map
+    pub own: Vec<SetupEntry>,
+    /// The number of demos the setups of the user's team are taken from
+    pub own_demos: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TeamSetups {
+    pub team: u32,
+    pub name: String,
+    pub setups: Vec<SetupEntry>,
+    /// The setups of the team across all analysed demos on the same map, in which most of the
+    /// players of a side played for it in this demo
+    #[serde(default)]
+    pub all_demos: Vec<SetupEntry>,
+    /// The number of demos the setups across demos are taken from
+    #[serde(default)]
+    pub demos: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SetupEntry {
+    /// `T` or `CT`
+    pub side: String,
+    /// The callouts the players held, in sorted order
+    pub places: Vec<String>,
+    /// Including the rounds with a similar setup
+    pub rounds: usize,
+    pub wins: usize,
+    /// The rounds played with a setup that differed in one position
+    #[serde(default)]
+    pub similar: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Movement {
+    pub rounds: Vec<RoundMovement>,
+    /// The movement of every player across all rounds
+    pub total: Vec<PlayerMovement>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RoundMovement {
+    /// Starting at 0
+    pub round: u32,
+    pub players: Vec<PlayerMovement>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PlayerMovement {
+    pub name: String,
+    /// The horizontal distance traveled in units
+    pub distance: f32,
+    /// In units per second while alive
+    pub average_speed: f32,
+    /// The seconds spent standing still
+    pub still: f32,
+    /// The seconds spent moving while crouched
+    pub crouched: f32,
+    /// The seconds spent moving while holding the walk key
+    pub walking: f32,
+    /// The seconds spent moving at full speed
+    pub running: f32,
+    /// Ordered by time descending
+    pub places: Vec<PlaceTime>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PlaceTime {
+    pub place: String,
+    pub seconds: f32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PostPlants {
+    pub plants: Vec<PlantEntry>,
+    pub teams: Vec<TeamPostPlants>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PlantEntry {
+    /// Starting at 0
+    pub round: usize,
+    pub site: String,
+    /// The name of the planting team
+    pub team: String,
+    pub t_alive: usize,
+    pub ct_alive: usize,
+    /// The seconds that were left on the round timer when the bomb was planted
+    pub time_remaining: f32,
+    /// The callouts of the alive Ts at the time of the plant
+    pub t_places: Vec<String>,
+    /// `Defused`, `Exploded`, `Eliminated` or `Other`
+    pub outcome: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TeamPostPlants {
+    pub team: u32,
+    pub name: String,
+    /// The rounds in which the team planted the bomb
+    pub post_plants: Vec<PostPlantSituation>,
+    /// The rounds in which the team had to retake a site
+    pub retakes: Vec<PostPlantSituation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PostPlantSituation {
+    pub site: String,
+    /// The alive Ts and CTs at the time of the plant, `None` for all situations on the site
+    pub players: Option<(usize, usize)>,
+    pub rounds: usize,
+    pub defused: usize,
+    pub exploded: usize,
+    pub eliminated: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CalloutStats {
+    pub player: String,
+    pub place: String,
+    pub kills: usize,
+    pub deaths: usize,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct HeadToHead {
     pub row_players: Vec<String>,
     pub column_players: Vec<String>,
     pub entries: Vec<Vec<(i16, i16)>>,
 }
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TimelineEntry {
+    pub tick: u32,
+    pub round: Option<usize>,
+    pub event: TimelineEvent,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum TimelineEvent {
+    Chat {
+        sender: String,
+        team_only: bool,
+        message: String,
+    },
+    TimeoutStarted {
+        side: String,
+    },
+    TimeoutEnded {
+        side: String,
+    },
+    TechnicalPauseStarted,
+    TechnicalPauseEnded,
+    VoteStarted {
+        issue: String,
+    },
+    VoteCast {
+        voter: String,
+        yes: bool,
+    },
+    VoteEnded {
+        issue: String,
+        yes: usize,
+        no: usize,
+    },
+    Surrender {
+        side: String,
+    },
+}
+
+/// The problems found while parsing a demo, empty if the demo could be read without issues
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DataQuality {
+    /// The number of events that failed to parse, by the kind of error
+    pub failed_events: Vec<(String, usize)>,
+    /// The number of entity updates that failed to parse, by the kind of error
+    pub failed_entities: Vec<(String, usize)>,
+    pub players_missing_info: Vec<String>,
+    /// Round numbers, starting at 0
+    pub rounds_missing_winreason: Vec<usize>,
+    pub unknown_map: Option<String>,
+    #[serde(default)]
+    pub stat_mismatches: Vec<StatMismatch>,
+}
+
+/// A player stat that was computed differently from the one tracked by the game, the scoreboard
+/// uses the value of the game
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct StatMismatch {
+    pub player: String,
+    pub stat: String,
+    pub computed: usize,
+    pub game: usize,
+}
+
+impl DataQuality {
+    pub fn is_clean(&self) -> bool {
+        self.failed_events.is_empty()
+            && self.failed_entities.is_empty()
+            && self.players_missing_info.is_empty()
+            && self.rounds_missing_winreason.is_empty()
+            && self.unknown_map.is_none()
+            && self.stat_mismatches.is_empty()
+    }
+}
+
+/// The sampled state of a round, all the positions are pixels on the 1024x1024 minimap
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RoundReplay {
+    /// The number of frames per second
+    pub sample_rate: u32,
+    pub players: Vec<String>,
+    pub frames: Vec<ReplayFrame>,
+    pub kills: Vec<ReplayKill>,
+    pub grenades: Vec<ReplayGrenade>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ReplayFrame {
+    pub tick: u32,
+    /// The state of each player, in the same order as the players of the replay
+    pub players: Vec<Option<ReplayPlayerState>>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ReplayPlayerState {
+    pub team: u32,
+    pub x: f32,
+    pub y: f32,
+    /// The horizontal view angle in degrees
+    pub yaw: f32,
+    pub health: u32,
+    pub armor: u32,
+    pub weapon: Option<String>,
+    pub alive: bool,
+    /// The callout the player is at
+    pub place: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ReplayKill {
+    pub tick: u32,
+    pub attacker: Option<String>,
+    pub died: String,
+    pub weapon: Option<String>,
+    pub position: Option<(f32, f32)>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ReplayGrenade {
+    pub kind: String,
+    pub thrower: Option<String>,
+    /// The positions of the grenade, as `(tick, x, y)`
+    pub points: Vec<(u32, f32, f32)>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct GrenadeThrow {
+    /// Starting at 0
+    pub round: usize,
+    pub kind: String,
+    pub thrower: String,
+    /// `T` or `CT`
+    pub side: String,
+    /// The position on the 1024x1024 minimap
+    pub throw_position: (f32, f32),
+    /// The position on the 1024x1024 minimap
+    pub land_position: (f32, f32),
+}
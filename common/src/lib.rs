@@ -12,6 +12,9 @@ pub struct BaseDemoInfo {
     pub team2_score: i16,
     pub team3_score: i16,
     pub player_team: i16,
+    /// The duration of the match itself (without warmup) in seconds
+    #[serde(default)]
+    pub duration: Option<u32>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -20,10 +23,71 @@ pub struct UserStatus {
     pub steamid: String,
 }
 
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PlayerCareer {
+    pub steam_id: String,
+    /// The name the player used in their most recent demo
+    pub name: String,
+    pub total: CareerStats,
+    /// Ordered by the number of matches played on the map
+    pub maps: Vec<MapCareer>,
+    pub sides: Vec<SideCareer>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct MapCareer {
+    pub map: String,
+    pub stats: CareerStats,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SideCareer {
+    pub side: String,
+    pub stats: CareerStats,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CareerStats {
+    pub matches: usize,
+    pub wins: usize,
+    pub losses: usize,
+    pub rounds: usize,
+    pub kills: usize,
+    pub deaths: usize,
+    pub assists: usize,
+    pub damage: usize,
+    pub kd: f32,
+    pub adr: f32,
+    pub headshot_percentage: f32,
+    pub rating: f32,
+    /// The percentage of matches won, 0 for the side splits as a match is played on both sides
+    pub win_rate: f32,
+    pub round_win_rate: f32,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct DemoInfo {
     pub id: String,
     pub map: String,
+    #[serde(default)]
+    pub metadata: Option<DemoMetadata>,
+    /// The demo was cut short, so the analysis only covers the part that could be read
+    #[serde(default)]
+    pub partial: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DemoMetadata {
+    pub server_name: String,
+    pub demo_type: String,
+    pub game_mode: String,
+    pub tick_rate: u32,
+    /// The playback duration of the entire demo in seconds
+    pub playback_duration: u32,
+    /// The offset into the demo, at which the match started, in seconds
+    pub match_start: Option<u32>,
+    pub network_protocol: i32,
+    pub build_number: i32,
 }
 
 pub mod demo_analysis;
@@ -1,12 +1,14 @@
 use leptos::*;
 use leptos_router::{Outlet, A};
 
+pub mod grenades;
 pub mod heatmap;
 pub mod perround;
+pub mod replay;
 pub mod scoreboard;
 
 #[derive(Debug, Clone)]
-struct CurrentDemoName(ReadSignal<String>);
+pub(crate) struct CurrentDemoName(pub(crate) ReadSignal<String>);
 
 #[leptos::component]
 pub fn demo() -> impl leptos::IntoView {
@@ -31,6 +33,17 @@ pub fn demo() -> impl leptos::IntoView {
         },
     );
 
+    let quality = create_resource(
+        || (),
+        move |_| async move {
+            let res = reqwasm::http::Request::get(&format!("/api/demos/{}/analysis/quality", id()))
+                .send()
+                .await
+                .ok()?;
+            res.json::<common::demo_analysis::DataQuality>().await.ok()
+        },
+    );
+
     let rerun_analysis = create_action(move |_: &()| async move {
         let _ = reqwasm::http::Request::get(&format!("/api/demos/{}/reanalyse", id()))
             .send()
@@ -42,6 +55,81 @@ pub fn demo() -> impl leptos::IntoView {
         None => String::new(),
     };
 
+    let metadata = move || {
+        demo_info.get().and_then(|v| v.metadata).map(|meta| {
+            let match_start = meta
+                .match_start
+                .map(|s| format!("{}:{:02}", s / 60, s % 60))
+                .unwrap_or_else(|| "-".to_owned());
+
+            view! {
+                <p class="demo_metadata">
+                    <span>{ meta.server_name }</span>
+                    <span>{ meta.demo_type }</span>
+                    <span>{ meta.game_mode }</span>
+                    <span>{ format!("{} Tick", meta.tick_rate) }</span>
+                    <span>{ format!("Duration {}:{:02}", meta.playback_duration / 60, meta.playback_duration % 60) }</span>
+                    <span>{ format!("Match start {}", match_start) }</span>
+                    <span>{ format!("Protocol {} / Build {}", meta.network_protocol, meta.build_number) }</span>
+                </p>
+            }
+        })
+    };
+
+    let partial_warning = move || {
+        demo_info.get().filter(|v| v.partial).map(|_| {
+            view! {
+                <p class="partial_warning">
+                    "Partial demo: the demo was cut short, so the analysis only covers the part of the match that could be read"
+                </p>
+            }
+        })
+    };
+
+    let data_quality = move || {
+        quality.get().flatten().filter(|q| !q.is_clean()).map(|q| {
+            let mut issues = Vec::new();
+            for (kind, count) in q.failed_events {
+                issues.push(format!("{} events failed to parse ({})", count, kind));
+            }
+            for (kind, count) in q.failed_entities {
+                issues.push(format!("{} entity updates failed to parse ({})", count, kind));
+            }
+            if !q.players_missing_info.is_empty() {
+                issues.push(format!(
+                    "Missing player info for {}",
+                    q.players_missing_info.join(", ")
+                ));
+            }
+            if !q.rounds_missing_winreason.is_empty() {
+                let rounds: Vec<_> = q
+                    .rounds_missing_winreason
+                    .iter()
+                    .map(|r| (r + 1).to_string())
+                    .collect();
+                issues.push(format!("No win reason for rounds {}", rounds.join(", ")));
+            }
+            if let Some(map) = q.unknown_map {
+                issues.push(format!("Unknown map {:?}, no heatmaps available", map));
+            }
+            for mismatch in q.stat_mismatches {
+                issues.push(format!(
+                    "Computed {} {} for {}, the game tracked {}",
+                    mismatch.computed, mismatch.stat, mismatch.player, mismatch.game
+                ));
+            }
+
+            view! {
+                <details class="data_quality">
+                    <summary>"Data quality issues: some numbers for this demo might not be accurate"</summary>
+                    <ul>
+                        { issues.into_iter().map(|issue| view! { <li>{ issue }</li> }).collect::<Vec<_>>() }
+                    </ul>
+                </details>
+            }
+        })
+    };
+
     let style = stylers::style! {
         "Demo",
         span {
@@ -60,15 +148,46 @@ pub fn demo() -> impl leptos::IntoView {
             margin: 2vh 0px;
             grid-template-columns: auto auto;
         }
+
+        .demo_metadata {
+            grid-column: 1 / 3;
+            margin: 0px;
+        }
+        .demo_metadata > span {
+            margin-right: 1vw;
+            padding: 0.5vh 0.5vw;
+        }
+
+        .partial_warning {
+            grid-column: 1 / 3;
+            margin: 1vh 0px 0px 0px;
+            padding: 0.5vh 0.5vw;
+            color: #1a1a1a;
+            background-color: #d5a13a;
+        }
+
+        .data_quality {
+            grid-column: 1 / 3;
+            margin: 1vh 0px 0px 0px;
+            padding: 0.5vh 0.5vw;
+            color: #d5d5d5;
+            background-color: #4d4d4d;
+        }
+        .data_quality > ul {
+            margin: 0.5vh 0px;
+        }
     };
 
     view! {class = style,
         <div class="demo_heading">
             <h2>Demo - { id } - { map }</h2>
             <button on:click=move |_| rerun_analysis.dispatch(()) style="display: inline-block;">Rerun Analysis</button>
+            { metadata }
+            { partial_warning }
+            { data_quality }
         </div>
         
-        <TabBar prefix=move || format!("/demo/{}/", id()) parts=&[("scoreboard", "Scoreboard"), ("perround", "Per Round"), ("heatmaps", "Heatmaps")] />
+        <TabBar prefix=move || format!("/demo/{}/", id()) parts=&[("scoreboard", "Scoreboard"), ("perround", "Per Round"), ("heatmaps", "Heatmaps"), ("grenades", "Grenades"), ("replay", "Replay")] />
         
         <div>
             <Outlet/>
@@ -0,0 +1,156 @@
+use leptos::*;
+
+use super::CurrentDemoName;
+
+fn grenade_color(kind: &str) -> &'static str {
+    match kind {
+        "SmokeGrenade" => "#d0d0d0",
+        "Flashbang" => "#ffff80",
+        "HEGrenade" => "#ff5050",
+        "Molotov" | "Incendiary" => "#ff9a30",
+        _ => "#80ff80",
+    }
+}
+
+/// The selected value of a filter, an empty string means no filtering
+fn matches_filter(filter: &str, value: &str) -> bool {
+    filter.is_empty() || filter == value
+}
+
+#[leptos::component]
+pub fn grenades() -> impl leptos::IntoView {
+    let grenades_resource = create_resource(leptos_router::use_params_map(), |params| async move {
+        let id = params.get("id").unwrap();
+
+        let res = reqwasm::http::Request::get(&format!("/api/demos/{}/analysis/grenades", id))
+            .send()
+            .await
+            .unwrap();
+        res.json::<Vec<common::demo_analysis::GrenadeThrow>>()
+            .await
+            .unwrap_or_default()
+    });
+
+    let map = use_context::<CurrentDemoName>().unwrap();
+
+    let (player, set_player) = create_signal(String::new());
+    let (side, set_side) = create_signal(String::new());
+    let (kind, set_kind) = create_signal(String::new());
+    let (round, set_round) = create_signal(String::new());
+
+    let style = stylers::style! {
+        "Grenades",
+        .container {
+            margin-top: 1vh;
+        }
+
+        .filters > select {
+            margin-right: 1vw;
+        }
+
+        .grenade_image {
+            width: min(60vw, 75vh);
+            height: min(60vw, 75vh);
+            display: block;
+            position: relative;
+            margin-top: 1vh;
+        }
+        .grenade_image > * {
+            position: absolute;
+            width: min(60vw, 75vh);
+            height: min(60vw, 75vh);
+        }
+    };
+
+    // The distinct values of the grenades for one of the filters
+    let options = move |value: fn(&common::demo_analysis::GrenadeThrow) -> String| {
+        let mut values: Vec<_> = grenades_resource
+            .get()
+            .unwrap_or_default()
+            .iter()
+            .map(value)
+            .collect();
+        values.sort_unstable();
+        values.dedup();
+
+        values
+            .into_iter()
+            .map(|v| view! { <option value=v.clone()>{ v }</option> })
+            .collect::<Vec<_>>()
+    };
+
+    let round_options = move || {
+        let mut rounds: Vec<_> = grenades_resource
+            .get()
+            .unwrap_or_default()
+            .iter()
+            .map(|g| g.round)
+            .collect();
+        rounds.dedup();
+
+        rounds
+            .into_iter()
+            .map(|r| view! { <option value=r.to_string()>{ format!("Round {}", r + 1) }</option> })
+            .collect::<Vec<_>>()
+    };
+
+    let throws = move || {
+        grenades_resource
+            .get()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|g| {
+                matches_filter(&player(), &g.thrower)
+                    && matches_filter(&side(), &g.side)
+                    && matches_filter(&kind(), &g.kind)
+                    && matches_filter(&round(), &g.round.to_string())
+            })
+            .map(|g| {
+                let (throw_x, throw_y) = g.throw_position;
+                let (land_x, land_y) = g.land_position;
+                let color = grenade_color(&g.kind);
+
+                view! {
+                    <g>
+                        <line x1=throw_x y1=throw_y x2=land_x y2=land_y stroke=color stroke-width="2" stroke-dasharray="6 4" />
+                        <circle cx=throw_x cy=throw_y r="4" fill=color />
+                        <circle cx=land_x cy=land_y r="9" fill=color fill-opacity="0.6" stroke="black" stroke-width="2" />
+                    </g>
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    view! {
+        class=style,
+        <div class="container">
+            <div class="filters">
+                <select on:change=move |ev| set_player(event_target_value(&ev))>
+                    <option value="">"All Players"</option>
+                    { move || options(|g| g.thrower.clone()) }
+                </select>
+                <select on:change=move |ev| set_side(event_target_value(&ev))>
+                    <option value="">"Both Sides"</option>
+                    { move || options(|g| g.side.clone()) }
+                </select>
+                <select on:change=move |ev| set_kind(event_target_value(&ev))>
+                    <option value="">"All Grenades"</option>
+                    { move || options(|g| g.kind.clone()) }
+                </select>
+                <select on:change=move |ev| set_round(event_target_value(&ev))>
+                    <option value="">"All Rounds"</option>
+                    { round_options }
+                </select>
+            </div>
+
+            <Suspense fallback=move || view! { <p>"Loading Grenades"</p> }>
+                <div class="grenade_image">
+                    <img src=move || format!("/static/minimaps/{}.png", map.0.get()) />
+                    <svg viewBox="0 0 1024 1024">
+                        { throws }
+                    </svg>
+                </div>
+            </Suspense>
+        </div>
+    }
+}
@@ -42,7 +42,7 @@ pub fn heatmaps() -> impl leptos::IntoView {
 }
 
 #[leptos::component]
-fn heatmap_view(heatmaps: Vec<common::demo_analysis::PlayerHeatmap>) -> impl leptos::IntoView {
+pub(crate) fn heatmap_view(heatmaps: Vec<common::demo_analysis::PlayerHeatmap>) -> impl leptos::IntoView {
     let (idx, set_idx) = create_signal(0usize);
     let (value, set_value) = create_signal(Vec::<common::demo_analysis::PlayerHeatmap>::new());
 
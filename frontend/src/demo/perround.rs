@@ -26,6 +26,18 @@ pub fn per_round() -> impl leptos::IntoView {
             .unwrap()
     });
 
+    let timeline_resource = create_resource(leptos_router::use_params_map(), |params| async move {
+        let id = params.get("id").unwrap();
+
+        let res = reqwasm::http::Request::get(&format!("/api/demos/{}/analysis/timeline", id))
+            .send()
+            .await
+            .unwrap();
+        res.json::<Vec<common::demo_analysis::TimelineEntry>>()
+            .await
+            .unwrap_or_default()
+    });
+
     let style = stylers::style! {
         "PerRound",
         .round_overview {
@@ -66,6 +78,24 @@ pub fn per_round() -> impl leptos::IntoView {
         .lose {
             background-color: #22222277;
         }
+
+        .alive_strip {
+            display: flex;
+            width: 60vw;
+            height: 3vh;
+            border: 1px solid #101010;
+        }
+        .alive_segment {
+            overflow: hidden;
+            text-align: center;
+            border-right: 1px solid #101010;
+        }
+
+        .momentum {
+            width: 90vw;
+            height: 15vh;
+            margin-top: 1vh;
+        }
     };
 
     let (round, set_round) = create_signal(0);
@@ -83,9 +113,12 @@ pub fn per_round() -> impl leptos::IntoView {
             (Some(round), Some(teams)) => {
                 round.events.into_iter().map(|event| {
                     match event {
-                        common::demo_analysis::RoundEvent::BombPlanted => view! { <li>Bomb has been planted</li> }.into_view(),
+                        common::demo_analysis::RoundEvent::BombPlanted { place } => {
+                            let place_display = place.map(|p| format!(" at {}", p)).unwrap_or_default();
+                            view! { <li>Bomb has been planted{ place_display }</li> }.into_view()
+                        },
                         common::demo_analysis::RoundEvent::BombDefused => view! { <li>Bomb has been defused</li> }.into_view(),
-                        common::demo_analysis::RoundEvent::Killed { attacker, died, weapon, headshot, noscope } => {
+                        common::demo_analysis::RoundEvent::Killed { attacker, died, weapon, headshot, noscope, died_place, penetrated, thrusmoke, attackerblind, distance, impact, .. } => {
                             let mut attacker_t = teams.iter().find(|t| t.players.contains(&attacker)).map(|t| t.name == "TERRORIST").unwrap_or(false);
                             let mut died_t = teams.iter().find(|t| t.players.contains(&died)).map(|t| t.name == "TERRORIST").unwrap_or(false);
 
@@ -97,10 +130,16 @@ pub fn per_round() -> impl leptos::IntoView {
                             let weapon_display = move || {
                                 let parts = weapon.as_ref().into_iter().map(|w| w.as_str())
                                     .chain(headshot.then_some("Headshot").into_iter())
-                                    .chain(noscope.then_some("Noscope").into_iter());
+                                    .chain(noscope.then_some("Noscope").into_iter())
+                                    .chain(penetrated.then_some("Wallbang").into_iter())
+                                    .chain(thrusmoke.then_some("Through Smoke").into_iter())
+                                    .chain(attackerblind.then_some("Blind").into_iter());
+                                let distance = distance.map(|d| format!(", {} units", d)).unwrap_or_default();
 
-                                format!("(using {})", parts.collect::<Vec<_>>().join(","))
+                                format!("(using {}{})", parts.collect::<Vec<_>>().join(","), distance)
                             };
+                            let place_display = died_place.map(|p| format!(" at {}", p)).unwrap_or_default();
+                            let impact_display = impact.map(|i| format!(" ({:+}% win chance)", i)).unwrap_or_default();
 
                             view! {
                                 class=style,
@@ -108,6 +147,8 @@ pub fn per_round() -> impl leptos::IntoView {
                                     {"'"}<span class:t_player=move || attacker_t class:ct_player=move || !attacker_t>{ attacker }</span>{"'"}
                                     killed { weapon_display }
                                     {"'"}<span class:t_player=move || died_t class:ct_player=move || !died_t>{ died }</span>{"'"}
+                                    { place_display }
+                                    { impact_display }
                                 </li>
                             }.into_view()
                         },
@@ -186,21 +227,199 @@ pub fn per_round() -> impl leptos::IntoView {
         let upper = perround_teams
             .iter()
             .find(|t| t.name == "CT")
-            .map(|t| t.number)
-            .unwrap_or(0);
+            .map(|t| t.display_name())
+            .unwrap_or_default();
         let lower = perround_teams
             .iter()
             .find(|t| t.name == "TERRORIST")
-            .map(|t| t.number)
-            .unwrap_or(0);
+            .map(|t| t.display_name())
+            .unwrap_or_default();
+
+        view! {
+            <span style="grid-column: 1; grid-row: 1">{ upper }</span>
+            <span style="grid-column: 1; grid-row: 3">{ lower }</span>
+        }
+        .into_view()
+    };
+
+    let timeline_list = move || {
+        let round_index = round();
+
+        timeline_resource
+            .get()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|entry| entry.round == Some(round_index))
+            .map(|entry| {
+                let text = match entry.event {
+                    common::demo_analysis::TimelineEvent::Chat {
+                        sender,
+                        team_only,
+                        message,
+                    } => {
+                        let prefix = if team_only { "(Team) " } else { "" };
+                        format!("{}{}: {}", prefix, sender, message)
+                    }
+                    common::demo_analysis::TimelineEvent::TimeoutStarted { side } => {
+                        format!("{} called a tactical timeout", side)
+                    }
+                    common::demo_analysis::TimelineEvent::TimeoutEnded { side } => {
+                        format!("Tactical timeout of {} ended", side)
+                    }
+                    common::demo_analysis::TimelineEvent::TechnicalPauseStarted => {
+                        "Technical pause started".to_owned()
+                    }
+                    common::demo_analysis::TimelineEvent::TechnicalPauseEnded => {
+                        "Technical pause ended".to_owned()
+                    }
+                    common::demo_analysis::TimelineEvent::VoteStarted { issue } => {
+                        format!("Vote started: {}", issue)
+                    }
+                    common::demo_analysis::TimelineEvent::VoteCast { voter, yes } => {
+                        format!("{} voted {}", voter, if yes { "Yes" } else { "No" })
+                    }
+                    common::demo_analysis::TimelineEvent::VoteEnded { issue, yes, no } => {
+                        format!("Vote {} ended ({} Yes / {} No)", issue, yes, no)
+                    }
+                    common::demo_analysis::TimelineEvent::Surrender { side } => {
+                        format!("{} surrendered", side)
+                    }
+                };
+
+                view! { <li>{ text }</li> }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // The alive players of both sides over the course of the selected round, every segment is as
+    // wide as the time until the next death
+    let alive_strip = move || {
+        let current_round = perround_resource
+            .get()
+            .and_then(|rs| rs.rounds.get(round()).cloned());
+        let current_round = match current_round {
+            Some(r) if !r.alive.is_empty() => r,
+            _ => return view! {}.into_view(),
+        };
+
+        let length = current_round.length.max(1);
+        let segments = current_round
+            .alive
+            .iter()
+            .enumerate()
+            .map(|(idx, alive)| {
+                let end = current_round
+                    .alive
+                    .get(idx + 1)
+                    .map(|next| next.tick)
+                    .unwrap_or(length);
+                let width = end.saturating_sub(alive.tick) as f32 * 100.0 / length as f32;
+                let t_advantage = alive.t > alive.ct;
+                let ct_advantage = alive.ct > alive.t;
+
+                view! {
+                    class=style,
+                    <div
+                        class="alive_segment"
+                        class:t_player=move || t_advantage
+                        class:ct_player=move || ct_advantage
+                        class:lose=move || !t_advantage && !ct_advantage
+                        style=format!("width: {}%;", width)
+                        title=format!("{} T vs {} CT", alive.t, alive.ct)
+                    >{ format!("{}v{}", alive.t, alive.ct) }</div>
+                }
+            })
+            .collect::<Vec<_>>();
+
+        view! {
+            class=style,
+            <h4> Alive Players (T vs CT) </h4>
+            <div class="alive_strip">{ segments }</div>
+        }
+        .into_view()
+    };
+
+    let advantage_rows = move || {
+        let data = match perround_resource.get() {
+            Some(d) => d,
+            None => return Vec::new(),
+        };
+
+        data.advantages
+            .into_iter()
+            .map(|a| {
+                let team = data
+                    .teams
+                    .iter()
+                    .find(|t| t.number == a.team)
+                    .map(|t| t.display_name())
+                    .unwrap_or_else(|| format!("Team {}", a.team));
+
+                view! {
+                    <tr>
+                        <td>{ team }</td>
+                        <td>{ a.situation }</td>
+                        <td>{ a.rounds }</td>
+                        <td>{ a.won }</td>
+                        <td>{ a.lost }</td>
+                    </tr>
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // The win probability of the team that started on CT over the whole match, with a line at the
+    // start of every round
+    let momentum_graph = move || {
+        let data = match perround_resource.get() {
+            Some(d) if d.momentum.len() > 1 => d,
+            _ => return view! {}.into_view(),
+        };
+
+        let step = 1000.0 / (data.momentum.len() - 1) as f32;
+        let points = data
+            .momentum
+            .iter()
+            .enumerate()
+            .map(|(idx, p)| format!("{},{}", idx as f32 * step, 100 - p.starting_ct_win.min(100)))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let round_lines = data
+            .momentum
+            .iter()
+            .enumerate()
+            .filter(|(idx, p)| *idx == 0 || data.momentum[idx - 1].round != p.round)
+            .map(|(idx, _)| {
+                let x = idx as f32 * step;
+                view! { <line x1=x y1="0" x2=x y2="100" stroke="#555555" stroke-width="0.5" /> }
+            })
+            .collect::<Vec<_>>();
+        let team = data
+            .teams
+            .iter()
+            .find(|t| t.name == "CT")
+            .map(|t| t.display_name())
+            .unwrap_or_default();
 
         view! {
-            <span style="grid-column: 1; grid-row: 1">Team { upper }</span>
-            <span style="grid-column: 1; grid-row: 3">Team { lower }</span>
+            class=style,
+            <h3> Momentum </h3>
+            <p>"Win chance of " { team } " over the match"</p>
+            <svg class="momentum" viewBox="0 0 1000 100" preserveAspectRatio="none">
+                { round_lines }
+                <line x1="0" y1="50" x2="1000" y2="50" stroke="#888888" stroke-dasharray="4" />
+                <polyline points=points fill="none" stroke="#1111ff" stroke-width="1.5" />
+            </svg>
         }
         .into_view()
     };
 
+    let player_grid = move || {
+        perround_resource.get().map(|data| {
+            view! { <PlayerRoundGrid rounds=data.rounds teams=data.teams /> }
+        })
+    };
+
     view! {
         class=style,
         <div class="round_overview">
@@ -208,11 +427,125 @@ pub fn per_round() -> impl leptos::IntoView {
             { round_overview }
         </div>
 
+        <div>
+            { momentum_graph }
+        </div>
+
         <div>
             <h3> Round { move || round.get() + 1 } </h3>
+            { alive_strip }
             <div>
                 <ul> { events_list } </ul>
             </div>
+            <div>
+                <h4> Timeline </h4>
+                <ul> { timeline_list } </ul>
+            </div>
+        </div>
+
+        <div>
+            <h3> Man Advantages </h3>
+            <table>
+                <tr>
+                    <th>Team</th>
+                    <th>Situation</th>
+                    <th>Rounds</th>
+                    <th>Converted</th>
+                    <th>Thrown</th>
+                </tr>
+                { advantage_rows }
+            </table>
         </div>
+
+        <div>
+            <h3> Players </h3>
+            { player_grid }
+        </div>
+    }
+}
+
+#[leptos::component]
+fn player_round_grid(
+    rounds: Vec<common::demo_analysis::DemoRound>,
+    teams: Vec<common::demo_analysis::PerRoundTeam>,
+) -> impl leptos::IntoView {
+    let style = stylers::style! {
+        "PlayerRoundGrid",
+        table {
+            border-collapse: collapse;
+        }
+        td, th {
+            padding: 2px 4px;
+            border: 1px solid #101010;
+            text-align: center;
+            white-space: nowrap;
+        }
+        td.name {
+            text-align: left;
+        }
+        .survived {
+            background-color: var(--color-surface-a20);
+        }
+        .died {
+            background-color: #22222277;
+            color: #999999;
+        }
+        .stat {
+            display: block;
+            font-size: 12px;
+        }
+    };
+
+    let mut sorted_teams = teams;
+    sorted_teams.sort_unstable_by_key(|t| t.number);
+
+    let header = (0..rounds.len())
+        .map(|r| view! { class=style, <th>{ r + 1 }</th> })
+        .collect::<Vec<_>>();
+
+    let rows = sorted_teams
+        .into_iter()
+        .flat_map(|team| {
+            let mut players = team.players.into_iter().collect::<Vec<_>>();
+            players.sort_unstable();
+            players
+        })
+        .map(|player| {
+            let cells = rounds
+                .iter()
+                .map(|round| match round.players.iter().find(|p| p.name == player) {
+                    Some(stats) => {
+                        let survived = stats.survived;
+                        view! {
+                            class=style,
+                            <td class:survived=move || survived class:died=move || !survived>
+                                <span class="stat">{ format!("{}K / {} DMG", stats.kills, stats.damage_dealt) }</span>
+                                <span class="stat">{ format!("{} HP / ${}", stats.end_health, stats.equipment_saved) }</span>
+                            </td>
+                        }
+                    }
+                    None => view! { class=style, <td></td> },
+                })
+                .collect::<Vec<_>>();
+
+            view! {
+                class=style,
+                <tr>
+                    <td class="name">{ player }</td>
+                    { cells }
+                </tr>
+            }
+        })
+        .collect::<Vec<_>>();
+
+    view! {
+        class=style,
+        <table>
+            <tr>
+                <th>Player</th>
+                { header }
+            </tr>
+            { rows }
+        </table>
     }
 }
@@ -0,0 +1,299 @@
+use leptos::*;
+
+use super::CurrentDemoName;
+
+fn team_color(team: u32) -> &'static str {
+    match team {
+        2 => "#dd4411",
+        3 => "#4477ff",
+        _ => "#a0a0a0",
+    }
+}
+
+fn grenade_color(kind: &str) -> &'static str {
+    match kind {
+        "SmokeGrenade" => "#d0d0d0",
+        "Flashbang" => "#ffff80",
+        "HEGrenade" => "#ff5050",
+        "Molotov" | "Incendiary" => "#ff9a30",
+        _ => "#80ff80",
+    }
+}
+
+#[leptos::component]
+pub fn replay() -> impl leptos::IntoView {
+    let params = leptos_router::use_params_map();
+    let id = move || params.with(|params| params.get("id").cloned().unwrap_or_default());
+
+    let map = use_context::<CurrentDemoName>().unwrap();
+
+    let rounds_resource = create_resource(id, |id| async move {
+        let res = reqwasm::http::Request::get(&format!("/api/demos/{}/analysis/perround", id))
+            .send()
+            .await
+            .ok()?;
+        res.json::<common::demo_analysis::PerRoundResult>()
+            .await
+            .ok()
+            .map(|r| r.rounds.len())
+    });
+
+    let (round, set_round) = create_signal(0usize);
+    let (frame, set_frame) = create_signal(0usize);
+    let (playing, set_playing) = create_signal(false);
+
+    let replay_resource = create_resource(
+        move || (id(), round()),
+        |(id, round)| async move {
+            let res =
+                reqwasm::http::Request::get(&format!("/api/demos/{}/rounds/{}/replay", id, round))
+                    .send()
+                    .await
+                    .ok()?;
+            res.json::<common::demo_analysis::RoundReplay>().await.ok()
+        },
+    );
+
+    // Restart the playback timer for every round, as it depends on the sample rate of the replay
+    let interval = store_value(None::<leptos::leptos_dom::helpers::IntervalHandle>);
+    create_effect(move |_| {
+        let (sample_rate, frames) = match replay_resource.get().flatten() {
+            Some(r) => (r.sample_rate.max(1), r.frames.len()),
+            None => return,
+        };
+
+        set_frame(0);
+        set_playing(false);
+
+        if let Some(handle) = interval.get_value() {
+            handle.clear();
+        }
+        let handle = set_interval_with_handle(
+            move || {
+                if !playing.get_untracked() {
+                    return;
+                }
+
+                if frame.get_untracked() + 1 < frames {
+                    set_frame.update(|f| *f += 1);
+                } else {
+                    set_playing(false);
+                }
+            },
+            std::time::Duration::from_millis(1000 / sample_rate as u64),
+        );
+        interval.set_value(handle.ok());
+    });
+    on_cleanup(move || {
+        if let Some(handle) = interval.get_value() {
+            handle.clear();
+        }
+    });
+
+    let style = stylers::style! {
+        "Replay",
+        .container {
+            margin-top: 1vh;
+        }
+
+        .replay_image {
+            width: min(60vw, 75vh);
+            height: min(60vw, 75vh);
+            display: block;
+            position: relative;
+        }
+        .replay_image > * {
+            position: absolute;
+            width: min(60vw, 75vh);
+            height: min(60vw, 75vh);
+        }
+
+        .controls {
+            display: grid;
+            width: min(60vw, 75vh);
+            grid-template-columns: auto 1fr auto;
+            column-gap: 1vw;
+            margin: 1vh 0px;
+        }
+
+        .kill_feed {
+            list-style: none;
+            padding: 0px;
+        }
+    };
+
+    let round_options = move || {
+        (0..rounds_resource.get().flatten().unwrap_or(0))
+            .map(|r| view! { <option value=r>{ format!("Round {}", r + 1) }</option> })
+            .collect::<Vec<_>>()
+    };
+
+    let content = move || {
+        let replay = match replay_resource.get() {
+            None => return view! { <p>"Loading Replay"</p> }.into_view(),
+            Some(None) => return view! { <p>"No replay available for this round"</p> }.into_view(),
+            Some(Some(r)) => r,
+        };
+
+        let frames = replay.frames.len();
+        let sample_rate = replay.sample_rate.max(1) as usize;
+        let players = replay.players.clone();
+
+        let current_tick = {
+            let replay = replay.clone();
+            move || replay.frames.get(frame()).map(|f| f.tick).unwrap_or(0)
+        };
+
+        let player_markers = {
+            let replay = replay.clone();
+            move || {
+                let current = match replay.frames.get(frame()) {
+                    Some(f) => f.clone(),
+                    None => return Vec::new(),
+                };
+
+                current
+                    .players
+                    .into_iter()
+                    .zip(players.iter().cloned())
+                    .filter_map(|(state, name)| state.filter(|s| s.alive).map(|s| (s, name)))
+                    .map(|(state, name)| {
+                        let yaw = state.yaw.to_radians();
+                        let view_x = state.x + yaw.cos() * 25.0;
+                        let view_y = state.y - yaw.sin() * 25.0;
+
+                        view! {
+                            <g>
+                                <line x1=state.x y1=state.y x2=view_x y2=view_y stroke="white" stroke-width="3" />
+                                <circle cx=state.x cy=state.y r="10" fill=team_color(state.team) stroke="black" stroke-width="2" />
+                                <text x={state.x + 14.0} y={state.y - 14.0} fill="white" font-size="18">
+                                    { format!("{} ({})", name, state.health) }
+                                </text>
+                                <text x={state.x + 14.0} y={state.y + 4.0} fill="#d0d0d0" font-size="14">
+                                    { state.place.clone().unwrap_or_default() }
+                                </text>
+                            </g>
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        let kill_markers = {
+            let replay = replay.clone();
+            let current_tick = current_tick.clone();
+            move || {
+                let tick = current_tick();
+                replay
+                    .kills
+                    .iter()
+                    .filter(|k| k.tick <= tick)
+                    .filter_map(|k| k.position)
+                    .map(|(x, y)| {
+                        view! {
+                            <g stroke="#ff2020" stroke-width="4">
+                                <line x1={x - 8.0} y1={y - 8.0} x2={x + 8.0} y2={y + 8.0} />
+                                <line x1={x - 8.0} y1={y + 8.0} x2={x + 8.0} y2={y - 8.0} />
+                            </g>
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        let grenade_trajectories = {
+            let replay = replay.clone();
+            let current_tick = current_tick.clone();
+            move || {
+                let tick = current_tick();
+                replay
+                    .grenades
+                    .iter()
+                    .filter(|g| g.points.first().is_some_and(|p| p.0 <= tick))
+                    .map(|g| {
+                        let points = g
+                            .points
+                            .iter()
+                            .filter(|p| p.0 <= tick)
+                            .map(|(_, x, y)| format!("{},{}", x, y))
+                            .collect::<Vec<_>>()
+                            .join(" ");
+
+                        view! {
+                            <polyline points=points fill="none" stroke=grenade_color(&g.kind) stroke-width="3" stroke-dasharray="6 4" />
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        let kill_feed = {
+            let replay = replay.clone();
+            move || {
+                let tick = current_tick();
+                replay
+                    .kills
+                    .iter()
+                    .filter(|k| k.tick <= tick)
+                    .map(|k| {
+                        let weapon = k.weapon.clone().unwrap_or_default();
+                        let text = match &k.attacker {
+                            Some(attacker) => {
+                                format!("{} killed {} ({})", attacker, k.died, weapon)
+                            }
+                            None => format!("{} died", k.died),
+                        };
+                        view! { <li>{ text }</li> }
+                    })
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        let time = move || {
+            let seconds = frame() / sample_rate;
+            format!("{}:{:02}", seconds / 60, seconds % 60)
+        };
+
+        view! {
+            class=style,
+            <div class="controls">
+                <button on:click=move |_| set_playing.update(|p| *p = !*p)>
+                    { move || if playing() { "Pause" } else { "Play" } }
+                </button>
+                <input
+                    type="range"
+                    min="0"
+                    max={frames.saturating_sub(1)}
+                    prop:value=move || frame().to_string()
+                    on:input=move |ev| set_frame(event_target_value(&ev).parse().unwrap_or(0))
+                />
+                <span>{ time }</span>
+            </div>
+            <div class="replay_image">
+                <img src=format!("/static/minimaps/{}.png", map.0.get()) />
+                <svg viewBox="0 0 1024 1024">
+                    { grenade_trajectories }
+                    { kill_markers }
+                    { player_markers }
+                </svg>
+            </div>
+            <ul class="kill_feed">
+                { kill_feed }
+            </ul>
+        }
+        .into_view()
+    };
+
+    view! {
+        class=style,
+        <div class="container">
+            <select on:change=move |ev| set_round(event_target_value(&ev).parse().unwrap_or(0)) prop:value=move || round().to_string()>
+                { round_options }
+            </select>
+
+            <Suspense fallback=move || view! { <p>"Loading Replay"</p> }>
+                { content }
+            </Suspense>
+        </div>
+    }
+}
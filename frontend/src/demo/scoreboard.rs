@@ -1,8 +1,15 @@
 use leptos::*;
 use leptos_router::Outlet;
 
+pub mod callouts;
 pub mod general;
 pub mod headtohead;
+pub mod hitgroups;
+pub mod movement;
+pub mod postplant;
+pub mod roundtypes;
+pub mod setups;
+pub mod weapons;
 
 use crate::demo::TabBar;
 
@@ -12,7 +19,7 @@ pub fn scoreboard() -> impl leptos::IntoView {
     let id = move || params.with(|params| params.get("id").cloned().unwrap_or_default());
 
     view! {
-        <TabBar prefix=move || format!("/demo/{}/scoreboard", id()) parts=&[("general", "General"), ("headtohead", "Head-to-Head")] />
+        <TabBar prefix=move || format!("/demo/{}/scoreboard", id()) parts=&[("general", "General"), ("headtohead", "Head-to-Head"), ("callouts", "Callouts"), ("weapons", "Weapons"), ("hitgroups", "Hitgroups"), ("roundtypes", "Round Types"), ("setups", "Setups"), ("movement", "Movement"), ("postplant", "Post Plant")] />
 
         <Outlet />
     }
@@ -68,7 +75,7 @@ mod orderings {
 }
 
 #[leptos::component]
-fn team_scoreboard(
+pub(crate) fn team_scoreboard(
     value: Vec<common::demo_analysis::ScoreBoardPlayer>,
     team_name: String,
 ) -> impl IntoView {
@@ -108,6 +115,10 @@ fn team_scoreboard(
                     <th on:click=move |_| {
             set_ordering(orderings::DAMAGE);
         }>Damage { move || ordering().display_symbol(orderings::SelectedStat::Damage) }</th>
+                    <th>Rounds</th>
+                    <th>Wallbangs</th>
+                    <th>Smoke Kills</th>
+                    <th>Blind Kills</th>
                 </tr>
         {
             move || {
@@ -118,7 +129,7 @@ fn team_scoreboard(
                 players.into_iter().map(|s| {
                     view! {
                         class=style,
-                        <tr><td>{ s.name }</td><td>{ s.kills }</td><td>{ s.assists }</td><td>{ s.deaths }</td><td>{ s.damage }</td></tr>
+                        <tr><td>{ s.name }</td><td>{ s.kills }</td><td>{ s.assists }</td><td>{ s.deaths }</td><td>{ s.damage }</td><td>{ s.rounds_played.map(|r| r.to_string()).unwrap_or_default() }</td><td>{ s.wallbangs.map(|r| r.to_string()).unwrap_or_default() }</td><td>{ s.smoke_kills.map(|r| r.to_string()).unwrap_or_default() }</td><td>{ s.blind_kills.map(|r| r.to_string()).unwrap_or_default() }</td></tr>
                     }
                 }).collect::<Vec<_>>()
             }
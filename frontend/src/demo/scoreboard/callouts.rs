@@ -0,0 +1,96 @@
+use leptos::*;
+use leptos::Suspense;
+
+#[leptos::component]
+pub fn callouts() -> impl leptos::IntoView {
+    let callouts_resource = create_resource(leptos_router::use_params_map(), |params| async move {
+        let id = params.get("id").unwrap();
+
+        let res = reqwasm::http::Request::get(&format!("/api/demos/{}/analysis/callouts", id))
+            .send()
+            .await
+            .unwrap();
+        res.json::<Vec<common::demo_analysis::CalloutStats>>()
+            .await
+            .unwrap_or_default()
+    });
+
+    let (player, set_player) = create_signal(String::new());
+
+    let style = stylers::style! {
+        "Callouts",
+        select {
+            margin: 1vh 0px;
+        }
+        tr:nth-child(even) {
+            background-color: var(--color-surface-a10);
+        }
+        th, td {
+            padding: 0.3vh 1vw;
+        }
+    };
+
+    let player_options = move || {
+        let mut players: Vec<_> = callouts_resource
+            .get()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|c| c.player)
+            .collect();
+        players.sort_unstable();
+        players.dedup();
+
+        players
+            .into_iter()
+            .map(|p| view! { <option value=p.clone()>{ p }</option> })
+            .collect::<Vec<_>>()
+    };
+
+    let rows = move || {
+        let mut stats: Vec<_> = callouts_resource
+            .get()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|c| player().is_empty() || c.player == player())
+            .collect();
+        stats.sort_by(|c1, c2| {
+            c1.player
+                .cmp(&c2.player)
+                .then(c2.kills.cmp(&c1.kills))
+                .then(c1.deaths.cmp(&c2.deaths))
+        });
+
+        stats
+            .into_iter()
+            .map(|c| {
+                view! {
+                    <tr>
+                        <td>{ c.player }</td>
+                        <td>{ c.place }</td>
+                        <td>{ c.kills }</td>
+                        <td>{ c.deaths }</td>
+                    </tr>
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    view! {
+        class=style,
+        <select on:change=move |ev| set_player(event_target_value(&ev))>
+            <option value="">"All Players"</option>
+            { player_options }
+        </select>
+        <Suspense fallback=move || view! { <p>Loading Callouts...</p> }>
+            <table>
+                <tr>
+                    <th>Player</th>
+                    <th>Callout</th>
+                    <th>Kills</th>
+                    <th>Deaths</th>
+                </tr>
+                { rows }
+            </table>
+        </Suspense>
+    }
+}
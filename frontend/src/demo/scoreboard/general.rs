@@ -29,7 +29,7 @@ pub fn general() -> impl leptos::IntoView {
             .flat_map(|v| v.teams.into_iter())
             .map(|team| {
                 view! {
-                    <TeamScoreboard value=team.players team_name=format!("Team {} - {}", team.number, team.score) />
+                    <TeamScoreboard value=team.players team_name=format!("{} - {}", team.display_name(), team.score) />
                 }
             })
             .collect::<Vec<_>>()
@@ -0,0 +1,194 @@
+use leptos::*;
+use leptos::Suspense;
+
+/// The parts of the body diagram, as `(hitgroup, x, y, width, height)` in the 200x400 viewbox
+const BODY_PARTS: &[(&str, f32, f32, f32, f32)] = &[
+    ("Head", 75.0, 0.0, 50.0, 50.0),
+    ("Neck", 88.0, 50.0, 24.0, 15.0),
+    ("Chest", 60.0, 65.0, 80.0, 75.0),
+    ("Stomach", 60.0, 140.0, 80.0, 60.0),
+    ("RightArm", 25.0, 65.0, 33.0, 130.0),
+    ("LeftArm", 142.0, 65.0, 33.0, 130.0),
+    ("RightLeg", 60.0, 200.0, 38.0, 190.0),
+    ("LeftLeg", 102.0, 200.0, 38.0, 190.0),
+];
+
+#[leptos::component]
+pub fn hitgroups() -> impl leptos::IntoView {
+    let hitgroups_resource =
+        create_resource(leptos_router::use_params_map(), |params| async move {
+            let id = params.get("id").unwrap();
+
+            let res = reqwasm::http::Request::get(&format!("/api/demos/{}/analysis/hitgroups", id))
+                .send()
+                .await
+                .unwrap();
+            res.json::<Vec<common::demo_analysis::PlayerHitgroups>>()
+                .await
+                .unwrap_or_default()
+        });
+
+    let (player, set_player) = create_signal(String::new());
+    let (received, set_received) = create_signal(false);
+    let (weapon, set_weapon) = create_signal(String::new());
+
+    let style = stylers::style! {
+        "Hitgroups",
+        .container {
+            display: flex;
+            column-gap: 2vw;
+            margin-top: 1vh;
+        }
+        .filters > select {
+            margin-right: 1vw;
+        }
+        svg {
+            width: 20vh;
+            height: 40vh;
+        }
+        th, td {
+            padding: 0.3vh 1vw;
+        }
+    };
+
+    // The hits of the selected player, or of all players if none is selected
+    let selected_hits = move || {
+        hitgroups_resource
+            .get()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|p| player().is_empty() || p.name == player())
+            .flat_map(|p| if received() { p.received } else { p.dealt })
+            .filter(|h| weapon().is_empty() || h.weapon == weapon())
+            .collect::<Vec<_>>()
+    };
+
+    // The hits and damage per hitgroup, ordered by the number of hits
+    let totals = move || {
+        let mut totals = std::collections::BTreeMap::<String, (usize, usize)>::new();
+        for hit in selected_hits() {
+            let entry = totals.entry(hit.hitgroup).or_default();
+            entry.0 += hit.hits;
+            entry.1 += hit.damage;
+        }
+
+        let mut totals: Vec<_> = totals.into_iter().collect();
+        totals.sort_by(|(_, t1), (_, t2)| t2.0.cmp(&t1.0));
+        totals
+    };
+
+    let player_options = move || {
+        hitgroups_resource
+            .get()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|p| view! { <option value=p.name.clone()>{ p.name }</option> })
+            .collect::<Vec<_>>()
+    };
+
+    let weapon_options = move || {
+        let mut weapons: Vec<_> = hitgroups_resource
+            .get()
+            .unwrap_or_default()
+            .into_iter()
+            .flat_map(|p| p.dealt.into_iter().map(|h| h.weapon))
+            .collect();
+        weapons.sort_unstable();
+        weapons.dedup();
+
+        weapons
+            .into_iter()
+            .map(|w| view! { <option value=w.clone()>{ w }</option> })
+            .collect::<Vec<_>>()
+    };
+
+    let body = move || {
+        let totals = totals();
+        let all_hits: usize = totals.iter().map(|(_, (hits, _))| hits).sum();
+        let share = |hitgroup: &str| {
+            let hits = totals
+                .iter()
+                .find(|(h, _)| h == hitgroup)
+                .map(|(_, (hits, _))| *hits)
+                .unwrap_or(0);
+            hits as f32 / all_hits.max(1) as f32
+        };
+
+        BODY_PARTS
+            .iter()
+            .map(|(hitgroup, x, y, width, height)| {
+                let share = share(*hitgroup);
+                view! {
+                    <g>
+                        <rect
+                            x=*x
+                            y=*y
+                            width=*width
+                            height=*height
+                            rx="6"
+                            fill="#ff5050"
+                            fill-opacity={0.1 + share * 0.9}
+                            stroke="white"
+                        />
+                        <text x={x + width / 2.0} y={y + height / 2.0} fill="white" font-size="12" text-anchor="middle">
+                            { format!("{:.0}%", share * 100.0) }
+                        </text>
+                    </g>
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let rows = move || {
+        let totals = totals();
+        let all_hits: usize = totals.iter().map(|(_, (hits, _))| hits).sum();
+
+        totals
+            .into_iter()
+            .map(|(hitgroup, (hits, damage))| {
+                view! {
+                    <tr>
+                        <td>{ hitgroup }</td>
+                        <td>{ hits }</td>
+                        <td>{ format!("{:.1}%", hits as f32 * 100.0 / all_hits.max(1) as f32) }</td>
+                        <td>{ damage }</td>
+                    </tr>
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    view! {
+        class=style,
+        <div class="filters">
+            <select on:change=move |ev| set_player(event_target_value(&ev))>
+                <option value="">"All Players"</option>
+                { player_options }
+            </select>
+            <select on:change=move |ev| set_received(event_target_value(&ev) == "received")>
+                <option value="dealt">"Damage Dealt"</option>
+                <option value="received">"Damage Received"</option>
+            </select>
+            <select on:change=move |ev| set_weapon(event_target_value(&ev))>
+                <option value="">"All Weapons"</option>
+                { weapon_options }
+            </select>
+        </div>
+        <Suspense fallback=move || view! { <p>Loading Hitgroups...</p> }>
+            <div class="container">
+                <svg viewBox="0 0 200 400">
+                    { body }
+                </svg>
+                <table>
+                    <tr>
+                        <th>Hitgroup</th>
+                        <th>Hits</th>
+                        <th>Share</th>
+                        <th>Damage</th>
+                    </tr>
+                    { rows }
+                </table>
+            </div>
+        </Suspense>
+    }
+}
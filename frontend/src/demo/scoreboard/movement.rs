@@ -0,0 +1,109 @@
+use leptos::*;
+use leptos::Suspense;
+
+#[leptos::component]
+pub fn movement() -> impl leptos::IntoView {
+    let movement_resource = create_resource(leptos_router::use_params_map(), |params| async move {
+        let id = params.get("id").unwrap();
+
+        let res = reqwasm::http::Request::get(&format!("/api/demos/{}/analysis/movement", id))
+            .send()
+            .await
+            .unwrap();
+        res.json::<common::demo_analysis::Movement>()
+            .await
+            .unwrap_or_default()
+    });
+
+    // The selected round, or the whole match if empty
+    let (round, set_round) = create_signal(String::new());
+
+    let style = stylers::style! {
+        "Movement",
+        tr:nth-child(even) {
+            background-color: var(--color-surface-a10);
+        }
+        th, td {
+            padding: 0.3vh 1vw;
+        }
+        .places {
+            text-align: left;
+        }
+        select {
+            margin-bottom: 1vh;
+        }
+    };
+
+    let round_options = move || {
+        movement_resource
+            .get()
+            .unwrap_or_default()
+            .rounds
+            .into_iter()
+            .map(|r| view! { <option value=r.round.to_string()>{ format!("Round {}", r.round + 1) }</option> })
+            .collect::<Vec<_>>()
+    };
+
+    let rows = move || {
+        let result = movement_resource.get().unwrap_or_default();
+        let players = match round().parse::<u32>() {
+            Ok(number) => result
+                .rounds
+                .into_iter()
+                .find(|r| r.round == number)
+                .map(|r| r.players)
+                .unwrap_or_default(),
+            Err(_) => result.total,
+        };
+
+        players
+            .into_iter()
+            .map(|p| {
+                let places = p
+                    .places
+                    .iter()
+                    .take(3)
+                    .map(|t| format!("{} ({:.0}s)", t.place, t.seconds))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                view! {
+                    class=style,
+                    <tr>
+                        <td>{ p.name }</td>
+                        <td>{ format!("{:.0}", p.distance) }</td>
+                        <td>{ format!("{:.0}", p.average_speed) }</td>
+                        <td>{ format!("{:.0}s", p.still) }</td>
+                        <td>{ format!("{:.0}s", p.crouched) }</td>
+                        <td>{ format!("{:.0}s", p.walking) }</td>
+                        <td>{ format!("{:.0}s", p.running) }</td>
+                        <td class="places">{ places }</td>
+                    </tr>
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    view! {
+        class=style,
+        <Suspense fallback=move || view! { <p>Loading Movement...</p> }>
+            <select on:change=move |ev| set_round(event_target_value(&ev))>
+                <option value="">"All Rounds"</option>
+                { round_options }
+            </select>
+            <table>
+                <tr>
+                    <th>Player</th>
+                    <th>Distance</th>
+                    <th>"Average Speed"</th>
+                    <th>Still</th>
+                    <th>Crouched</th>
+                    <th>Walking</th>
+                    <th>Running</th>
+                    <th class="places">"Most Time At"</th>
+                </tr>
+                { rows }
+            </table>
+        </Suspense>
+    }
+}
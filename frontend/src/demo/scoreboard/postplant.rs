@@ -0,0 +1,147 @@
+use leptos::*;
+use leptos::Suspense;
+
+#[leptos::component]
+pub fn post_plant() -> impl leptos::IntoView {
+    let postplant_resource =
+        create_resource(leptos_router::use_params_map(), |params| async move {
+            let id = params.get("id").unwrap();
+
+            let res = reqwasm::http::Request::get(&format!("/api/demos/{}/analysis/postplant", id))
+                .send()
+                .await
+                .unwrap();
+            res.json::<common::demo_analysis::PostPlants>()
+                .await
+                .unwrap_or_default()
+        });
+
+    let style = stylers::style! {
+        "PostPlant",
+        tr:nth-child(even) {
+            background-color: var(--color-surface-a10);
+        }
+        th, td {
+            padding: 0.3vh 1vw;
+        }
+        .places {
+            text-align: left;
+        }
+        .total {
+            font-weight: bold;
+        }
+    };
+
+    // The success rate of the side the table is for
+    let situation_rows = move |situations: Vec<common::demo_analysis::PostPlantSituation>,
+                               retake: bool| {
+        situations
+                .into_iter()
+                .map(|s| {
+                    let wins = if retake {
+                        s.rounds - s.exploded - s.eliminated
+                    } else {
+                        s.exploded + s.eliminated
+                    };
+                    let total = s.players.is_none();
+                    let players = s
+                        .players
+                        .map(|(t, ct)| format!("{}v{}", t, ct))
+                        .unwrap_or_else(|| "All".to_owned());
+
+                    view! {
+                        class=style,
+                        <tr class:total=move || total>
+                            <td>{ s.site }</td>
+                            <td>{ players }</td>
+                            <td>{ s.rounds }</td>
+                            <td>{ format!("{}/{} ({:.0}%)", wins, s.rounds, wins as f32 * 100.0 / s.rounds.max(1) as f32) }</td>
+                            <td>{ s.defused }</td>
+                            <td>{ s.exploded }</td>
+                            <td>{ s.eliminated }</td>
+                        </tr>
+                    }
+                })
+                .collect::<Vec<_>>()
+    };
+
+    let situation_table = move |situations: Vec<common::demo_analysis::PostPlantSituation>,
+                                retake: bool| {
+        view! {
+            class=style,
+            <table>
+                <tr>
+                    <th>Site</th>
+                    <th>"T v CT"</th>
+                    <th>Rounds</th>
+                    <th>{ if retake { "Retakes Won" } else { "Post Plants Won" } }</th>
+                    <th>Defused</th>
+                    <th>Exploded</th>
+                    <th>"CTs Eliminated"</th>
+                </tr>
+                { situation_rows(situations, retake) }
+            </table>
+        }
+    };
+
+    let team_tables = move || {
+        postplant_resource
+            .get()
+            .unwrap_or_default()
+            .teams
+            .into_iter()
+            .map(|t| {
+                view! {
+                    class=style,
+                    <h3>{ format!("{} Post Plants", t.name) }</h3>
+                    { situation_table(t.post_plants, false) }
+                    <h3>{ format!("{} Retakes", t.name) }</h3>
+                    { situation_table(t.retakes, true) }
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let plant_rows = move || {
+        postplant_resource
+            .get()
+            .unwrap_or_default()
+            .plants
+            .into_iter()
+            .map(|p| {
+                view! {
+                    class=style,
+                    <tr>
+                        <td>{ p.round + 1 }</td>
+                        <td>{ p.team }</td>
+                        <td>{ p.site }</td>
+                        <td>{ format!("{}v{}", p.t_alive, p.ct_alive) }</td>
+                        <td>{ format!("{:.0}s", p.time_remaining) }</td>
+                        <td>{ p.outcome }</td>
+                        <td class="places">{ p.t_places.join(", ") }</td>
+                    </tr>
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    view! {
+        class=style,
+        <Suspense fallback=move || view! { <p>Loading Post Plants...</p> }>
+            { team_tables }
+            <h3>Plants</h3>
+            <table>
+                <tr>
+                    <th>Round</th>
+                    <th>Team</th>
+                    <th>Site</th>
+                    <th>"T v CT"</th>
+                    <th>"Time Left"</th>
+                    <th>Outcome</th>
+                    <th class="places">"T Positions"</th>
+                </tr>
+                { plant_rows }
+            </table>
+        </Suspense>
+    }
+}
@@ -0,0 +1,152 @@
+use leptos::*;
+use leptos::Suspense;
+
+#[leptos::component]
+pub fn round_types() -> impl leptos::IntoView {
+    let roundtypes_resource =
+        create_resource(leptos_router::use_params_map(), |params| async move {
+            let id = params.get("id").unwrap();
+
+            let res =
+                reqwasm::http::Request::get(&format!("/api/demos/{}/analysis/roundtypes", id))
+                    .send()
+                    .await
+                    .unwrap();
+            res.json::<common::demo_analysis::RoundTypes>()
+                .await
+                .unwrap_or_default()
+        });
+
+    let style = stylers::style! {
+        "RoundTypes",
+        tr:nth-child(even) {
+            background-color: var(--color-surface-a10);
+        }
+        th, td {
+            padding: 0.3vh 1vw;
+        }
+        .rounds td {
+            padding: 0.3vh 0.3vw;
+            text-align: center;
+        }
+        .won {
+            background-color: var(--color-primary-a20);
+        }
+    };
+
+    let percentage = |part: usize, total: usize| {
+        if total == 0 {
+            "-".to_owned()
+        } else {
+            format!(
+                "{}/{} ({:.0}%)",
+                part,
+                total,
+                part as f32 * 100.0 / total as f32
+            )
+        }
+    };
+
+    let summary_rows = move || {
+        roundtypes_resource
+            .get()
+            .unwrap_or_default()
+            .teams
+            .into_iter()
+            .map(|t| {
+                view! {
+                    class=style,
+                    <tr>
+                        <td>{ t.name }</td>
+                        <td>{ percentage(t.pistol_wins, t.pistol_rounds) }</td>
+                        <td>{ percentage(t.pistol_conversions, t.pistol_wins) }</td>
+                        <td>{ percentage(t.force_wins, t.force_rounds) }</td>
+                        <td>{ percentage(t.advantage_wins, t.advantage_rounds) }</td>
+                    </tr>
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let round_rows = move || {
+        let result = roundtypes_resource.get().unwrap_or_default();
+
+        let numbers = (1..=result.rounds.len())
+            .map(|r| view! { class=style, <th>{ r }</th> })
+            .collect::<Vec<_>>();
+        let post_plants = result
+            .rounds
+            .iter()
+            .map(|r| view! { class=style, <td>{ if r.post_plant { "Plant" } else { "" } }</td> })
+            .collect::<Vec<_>>();
+
+        let team_rows = result
+            .teams
+            .iter()
+            .map(|team| {
+                let buys = result
+                    .rounds
+                    .iter()
+                    .map(|r| {
+                        let buy = r.teams.iter().find(|t| t.team == team.team).cloned();
+                        let won = buy.as_ref().is_some_and(|b| b.won);
+                        let (text, title) = buy
+                            .map(|b| {
+                                let title = format!(
+                                    "{} on {}, ${} per player",
+                                    b.buy, b.side, b.equipment_value
+                                );
+                                (b.buy, title)
+                            })
+                            .unwrap_or_default();
+
+                        view! {
+                            class=style,
+                            <td class:won=move || won title=title>{ text }</td>
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                view! {
+                    class=style,
+                    <tr>
+                        <th>{ team.name.clone() }</th>
+                        { buys }
+                    </tr>
+                }
+            })
+            .collect::<Vec<_>>();
+
+        view! {
+            class=style,
+            <tr>
+                <th>Round</th>
+                { numbers }
+            </tr>
+            { team_rows }
+            <tr>
+                <th>Bomb</th>
+                { post_plants }
+            </tr>
+        }
+    };
+
+    view! {
+        class=style,
+        <Suspense fallback=move || view! { <p>Loading Round Types...</p> }>
+            <table>
+                <tr>
+                    <th>Team</th>
+                    <th>"Pistol Wins"</th>
+                    <th>"Pistol Conversions"</th>
+                    <th>"Force Buy Wins"</th>
+                    <th>"5v4 Conversions"</th>
+                </tr>
+                { summary_rows }
+            </table>
+            <table class="rounds">
+                { round_rows }
+            </table>
+        </Suspense>
+    }
+}
@@ -0,0 +1,115 @@
+use leptos::*;
+use leptos::Suspense;
+
+#[leptos::component]
+pub fn setups() -> impl leptos::IntoView {
+    let setups_resource = create_resource(leptos_router::use_params_map(), |params| async move {
+        let id = params.get("id").unwrap();
+
+        let res = reqwasm::http::Request::get(&format!("/api/demos/{}/analysis/setups", id))
+            .send()
+            .await
+            .unwrap();
+        res.json::<common::demo_analysis::Setups>()
+            .await
+            .unwrap_or_default()
+    });
+
+    let style = stylers::style! {
+        "Setups",
+        tr:nth-child(even) {
+            background-color: var(--color-surface-a10);
+        }
+        th, td {
+            padding: 0.3vh 1vw;
+        }
+        .places {
+            text-align: left;
+        }
+    };
+
+    let setup_rows = move |setups: Vec<common::demo_analysis::SetupEntry>| {
+        setups
+            .into_iter()
+            .map(|s| {
+                let win_rate = s.wins as f32 * 100.0 / s.rounds.max(1) as f32;
+                let rounds = if s.similar > 0 {
+                    format!("{} ({} similar)", s.rounds, s.similar)
+                } else {
+                    s.rounds.to_string()
+                };
+                view! {
+                    class=style,
+                    <tr>
+                        <td>{ s.side }</td>
+                        <td class="places">{ s.places.join(", ") }</td>
+                        <td>{ rounds }</td>
+                        <td>{ format!("{}/{} ({:.0}%)", s.wins, s.rounds, win_rate) }</td>
+                    </tr>
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let setup_table = move |setups: Vec<common::demo_analysis::SetupEntry>| {
+        view! {
+            class=style,
+            <table>
+                <tr>
+                    <th>Side</th>
+                    <th class="places">Positions</th>
+                    <th>Rounds</th>
+                    <th>Wins</th>
+                </tr>
+                { setup_rows(setups) }
+            </table>
+        }
+    };
+
+    let team_tables = move || {
+        setups_resource
+            .get()
+            .unwrap_or_default()
+            .teams
+            .into_iter()
+            .map(|t| {
+                // Only worth showing if the team was found in other demos as well
+                let all_demos = (t.demos > 1).then(|| {
+                    view! {
+                        class=style,
+                        <h4>{ format!("{} across {} demos", t.name, t.demos) }</h4>
+                        { setup_table(t.all_demos) }
+                    }
+                });
+
+                view! {
+                    class=style,
+                    <h3>{ t.name }</h3>
+                    { setup_table(t.setups) }
+                    { all_demos }
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let own_table = move || {
+        let result = setups_resource.get().unwrap_or_default();
+        if result.own.is_empty() {
+            return None;
+        }
+
+        Some(view! {
+            class=style,
+            <h3>{ format!("Your team on {} ({} demos)", result.map, result.own_demos) }</h3>
+            { setup_table(result.own) }
+        })
+    };
+
+    view! {
+        class=style,
+        <Suspense fallback=move || view! { <p>Loading Setups...</p> }>
+            { team_tables }
+            { own_table }
+        </Suspense>
+    }
+}
@@ -0,0 +1,110 @@
+use leptos::*;
+use leptos::Suspense;
+
+#[leptos::component]
+pub fn weapons() -> impl leptos::IntoView {
+    let weapons_resource = create_resource(leptos_router::use_params_map(), |params| async move {
+        let id = params.get("id").unwrap();
+
+        let res = reqwasm::http::Request::get(&format!("/api/demos/{}/analysis/weapons", id))
+            .send()
+            .await
+            .unwrap();
+        res.json::<Vec<common::demo_analysis::WeaponStats>>()
+            .await
+            .unwrap_or_default()
+    });
+
+    let style = stylers::style! {
+        "Weapons",
+        tr:nth-child(even) {
+            background-color: var(--color-surface-a10);
+        }
+        th, td {
+            padding: 0.3vh 1vw;
+        }
+        .ranges {
+            display: flex;
+            align-items: flex-end;
+            height: 4vh;
+            column-gap: 2px;
+        }
+        .range_bar {
+            width: 1.5vw;
+            background-color: var(--color-primary-a20);
+        }
+    };
+
+    let range_header = move || {
+        weapons_resource
+            .get()
+            .and_then(|w| w.first().cloned())
+            .map(|w| {
+                w.ranges
+                    .into_iter()
+                    .map(|(range, _)| range)
+                    .collect::<Vec<_>>()
+                    .join(" / ")
+            })
+            .unwrap_or_default()
+    };
+
+    let rows = move || {
+        weapons_resource
+            .get()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|w| {
+                let most = w.ranges.iter().map(|(_, kills)| *kills).max().unwrap_or(0);
+                let ranges = w
+                    .ranges
+                    .into_iter()
+                    .map(|(range, kills)| {
+                        view! {
+                            class=style,
+                            <div
+                                class="range_bar"
+                                title=format!("{}: {} kills", range, kills)
+                                style=format!("height: {}%;", kills * 100 / most.max(1))
+                            ></div>
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                view! {
+                    class=style,
+                    <tr>
+                        <td>{ w.weapon }</td>
+                        <td>{ w.kills }</td>
+                        <td>{ w.headshots }</td>
+                        <td>{ w.noscopes }</td>
+                        <td>{ w.wallbangs }</td>
+                        <td>{ w.smoke_kills }</td>
+                        <td>{ w.blind_kills }</td>
+                        <td><div class="ranges">{ ranges }</div></td>
+                    </tr>
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    view! {
+        class=style,
+        <Suspense fallback=move || view! { <p>Loading Weapons...</p> }>
+            <p>"Engagement ranges in game units: " { range_header }</p>
+            <table>
+                <tr>
+                    <th>Weapon</th>
+                    <th>Kills</th>
+                    <th>Headshots</th>
+                    <th>Noscopes</th>
+                    <th>Wallbangs</th>
+                    <th>Smoke Kills</th>
+                    <th>Blind Kills</th>
+                    <th>"Engagement Ranges"</th>
+                </tr>
+                { rows }
+            </table>
+        </Suspense>
+    }
+}
@@ -47,7 +47,7 @@ fn demo_list(
             display: inline-grid;
             width: 100%;
 
-            grid-template-columns: auto auto auto;
+            grid-template-columns: auto auto auto auto;
             row-gap: 1vh;
         }
 
@@ -62,6 +62,7 @@ fn demo_list(
             <span class="headers">Score</span>
             <span class="headers">Date</span>
             <span class="headers">Map</span>
+            <span class="headers">Duration</span>
 
             { move || demos.get().map(|d| d.done).unwrap_or_default().into_iter().enumerate().map(|(i, demo)| view! { <DemoListEntry demo idx=i+1 /> }).collect::<Vec<_>>() }
         </div>
@@ -75,7 +76,7 @@ fn demo_list_entry(demo: common::BaseDemoInfo, idx: usize) -> impl leptos::IntoV
         .entry {
             display: inline-block;
 
-            grid-column: 1 / 4;
+            grid-column: 1 / 5;
             width: 100%;
             height: 100%;
         }
@@ -84,7 +85,7 @@ fn demo_list_entry(demo: common::BaseDemoInfo, idx: usize) -> impl leptos::IntoV
             border-radius: 6px;
         }
 
-        .score, .map {
+        .score, .map, .duration {
             padding-left: 5px;
             padding-right: 5px;
 
@@ -101,6 +102,9 @@ fn demo_list_entry(demo: common::BaseDemoInfo, idx: usize) -> impl leptos::IntoV
         .map {
             grid-column: 3;
         }
+        .duration {
+            grid-column: 4;
+        }
 
         .date {
             display: inline-grid;
@@ -126,6 +130,11 @@ fn demo_list_entry(demo: common::BaseDemoInfo, idx: usize) -> impl leptos::IntoV
     let lost = move || enemy_score > player_score;
     let tie = move || player_score == enemy_score;
 
+    let duration = demo
+        .duration
+        .map(|d| format!("{}:{:02}", d / 60, d % 60))
+        .unwrap_or_default();
+
     view! {
         class=style,
             <span class="entry background_entry" style=format!("grid-row: {};", idx + 1)></span>
@@ -141,6 +150,7 @@ fn demo_list_entry(demo: common::BaseDemoInfo, idx: usize) -> impl leptos::IntoV
                 <span>{demo.uploaded_at.format("%H:%M:%S").to_string()}</span>
             </div>
             <span class="map" style=format!("grid-row: {};", idx + 1)>{demo.map}</span>
+            <span class="duration" style=format!("grid-row: {};", idx + 1)>{duration}</span>
             <a class="entry" href=format!("demo/{}/scoreboard", demo.id) style=format!("grid-row: {};", idx + 1)></a>
     }
 }
@@ -9,6 +9,12 @@ pub use navbar::TopBar;
 pub mod homepage;
 pub use homepage::Homepage;
 
+pub mod preview;
+pub use preview::LocalPreview;
+
+pub mod player;
+pub use player::Player;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DemoUploadStatus {
     Hidden,
@@ -144,6 +150,11 @@ pub fn upload_demo(
                 <input type="file" name="demo" id="demo"></input>
                 <input type="submit" value="Upload Demo" name="submit"></input>
             </Form>
+            <p>
+                <a href="/preview" on:click=move |_| update_shown(DemoUploadStatus::Hidden)>
+                    "Preview a demo without uploading it"
+                </a>
+            </p>
             <button on:click=move |_| update_shown(DemoUploadStatus::Hidden)>
                 Close
             </button>
@@ -19,14 +19,25 @@ fn main() {
 
                     <Routes>
                         <Route path="/" view=move || view! { <Homepage get_notification=get_reload_demos /> } />
+                        <Route path="/preview" view=frontend::LocalPreview />
+                        <Route path="/player/:steamid" view=frontend::Player />
                         <Route path="/demo/:id" view=Demo>
                             <Route path="scoreboard" view=frontend::demo::scoreboard::Scoreboard>
                                 <Route path="general" view=frontend::demo::scoreboard::general::General />
                                 <Route path="headtohead" view=frontend::demo::scoreboard::headtohead::HeadToHead />
+                                <Route path="callouts" view=frontend::demo::scoreboard::callouts::Callouts />
+                                <Route path="weapons" view=frontend::demo::scoreboard::weapons::Weapons />
+                                <Route path="hitgroups" view=frontend::demo::scoreboard::hitgroups::Hitgroups />
+                                <Route path="roundtypes" view=frontend::demo::scoreboard::roundtypes::RoundTypes />
+                                <Route path="setups" view=frontend::demo::scoreboard::setups::Setups />
+                                <Route path="movement" view=frontend::demo::scoreboard::movement::Movement />
+                                <Route path="postplant" view=frontend::demo::scoreboard::postplant::PostPlant />
                                 <Route path="" view=frontend::demo::scoreboard::general::General />
                             </Route>
                             <Route path="perround" view=frontend::demo::perround::PerRound />
                             <Route path="heatmaps" view=frontend::demo::heatmap::Heatmaps />
+                            <Route path="grenades" view=frontend::demo::grenades::Grenades />
+                            <Route path="replay" view=frontend::demo::replay::Replay />
                             <Route path="" view=frontend::demo::scoreboard::Scoreboard />
                         </Route>
                     </Routes>
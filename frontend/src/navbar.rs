@@ -20,7 +20,9 @@ fn steam_login(height: &'static str, width: &'static str) -> impl leptos::IntoVi
         match user_status.get() {
         Some(Ok(user)) => 
             view! {
-                <p>{user.name}</p>
+                <A href=format!("/player/{}", user.steamid)>
+                    <p>{user.name}</p>
+                </A>
             }.into_view(),
         _ => 
         view! {    
@@ -0,0 +1,251 @@
+use leptos::*;
+use leptos::Suspense;
+
+#[leptos::component]
+pub fn player() -> impl leptos::IntoView {
+    // The upload dates in the `YYYY-MM-DD` format of the date inputs, empty for no limit
+    let (from, set_from) = create_signal(String::new());
+    let (to, set_to) = create_signal(String::new());
+
+    let params = leptos_router::use_params_map();
+    let career_resource = create_resource(
+        move || (params.get(), from(), to()),
+        |(params, from, to)| async move {
+            let steam_id = params.get("steamid").cloned().unwrap_or_default();
+
+            let res = reqwasm::http::Request::get(&format!(
+                "/api/players/{}/stats?{}",
+                steam_id,
+                date_filters(&from, &to).join("&")
+            ))
+            .send()
+            .await
+            .unwrap();
+            if res.status() != 200 {
+                return None;
+            }
+            res.json::<common::PlayerCareer>().await.ok()
+        },
+    );
+
+    let style = stylers::style! {
+        "Player",
+        tr:nth-child(even) {
+            background-color: var(--color-surface-a10);
+        }
+        th, td {
+            padding: 0.3vh 1vw;
+        }
+        .filters {
+            margin-bottom: 1vh;
+        }
+        .filters > label {
+            margin-right: 1vw;
+        }
+    };
+
+    let content = move || {
+        let career = match career_resource.get().flatten() {
+            Some(c) => c,
+            None => {
+                return view! { <p>No analysed demos uploaded in this time range</p> }.into_view();
+            }
+        };
+
+        let map_names: Vec<String> = career.maps.iter().map(|m| m.map.clone()).collect();
+        let maps = career
+            .maps
+            .into_iter()
+            .map(|m| view! { <StatsRow label=m.map stats=m.stats /> })
+            .collect::<Vec<_>>();
+        let sides = career
+            .sides
+            .into_iter()
+            .map(|s| view! { <StatsRow label=s.side stats=s.stats /> })
+            .collect::<Vec<_>>();
+
+        view! {
+            class=style,
+            <h2>{ career.name }</h2>
+
+            <h3>Overall</h3>
+            <table>
+                <StatsHeader label="" />
+                <StatsRow label="Total".to_owned() stats=career.total />
+            </table>
+
+            <h3>Maps</h3>
+            <table>
+                <StatsHeader label="Map" />
+                { maps }
+            </table>
+
+            <h3>Sides</h3>
+            <table>
+                <StatsHeader label="Side" />
+                { sides }
+            </table>
+
+            <h3>Heatmap</h3>
+            <PlayerHeatmap steam_id=career.steam_id maps=map_names from to />
+        }
+        .into_view()
+    };
+
+    view! {
+        class=style,
+        <div>
+            <div class="filters">
+                <label>
+                    "Uploaded from "
+                    <input type="date" on:change=move |ev| set_from(event_target_value(&ev)) />
+                </label>
+                <label>
+                    "Uploaded to "
+                    <input type="date" on:change=move |ev| set_to(event_target_value(&ev)) />
+                </label>
+            </div>
+
+            <Suspense fallback=move || view! { <p>Loading Player</p> }>
+                { content }
+            </Suspense>
+        </div>
+    }
+}
+
+#[leptos::component]
+fn stats_header(label: &'static str) -> impl leptos::IntoView {
+    view! {
+        <tr>
+            <th>{ label }</th>
+            <th>Matches</th>
+            <th>Rounds</th>
+            <th>K/D</th>
+            <th>ADR</th>
+            <th>HS%</th>
+            <th>Rating</th>
+            <th>Win Rate</th>
+            <th>Round Win Rate</th>
+        </tr>
+    }
+}
+
+#[leptos::component]
+fn stats_row(label: String, stats: common::CareerStats) -> impl leptos::IntoView {
+    // The side splits don't have matches, as every match is played on both sides
+    let (matches, win_rate) = if stats.matches > 0 {
+        (
+            format!("{} ({}W {}L)", stats.matches, stats.wins, stats.losses),
+            format!("{:.1}%", stats.win_rate),
+        )
+    } else {
+        ("-".to_owned(), "-".to_owned())
+    };
+
+    view! {
+        <tr>
+            <td>{ label }</td>
+            <td>{ matches }</td>
+            <td>{ stats.rounds }</td>
+            <td>{ format!("{:.2} ({}/{})", stats.kd, stats.kills, stats.deaths) }</td>
+            <td>{ format!("{:.1}", stats.adr) }</td>
+            <td>{ format!("{:.1}%", stats.headshot_percentage) }</td>
+            <td>{ format!("{:.2}", stats.rating) }</td>
+            <td>{ win_rate }</td>
+            <td>{ format!("{:.1}%", stats.round_win_rate) }</td>
+        </tr>
+    }
+}
+
+/// The positions of the player summed up across all their demos on one map
+#[leptos::component]
+fn player_heatmap(
+    steam_id: String,
+    maps: Vec<String>,
+    from: ReadSignal<String>,
+    to: ReadSignal<String>,
+) -> impl leptos::IntoView {
+    let (map, set_map) = create_signal(maps.first().cloned().unwrap_or_default());
+    // Empty for both sides
+    let (side, set_side) = create_signal(String::new());
+    let (team, set_team) = create_signal(false);
+
+    let style = stylers::style! {
+        "PlayerHeatmap",
+        .heatmap_image {
+            width: min(40vw, 60vh);
+            height: min(40vw, 60vh);
+            display: block;
+            position: relative;
+            margin-top: 1vh;
+        }
+        .heatmap_image > * {
+            position: absolute;
+            width: min(40vw, 60vh);
+            height: min(40vw, 60vh);
+        }
+        .heatmap_image > .heatmap {
+            opacity: 0.5;
+        }
+        select, label {
+            margin-right: 1vw;
+        }
+    };
+
+    let heatmap_url = move || {
+        let mut filters = date_filters(&from(), &to());
+        if !side().is_empty() {
+            filters.push(format!("side={}", side()));
+        }
+        if team() {
+            filters.push("team=true".to_owned());
+        }
+
+        format!(
+            "/api/players/{}/heatmaps/{}?{}",
+            steam_id,
+            map(),
+            filters.join("&")
+        )
+    };
+
+    let map_options = maps
+        .into_iter()
+        .map(|m| view! { <option value=m.clone()>{ m.clone() }</option> })
+        .collect::<Vec<_>>();
+
+    view! {
+        class=style,
+        <div>
+            <select on:change=move |ev| set_map(event_target_value(&ev))>
+                { map_options }
+            </select>
+            <select on:change=move |ev| set_side(event_target_value(&ev))>
+                <option value="">Both Sides</option>
+                <option value="T">T</option>
+                <option value="CT">CT</option>
+            </select>
+            <label>
+                <input type="checkbox" on:change=move |ev| set_team(event_target_checked(&ev)) />
+                " Include Teammates"
+            </label>
+
+            <div class="heatmap_image">
+                <img class="radar" src=move || format!("/static/minimaps/{}.png", map()) />
+                <img class="heatmap" src=heatmap_url />
+            </div>
+        </div>
+    }
+}
+
+/// The query parameters for the date inputs that are set
+fn date_filters(from: &str, to: &str) -> Vec<String> {
+    let mut filters = Vec::new();
+    if !from.is_empty() {
+        filters.push(format!("from={}", from));
+    }
+    if !to.is_empty() {
+        filters.push(format!("to={}", to));
+    }
+    filters
+}
@@ -0,0 +1,194 @@
+use leptos::*;
+
+use crate::demo::{heatmap::HeatmapView, scoreboard::TeamScoreboard, CurrentDemoName};
+
+#[derive(Debug, Clone)]
+struct Preview {
+    map: String,
+    complete: bool,
+    teams: Vec<common::demo_analysis::ScoreBoardTeam>,
+    heatmaps: Vec<common::demo_analysis::PlayerHeatmap>,
+}
+
+#[derive(Debug, Clone)]
+enum PreviewState {
+    Empty,
+    Analysing,
+    Failed(String),
+    Done(Preview),
+}
+
+/// Analyses a local demo file in the browser, without uploading it
+#[leptos::component]
+pub fn local_preview() -> impl leptos::IntoView {
+    let (state, set_state) = create_signal(PreviewState::Empty);
+
+    let (map, set_map) = create_signal(String::new());
+    provide_context(CurrentDemoName(map));
+
+    let on_change = move |ev: leptos::ev::Event| {
+        let input: leptos::web_sys::HtmlInputElement = event_target(&ev);
+        let file = match input.files().and_then(|files| files.get(0)) {
+            Some(f) => f,
+            None => return,
+        };
+
+        set_state(PreviewState::Analysing);
+        spawn_local(async move {
+            let buffer = match wasm_bindgen_futures::JsFuture::from(file.array_buffer()).await {
+                Ok(b) => b,
+                Err(e) => {
+                    set_state(PreviewState::Failed(format!("Reading file: {:?}", e)));
+                    return;
+                }
+            };
+            let data = js_sys::Uint8Array::new(&buffer).to_vec();
+
+            // The analysis blocks the page, so the browser gets a chance to show the loading
+            // state before it starts
+            set_timeout(
+                move || match analyse(&data) {
+                    Ok(preview) => {
+                        set_map(preview.map.clone());
+                        set_state(PreviewState::Done(preview));
+                    }
+                    Err(e) => set_state(PreviewState::Failed(e)),
+                },
+                std::time::Duration::ZERO,
+            );
+        });
+    };
+
+    let style = stylers::style! {
+        "Local-Preview",
+        .container {
+            margin: 2vh 1vw;
+        }
+
+        .partial_warning {
+            padding: 0.5vh 0.5vw;
+            color: #1a1a1a;
+            background-color: #d5a13a;
+        }
+    };
+
+    let content = move || match state.get() {
+        PreviewState::Empty => view! { <p>"Select a demo file to preview it"</p> }.into_view(),
+        PreviewState::Analysing => view! { <p>"Analysing Demo"</p> }.into_view(),
+        PreviewState::Failed(e) => {
+            view! { <p>{ format!("Could not analyse Demo: {}", e) }</p> }.into_view()
+        }
+        PreviewState::Done(preview) => {
+            let teams = preview.teams.into_iter().map(|team| {
+                view! {
+                    <TeamScoreboard value=team.players team_name=format!("{} - {}", team.display_name(), team.score) />
+                }
+            }).collect::<Vec<_>>();
+
+            view! {
+                class=style,
+                <h3>{ preview.map }</h3>
+                { (!preview.complete).then(|| view! {
+                    class=style,
+                    <p class="partial_warning">"Partial demo: the demo was cut short, so the analysis only covers the part of the match that could be read"</p>
+                }) }
+                { teams }
+                <HeatmapView heatmaps=preview.heatmaps />
+            }.into_view()
+        }
+    };
+
+    view! {
+        class=style,
+        <div class="container">
+            <h2>Local Preview</h2>
+            <p>"The demo is analysed in your browser and is not uploaded"</p>
+            <input type="file" accept=".dem" on:change=on_change />
+
+            { content }
+        </div>
+    }
+}
+
+fn analyse(data: &[u8]) -> Result<Preview, String> {
+    use base64::prelude::Engine;
+
+    let result = analysis::endofgame::parse(data).map_err(|_| "Could not parse demo".to_owned())?;
+
+    let mut teams = std::collections::BTreeMap::new();
+    for (info, stats) in result.players.iter() {
+        let team =
+            teams
+                .entry(info.team)
+                .or_insert_with(|| common::demo_analysis::ScoreBoardTeam {
+                    number: info.team as u32,
+                    score: result
+                        .teams
+                        .get(&info.team)
+                        .map(|t| t.end_score as i16)
+                        .unwrap_or(0),
+                    players: Vec::new(),
+                    clan_name: result
+                        .teams
+                        .get(&info.team)
+                        .and_then(|t| t.clan_name.clone()),
+                });
+
+        team.players.push(common::demo_analysis::ScoreBoardPlayer {
+            name: info.name.clone(),
+            kills: stats.kills,
+            deaths: stats.deaths,
+            damage: stats.damage,
+            assists: stats.assists,
+            rounds_played: None,
+            wallbangs: None,
+            smoke_kills: None,
+            blind_kills: None,
+        });
+    }
+
+    // Heatmaps can only be shown for maps with a known minimap
+    let heatmaps = match analysis::minimap::MINIMAP_COORDINATES.get(&result.map) {
+        Some(minimap) => {
+            let config = analysis::heatmap::Config { cell_size: 5.0 };
+            let heatmaps = analysis::heatmap::parse(&config, data)
+                .map_err(|_| "Could not generate heatmaps".to_owned())?;
+
+            heatmaps
+                .player_heatmaps
+                .into_iter()
+                .filter_map(|mut player_heatmap| {
+                    let steam_id = player_heatmap.steam_id.to_string();
+                    let name = result
+                        .players
+                        .iter()
+                        .find(|(info, _)| info.steam_id == steam_id)
+                        .map(|(info, _)| info.name.clone())?;
+
+                    minimap.fit(&mut player_heatmap.heatmap);
+
+                    let mut buffer = std::io::Cursor::new(Vec::new());
+                    player_heatmap
+                        .heatmap
+                        .as_image()
+                        .write_to(&mut buffer, image::ImageFormat::Png)
+                        .ok()?;
+
+                    Some(common::demo_analysis::PlayerHeatmap {
+                        name,
+                        team: player_heatmap.team,
+                        png_data: base64::prelude::BASE64_STANDARD.encode(buffer.into_inner()),
+                    })
+                })
+                .collect()
+        }
+        None => Vec::new(),
+    };
+
+    Ok(Preview {
+        map: result.map,
+        complete: result.complete,
+        teams: teams.into_values().collect(),
+        heatmaps,
+    })
+}
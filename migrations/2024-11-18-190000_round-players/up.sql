@@ -0,0 +1,14 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS demo_round_players (
+  demo_id TEXT NOT NULL,
+  round_number int2 NOT NULL,
+  steam_id TEXT NOT NULL,
+  team int2 NOT NULL,
+  kills int2 NOT NULL,
+  damage_dealt int2 NOT NULL,
+  damage_received int2 NOT NULL,
+  survived BOOLEAN NOT NULL,
+  end_health int2 NOT NULL,
+  equipment_saved int4 NOT NULL,
+  PRIMARY KEY (demo_id, round_number, steam_id)
+);
@@ -0,0 +1,10 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE demo_info
+  DROP COLUMN server_name,
+  DROP COLUMN demo_type,
+  DROP COLUMN tick_rate,
+  DROP COLUMN playback_ticks,
+  DROP COLUMN game_mode,
+  DROP COLUMN match_start_tick,
+  DROP COLUMN network_protocol,
+  DROP COLUMN build_number;
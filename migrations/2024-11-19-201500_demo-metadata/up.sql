@@ -0,0 +1,10 @@
+-- Your SQL goes here
+ALTER TABLE demo_info
+  ADD COLUMN IF NOT EXISTS server_name TEXT,
+  ADD COLUMN IF NOT EXISTS demo_type TEXT,
+  ADD COLUMN IF NOT EXISTS tick_rate int4,
+  ADD COLUMN IF NOT EXISTS playback_ticks int4,
+  ADD COLUMN IF NOT EXISTS game_mode TEXT,
+  ADD COLUMN IF NOT EXISTS match_start_tick int4,
+  ADD COLUMN IF NOT EXISTS network_protocol int4,
+  ADD COLUMN IF NOT EXISTS build_number int4;
@@ -0,0 +1,5 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS demo_timeline (
+  demo_id TEXT PRIMARY KEY,
+  events JSON NOT NULL
+);
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE demo_players
+  DROP COLUMN join_tick,
+  DROP COLUMN leave_tick,
+  DROP COLUMN rounds_played;
@@ -0,0 +1,5 @@
+-- Your SQL goes here
+ALTER TABLE demo_players
+  ADD COLUMN IF NOT EXISTS join_tick int4,
+  ADD COLUMN IF NOT EXISTS leave_tick int4,
+  ADD COLUMN IF NOT EXISTS rounds_played int2;
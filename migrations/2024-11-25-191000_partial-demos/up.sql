@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TABLE demo_info ADD COLUMN IF NOT EXISTS partial BOOLEAN NOT NULL DEFAULT false;
@@ -0,0 +1,5 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS demo_quality (
+  demo_id TEXT PRIMARY KEY,
+  report JSON NOT NULL
+);
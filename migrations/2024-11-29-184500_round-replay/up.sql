@@ -0,0 +1,8 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS demo_round_replay (
+  demo_id TEXT NOT NULL,
+  round_number int2 NOT NULL,
+  sample_rate int2 NOT NULL,
+  replay JSON NOT NULL,
+  PRIMARY KEY (demo_id, round_number)
+);
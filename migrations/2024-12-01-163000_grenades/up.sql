@@ -0,0 +1,5 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS demo_grenades (
+  demo_id TEXT PRIMARY KEY,
+  grenades JSON NOT NULL
+);
@@ -0,0 +1,5 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS demo_hitgroups (
+  demo_id TEXT PRIMARY KEY,
+  players JSON NOT NULL
+);
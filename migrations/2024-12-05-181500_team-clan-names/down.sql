@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE demo_teams
+  DROP COLUMN clan_name;
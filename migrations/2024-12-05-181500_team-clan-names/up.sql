@@ -0,0 +1,3 @@
+-- Your SQL goes here
+ALTER TABLE demo_teams
+  ADD COLUMN IF NOT EXISTS clan_name TEXT;
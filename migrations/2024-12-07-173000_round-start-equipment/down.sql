@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE demo_round_players
+  DROP COLUMN start_equipment;
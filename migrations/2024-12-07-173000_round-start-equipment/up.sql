@@ -0,0 +1,3 @@
+-- Your SQL goes here
+ALTER TABLE demo_round_players
+  ADD COLUMN IF NOT EXISTS start_equipment int4;
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE demo_round
+  DROP COLUMN alive;
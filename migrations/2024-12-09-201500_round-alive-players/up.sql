@@ -0,0 +1,3 @@
+-- Your SQL goes here
+ALTER TABLE demo_round
+  ADD COLUMN IF NOT EXISTS alive JSON;
@@ -0,0 +1,5 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS demo_setups (
+  demo_id TEXT PRIMARY KEY,
+  sides JSON NOT NULL
+);
@@ -0,0 +1,5 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS demo_post_plants (
+  demo_id TEXT PRIMARY KEY,
+  plants JSON NOT NULL
+);
@@ -0,0 +1,2 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE demo_info DROP COLUMN stat_mismatches;
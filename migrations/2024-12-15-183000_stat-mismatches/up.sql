@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TABLE demo_info ADD COLUMN IF NOT EXISTS stat_mismatches JSON;